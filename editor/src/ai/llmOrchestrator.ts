@@ -67,6 +67,18 @@ Script component rules (IMPORTANT):
     — Physics:
     engine.getVelocity(id)               → [x, y, z]  (requires rigidbody)
     engine.setVelocity(id, x, y, z)      (requires rigidbody)
+    engine.applyForce(id, x, y, z)       (newtons, this frame only — call every frame for a steady push)
+    engine.applyImpulse(id, x, y, z)     (instant velocity change = impulse / mass)
+    engine.applyTorque(id, x, y, z)      engine.getAngularVelocity(id) / setAngularVelocity(id, x, y, z)
+    engine.setGravityScale(id, scale)    engine.setGravity(x, y, z)  (world, default [0, -9.8, 0])
+    engine.isSleeping(id)                → boolean  (body at rest, skipped until a contact, impulse or setPosition/setVelocity)
+    engine.wakeUp(id)                    (wakes the body and the stack it rests in)
+    engine.setCharacterIntent(id, x, z, jump?)  (non-player CharacterController: walk direction, length <= 1, kept until next call)
+    engine.addBallJoint(id, target, [px, py, pz])  (target id or null = world; pivot in world space)
+    engine.addHingeJoint(id, target, pivot, axis, [minDeg, maxDeg]?)
+    engine.addDistanceJoint(id, target, anchor, targetAnchor, length?)  (anchors local to each body)
+    engine.addFixedJoint(id, target)
+    engine.removeJoint(id)
     engine.getKey(key)                   → boolean  (e.g. getKey('arrowleft'))
     — Scene:
     engine.getEntityByTag(tag)           → number | null
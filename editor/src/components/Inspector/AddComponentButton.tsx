@@ -27,6 +27,10 @@ const DEFAULT_VALUES: Required<EntityComponents> = {
   pointLight:       { r: 1, g: 1, b: 1, intensity: 5.0 },
   directionalLight: { dx: 0.3, dy: -1, dz: 0.5, r: 1, g: 0.95, b: 0.8, intensity: 100, coneAngle: 30 },
   isPlayer:         true,
+  characterController: {
+    speed: 5, acceleration: 60, airControl: 0.3, jumpHeight: 1.25,
+    stepHeight: 0.3, slopeLimit: 45, coyoteTime: 0.1, jumpBuffer: 0.1,
+  },
   script:           '// Script body — runs every frame in Play mode.\n// getPosition/setPosition = LOCAL space (relative to parent).\n// Use getWorldPosition/setWorldPosition for world space.\n\n// Bobbing example (works on child entities too):\nvar baseY = null;\nif (baseY === null) baseY = engine.getPosition(entity.id)[1];\nvar [lx, , lz] = engine.getPosition(entity.id);\nengine.setPosition(entity.id, lx, baseY + Math.sin(Date.now() * 0.003) * 0.05, lz);',
   camera:           { fov: 60, near: 0.1, far: 1000, isActive: false, followEntity: false },
   particle:         {
@@ -85,6 +89,8 @@ export default function AddComponentButton({ entityId }: { entityId: EntityId })
       }
       case 'isPlayer': {
         bridge.setPlayer(entityId);
+        setComponent(entityId, 'characterController', { ...DEFAULT_VALUES.characterController });
+        bridge.setCharacterController(entityId, DEFAULT_VALUES.characterController);
         break;
       }
       case 'camera': {
@@ -4,23 +4,34 @@ import { useComponentStore } from '../../../store/componentStore';
 import { bridge } from '../../../engine/engineBridge';
 import type { EntityId, ColliderData } from '../../../engine/types';
 
+const LAYER_FIELDS = [
+  { key: 'layer', label: 'Layer', fallback: 0x1 },
+  { key: 'mask',  label: 'Mask',  fallback: 0xFFFFFFFF },
+] as const;
+
+const toHex = (bits: number) => '0x' + (bits >>> 0).toString(16).toUpperCase();
+
 export default function ColliderPanel({ entityId }: { entityId: EntityId }) {
   const { getComponents, setComponent, removeComponent } = useComponentStore();
   const col: ColliderData = getComponents(entityId).collider ?? { hx: 0.5, hy: 0.5, hz: 0.5 };
 
   const apply = (next: ColliderData) => {
-    setComponent(entityId, 'collider', next);
-    bridge.addCollider(entityId, next.hx, next.hy, next.hz);
+    // Sans mesh custom, retombe sur la boîte.
+    const mesh = next.mesh === true && bridge.addMeshCollider(entityId);
+    if (!mesh) bridge.addCollider(entityId, next.hx, next.hy, next.hz);
+    setComponent(entityId, 'collider', { ...next, mesh });
+    bridge.setColliderTrigger(entityId, next.isTrigger ?? false);
+    bridge.setColliderLayers(entityId, next.layer ?? 0x1, next.mask ?? 0xFFFFFFFF);
   };
 
   return (
-    <PanelSection title="Box Collider" onRemove={() => removeComponent(entityId, 'collider')}>
+    <PanelSection title={col.mesh ? 'Mesh Collider' : 'Box Collider'} onRemove={() => removeComponent(entityId, 'collider')}>
       <div style={{ display: 'flex', gap: 6, marginBottom: 6 }}>
         <button
           onClick={() => {
             bridge.fitColliderToMesh(entityId, 0.05);
             const [hx, hy, hz] = bridge.getCollider(entityId);
-            apply({ hx, hy, hz });
+            apply({ ...col, hx, hy, hz });
           }}
           style={{
             background: 'var(--bg-hover)',
@@ -36,7 +47,7 @@ export default function ColliderPanel({ entityId }: { entityId: EntityId }) {
           Fit To Mesh
         </button>
       </div>
-      {(['hx', 'hy', 'hz'] as const).map(axis => (
+      {!col.mesh && (['hx', 'hy', 'hz'] as const).map(axis => (
         <div key={axis} style={{ display: 'flex', alignItems: 'center', marginBottom: 3, fontSize: 11, gap: 4 }}>
           <span style={{ color: 'var(--text-dim)', width: 70, flexShrink: 0 }}>Half {axis.toUpperCase()}</span>
           <input
@@ -57,6 +68,43 @@ export default function ColliderPanel({ entityId }: { entityId: EntityId }) {
           />
         </div>
       ))}
+      <label style={{ display: 'flex', alignItems: 'center', gap: 6, fontSize: 11, cursor: 'pointer', marginTop: 4 }}>
+        <input type="checkbox" checked={col.isTrigger ?? false} onChange={() => apply({ ...col, isTrigger: !col.isTrigger })} />
+        <span style={{ color: 'var(--text)' }}>Is Trigger</span>
+      </label>
+      <label
+        style={{ display: 'flex', alignItems: 'center', gap: 6, fontSize: 11, cursor: 'pointer', marginTop: 4 }}
+        title="Triangles du mesh custom (corps statique ou kinematic)"
+      >
+        <input type="checkbox" checked={col.mesh ?? false} onChange={() => apply({ ...col, mesh: !col.mesh })} />
+        <span style={{ color: 'var(--text)' }}>Use Mesh Triangles</span>
+      </label>
+      {LAYER_FIELDS.map(({ key, label, fallback }) => (
+        <div key={key} style={{ display: 'flex', alignItems: 'center', marginTop: 3, fontSize: 11, gap: 4 }}>
+          <span style={{ color: 'var(--text-dim)', width: 70, flexShrink: 0 }}>{label}</span>
+          <input
+            key={col[key] ?? fallback}
+            type="text"
+            defaultValue={toHex(col[key] ?? fallback)}
+            onBlur={e => {
+              const bits = Number(e.target.value.trim());
+              if (Number.isInteger(bits) && bits >= 0 && bits <= 0xFFFFFFFF) apply({ ...col, [key]: bits });
+              else e.target.value = toHex(col[key] ?? fallback);
+            }}
+            title="Bits hexadécimaux (0x...) ou décimaux"
+            style={{
+              width: 90,
+              background: 'var(--bg-hover)',
+              color: 'var(--text)',
+              border: '1px solid var(--border)',
+              borderRadius: 3,
+              fontSize: 11,
+              padding: '1px 4px',
+              fontFamily: 'monospace',
+            }}
+          />
+        </div>
+      ))}
     </PanelSection>
   );
 }
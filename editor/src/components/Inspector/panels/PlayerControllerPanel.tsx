@@ -2,24 +2,73 @@ import React, { useEffect } from 'react';
 import PanelSection from './PanelSection';
 import { useComponentStore } from '../../../store/componentStore';
 import { bridge } from '../../../engine/engineBridge';
-import type { EntityId } from '../../../engine/types';
+import type { EntityId, CharacterControllerData } from '../../../engine/types';
+
+const DEFAULTS: CharacterControllerData = {
+  speed: 5, acceleration: 60, airControl: 0.3, jumpHeight: 1.25,
+  stepHeight: 0.3, slopeLimit: 45, coyoteTime: 0.1, jumpBuffer: 0.1,
+};
+
+const FIELDS = [
+  { key: 'speed',        label: 'Speed',        step: 0.5,  min: 0, max: Infinity },
+  { key: 'acceleration', label: 'Acceleration', step: 5,    min: 0, max: Infinity },
+  { key: 'airControl',   label: 'Air Control',  step: 0.05, min: 0, max: 1 },
+  { key: 'jumpHeight',   label: 'Jump Height',  step: 0.1,  min: 0, max: Infinity },
+  { key: 'stepHeight',   label: 'Step Height',  step: 0.05, min: 0, max: Infinity },
+  { key: 'slopeLimit',   label: 'Slope Limit',  step: 1,    min: 0, max: 89 },
+  { key: 'coyoteTime',   label: 'Coyote Time',  step: 0.05, min: 0, max: Infinity },
+  { key: 'jumpBuffer',   label: 'Jump Buffer',  step: 0.05, min: 0, max: Infinity },
+] as const;
 
 export default function PlayerControllerPanel({ entityId }: { entityId: EntityId }) {
-  const { removeComponent } = useComponentStore();
+  const { getComponents, setComponent, removeComponent } = useComponentStore();
+  const cc: CharacterControllerData = getComponents(entityId).characterController ?? DEFAULTS;
 
   useEffect(() => {
     bridge.setPlayer(entityId);
   }, [entityId]);
 
+  const apply = (next: CharacterControllerData) => {
+    setComponent(entityId, 'characterController', next);
+    bridge.setCharacterController(entityId, next);
+  };
+
   return (
     <PanelSection
       title="Player Controller"
-      onRemove={() => removeComponent(entityId, 'isPlayer')}
+      onRemove={() => {
+        removeComponent(entityId, 'isPlayer');
+        removeComponent(entityId, 'characterController');
+        bridge.removeCharacterController(entityId);
+      }}
     >
-      <div style={{ fontSize: 11, color: 'var(--text-dim)', lineHeight: 1.6 }}>
-        Cette entité est le joueur FPS.<br />
+      <div style={{ fontSize: 11, color: 'var(--text-dim)', lineHeight: 1.6, marginBottom: 4 }}>
         En Play : WASD + souris, Espace = saut.
       </div>
+      {FIELDS.map(f => (
+        <div key={f.key} style={{ display: 'flex', alignItems: 'center', marginBottom: 3, fontSize: 11, gap: 4 }}>
+          <span style={{ color: 'var(--text-dim)', width: 70, flexShrink: 0 }}>{f.label}</span>
+          <input
+            type="number"
+            step={f.step}
+            min={f.min}
+            value={cc[f.key]}
+            onChange={e => {
+              const v = parseFloat(e.target.value);
+              apply({ ...cc, [f.key]: Math.min(f.max, Math.max(f.min, Number.isFinite(v) ? v : DEFAULTS[f.key])) });
+            }}
+            style={{
+              width: 60,
+              background: 'var(--bg-hover)',
+              color: 'var(--text)',
+              border: '1px solid var(--border)',
+              borderRadius: 3,
+              fontSize: 11,
+              padding: '1px 4px',
+            }}
+          />
+        </div>
+      ))}
     </PanelSection>
   );
 }
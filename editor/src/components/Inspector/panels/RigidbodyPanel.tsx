@@ -2,24 +2,76 @@ import React from 'react';
 import PanelSection from './PanelSection';
 import { useComponentStore } from '../../../store/componentStore';
 import { bridge } from '../../../engine/engineBridge';
-import type { EntityId } from '../../../engine/types';
+import type { EntityId, RigidbodyData } from '../../../engine/types';
+
+const FIELDS = [
+  { key: 'mass',        label: 'Mass',        def: 1,   min: 0.001, max: Infinity },
+  { key: 'restitution', label: 'Restitution', def: 0,   min: 0,     max: 1 },
+  { key: 'friction',    label: 'Friction',    def: 0.5, min: 0,     max: Infinity },
+  { key: 'linearDamping',  label: 'Lin. Damp',  def: 0,    min: 0,         max: Infinity },
+  { key: 'angularDamping', label: 'Ang. Damp',  def: 0.05, min: 0,         max: Infinity },
+  { key: 'gravityScale',   label: 'Gravity ×',  def: 1,    min: -Infinity, max: Infinity },
+] as const;
 
 export default function RigidbodyPanel({ entityId }: { entityId: EntityId }) {
   const { getComponents, setComponent, removeComponent } = useComponentStore();
-  const rb = getComponents(entityId).rigidbody ?? { isStatic: true };
+  const rb: RigidbodyData = getComponents(entityId).rigidbody ?? { isStatic: true };
 
-  const toggle = () => {
-    const next = { isStatic: !rb.isStatic };
+  const apply = (next: RigidbodyData) => {
     setComponent(entityId, 'rigidbody', next);
     bridge.addRigidBody(entityId, next.isStatic);
+    if (next.isKinematic && !next.isStatic) bridge.setBodyType(entityId, 'kinematic');
+    bridge.setCcd(entityId, next.ccd ?? false);
+    bridge.setRigidBodyMaterial(entityId, next.mass ?? 1, next.restitution ?? 0, next.friction ?? 0.5);
+    bridge.setDamping(entityId, next.linearDamping ?? 0, next.angularDamping ?? 0.05);
+    bridge.setGravityScale(entityId, next.gravityScale ?? 1);
   };
 
   return (
     <PanelSection title="Rigidbody" onRemove={() => removeComponent(entityId, 'rigidbody')}>
-      <label style={{ display: 'flex', alignItems: 'center', gap: 6, fontSize: 11, cursor: 'pointer' }}>
-        <input type="checkbox" checked={rb.isStatic} onChange={toggle} />
+      <label style={{ display: 'flex', alignItems: 'center', gap: 6, fontSize: 11, cursor: 'pointer', marginBottom: 4 }}>
+        <input type="checkbox" checked={rb.isStatic} onChange={() => apply({ ...rb, isStatic: !rb.isStatic })} />
         <span style={{ color: 'var(--text)' }}>Is Static</span>
       </label>
+      {!rb.isStatic && (
+        <label style={{ display: 'flex', alignItems: 'center', gap: 6, fontSize: 11, cursor: 'pointer', marginBottom: 4 }}>
+          <input type="checkbox" checked={rb.isKinematic ?? false} onChange={() => apply({ ...rb, isKinematic: !rb.isKinematic })} />
+          <span style={{ color: 'var(--text)' }}>Is Kinematic</span>
+        </label>
+      )}
+      {!rb.isStatic && !rb.isKinematic && (
+        <label
+          style={{ display: 'flex', alignItems: 'center', gap: 6, fontSize: 11, cursor: 'pointer', marginBottom: 4 }}
+          title="Continuous collision detection: fast bodies stop at walls instead of tunneling through"
+        >
+          <input type="checkbox" checked={rb.ccd ?? false} onChange={() => apply({ ...rb, ccd: !rb.ccd })} />
+          <span style={{ color: 'var(--text)' }}>Continuous (CCD)</span>
+        </label>
+      )}
+      {FIELDS.map(f => (
+        <div key={f.key} style={{ display: 'flex', alignItems: 'center', marginBottom: 3, fontSize: 11, gap: 4 }}>
+          <span style={{ color: 'var(--text-dim)', width: 70, flexShrink: 0 }}>{f.label}</span>
+          <input
+            type="number"
+            step={0.1}
+            min={f.min}
+            value={rb[f.key] ?? f.def}
+            onChange={e => {
+              const v = parseFloat(e.target.value);
+              apply({ ...rb, [f.key]: Math.min(f.max, Math.max(f.min, Number.isFinite(v) ? v : f.def)) });
+            }}
+            style={{
+              width: 60,
+              background: 'var(--bg-hover)',
+              color: 'var(--text)',
+              border: '1px solid var(--border)',
+              borderRadius: 3,
+              fontSize: 11,
+              padding: '1px 4px',
+            }}
+          />
+        </div>
+      ))}
     </PanelSection>
   );
 }
@@ -132,8 +132,11 @@ export default function GizmoOverlay({ width, height }: { width: number; height:
         return;
       }
 
-      // Click-to-select: find nearest entity on screen
+      // Click-to-select: collider under the cursor, else nearest entity on screen
       if (!bridge.isReady) return;
+      const ray = bridge.screenRay((e.offsetX / width) * 2 - 1, 1 - (e.offsetY / height) * 2);
+      const hit = ray && bridge.raycast(ray.origin, ray.dir, 1000);
+      if (hit) { select(hit.entity); return; }
       const vp = bridge.getViewProj();
       let bestId: number | null = null;
       let bestDist = 20;
@@ -1,5 +1,5 @@
 import init, { World } from '../../../engine-core/pkg/engine_core.js';
-import type { EntityId, Transform } from './types';
+import type { CharacterControllerData, CollisionEvent, CollisionPhase, EntityId, RayHit, Transform } from './types';
 
 class EngineBridge {
   private world: World | null = null;
@@ -85,6 +85,17 @@ class EngineBridge {
     };
   }
 
+  /** Tick de la dernière modification de Transform (synchro incrémentale). */
+  getTransformChangeTick(): number {
+    return this.world?.get_transform_change_tick() ?? 0;
+  }
+
+  /** Entités dont le Transform a changé depuis `tick` (cf. getTransformChangeTick). */
+  getTransformsChangedSince(tick: number): EntityId[] {
+    if (!this.world) return [];
+    return Array.from(this.world.get_transforms_changed_since(tick));
+  }
+
   setPosition(id: EntityId, x: number, y: number, z: number): void {
     this.world?.set_position(id, x, y, z);
   }
@@ -173,10 +184,51 @@ class EngineBridge {
     this.world?.add_rigid_body(entityId, isStatic);
   }
 
+  setBodyType(entityId: EntityId, bodyType: 'dynamic' | 'static' | 'kinematic'): void {
+    this.world?.set_body_type(entityId, bodyType);
+  }
+
+  setCcd(entityId: EntityId, enabled: boolean): void {
+    this.world?.set_ccd(entityId, enabled);
+  }
+
+  setRigidBodyMaterial(entityId: EntityId, mass: number, restitution: number, friction: number): void {
+    this.world?.set_rigid_body_material(entityId, mass, restitution, friction);
+  }
+
+  setDamping(entityId: EntityId, linear: number, angular: number): void {
+    this.world?.set_damping(entityId, linear, angular);
+  }
+
+  setGravityScale(entityId: EntityId, scale: number): void {
+    this.world?.set_gravity_scale(entityId, scale);
+  }
+
   addCollider(entityId: EntityId, hx: number, hy: number, hz: number): void {
     this.world?.add_collider_aabb(entityId, hx, hy, hz);
   }
 
+  addColliderSphere(entityId: EntityId, radius: number): void {
+    this.world?.add_collider_sphere(entityId, radius);
+  }
+
+  addColliderCapsule(entityId: EntityId, radius: number, halfHeight: number): void {
+    this.world?.add_collider_capsule(entityId, radius, halfHeight);
+  }
+
+  /** Collider maillage (triangles du mesh custom). false si l'entité n'a pas de mesh custom. */
+  addMeshCollider(entityId: EntityId): boolean {
+    return this.world?.add_mesh_collider(entityId) ?? false;
+  }
+
+  setColliderTrigger(entityId: EntityId, isTrigger: boolean): void {
+    this.world?.set_collider_trigger(entityId, isTrigger);
+  }
+
+  setColliderLayers(entityId: EntityId, layer: number, mask: number): void {
+    this.world?.set_collider_layers(entityId, layer >>> 0, mask >>> 0);
+  }
+
   fitColliderToMesh(entityId: EntityId, minHalfY = 0.05): void {
     this.world?.fit_collider_to_mesh(entityId, minHalfY);
   }
@@ -211,12 +263,109 @@ class EngineBridge {
     this.world?.set_velocity(id, x, y, z);
   }
 
+  /** rad/s autour de chaque axe monde. */
+  getAngularVelocity(id: EntityId): [number, number, number] {
+    const a = this.world?.get_angular_velocity(id);
+    if (!a) return [0, 0, 0];
+    return [a[0], a[1], a[2]];
+  }
+
+  setAngularVelocity(id: EntityId, x: number, y: number, z: number): void {
+    this.world?.set_angular_velocity(id, x, y, z);
+  }
+
+  /** Force (N) pendant le prochain update : à rappeler à chaque frame pour une poussée continue. */
+  applyForce(id: EntityId, x: number, y: number, z: number): void {
+    this.world?.apply_force(id, x, y, z);
+  }
+
+  applyTorque(id: EntityId, x: number, y: number, z: number): void {
+    this.world?.apply_torque(id, x, y, z);
+  }
+
+  /** Impulsion (N·s) : change la velocity immédiatement. */
+  applyImpulse(id: EntityId, x: number, y: number, z: number): void {
+    this.world?.apply_impulse(id, x, y, z);
+  }
+
+  isSleeping(id: EntityId): boolean {
+    return this.world?.is_sleeping(id) ?? false;
+  }
+
+  wakeUp(id: EntityId): void {
+    this.world?.wake_up(id);
+  }
+
+  setGravity(x: number, y: number, z: number): void {
+    this.world?.set_gravity(x, y, z);
+  }
+
+  getGravity(): [number, number, number] {
+    const g = this.world?.get_gravity();
+    if (!g) return [0, -9.8, 0];
+    return [g[0], g[1], g[2]];
+  }
+
+  // ── Joints ────────────────────────────────────────────────────────────────────
+  // Un joint par entité ; `target` null = attaché à un point fixe du monde.
+
+  /** Ancres locales à chaque corps ; `length` absent = distance actuelle. */
+  addDistanceJoint(
+    id: EntityId, target: EntityId | null,
+    anchor: [number, number, number], targetAnchor: [number, number, number], length?: number,
+  ): void {
+    this.world?.add_distance_joint(id, target ?? 0xFFFFFFFF, ...anchor, ...targetAnchor, length ?? -1);
+  }
+
+  /** `pivot` en coordonnées monde. */
+  addBallJoint(id: EntityId, target: EntityId | null, pivot: [number, number, number]): void {
+    this.world?.add_ball_joint(id, target ?? 0xFFFFFFFF, ...pivot);
+  }
+
+  /** `pivot` et `axis` en monde ; `limits` [min, max] en degrés, 0 = pose actuelle. */
+  addHingeJoint(
+    id: EntityId, target: EntityId | null,
+    pivot: [number, number, number], axis: [number, number, number], limits?: [number, number],
+  ): void {
+    this.world?.add_hinge_joint(id, target ?? 0xFFFFFFFF, ...pivot, ...axis, !!limits, limits?.[0] ?? 0, limits?.[1] ?? 0);
+  }
+
+  addFixedJoint(id: EntityId, target: EntityId | null): void {
+    this.world?.add_fixed_joint(id, target ?? 0xFFFFFFFF);
+  }
+
+  removeJoint(id: EntityId): void {
+    this.world?.remove_joint(id);
+  }
+
+  /** Cible du joint de l'entité, ou null (pas de joint, ou attaché au monde). */
+  getJointTarget(id: EntityId): EntityId | null {
+    const t = this.world?.get_joint_target(id) ?? 0xFFFFFFFF;
+    return t === 0xFFFFFFFF ? null : t;
+  }
+
   // ── Player / Input ────────────────────────────────────────────────────────────
 
   setPlayer(entityId: EntityId): void {
     this.world?.set_player(entityId);
   }
 
+  setCharacterController(entityId: EntityId, c: CharacterControllerData): void {
+    this.world?.set_character_controller(
+      entityId, c.speed, c.acceleration, c.airControl, c.jumpHeight,
+      c.stepHeight, c.slopeLimit, c.coyoteTime, c.jumpBuffer,
+    );
+  }
+
+  /** Marche (x, z monde, longueur ≤ 1) et saut d'un CharacterController non joueur. */
+  setCharacterIntent(entityId: EntityId, x: number, z: number, jump = false): void {
+    this.world?.set_character_intent(entityId, x, z, jump);
+  }
+
+  removeCharacterController(entityId: EntityId): void {
+    this.world?.remove_character_controller(entityId);
+  }
+
   setInput(keys: number, mouseDx: number, mouseDy: number): void {
     this.world?.set_input(keys, mouseDx, mouseDy);
   }
@@ -242,6 +391,47 @@ class EngineBridge {
     this.world?.update(deltaMs);
   }
 
+  /** Événements de collision / trigger depuis le dernier appel (la file est vidée). */
+  drainCollisionEvents(): CollisionEvent[] {
+    const raw = this.world?.drain_collision_events();
+    if (!raw) return [];
+    const phases: CollisionPhase[] = ['enter', 'stay', 'exit'];
+    const events: CollisionEvent[] = [];
+    for (let i = 0; i + 3 < raw.length; i += 4) {
+      events.push({ a: raw[i], b: raw[i + 1], phase: phases[raw[i + 2]], trigger: raw[i + 3] !== 0 });
+    }
+    return events;
+  }
+
+  // ── Requêtes spatiales ──────────────────────────────────────────────────────
+
+  /** Premier collider (hors triggers) touché le long de `dir`, ou null. `layerMask` : toutes les couches par défaut. */
+  raycast(origin: [number, number, number], dir: [number, number, number], maxDist: number, layerMask = 0xFFFFFFFF): RayHit | null {
+    if (!this.world) return null;
+    return parseHit(this.world.raycast(...origin, ...dir, maxDist, layerMask));
+  }
+
+  sphereCast(origin: [number, number, number], radius: number, dir: [number, number, number], maxDist: number, layerMask = 0xFFFFFFFF): RayHit | null {
+    if (!this.world) return null;
+    return parseHit(this.world.sphere_cast(...origin, radius, ...dir, maxDist, layerMask));
+  }
+
+  /** `rotation` : Euler en degrés, comme setRotation. */
+  boxCast(
+    center: [number, number, number], halfExtents: [number, number, number], rotation: [number, number, number],
+    dir: [number, number, number], maxDist: number, layerMask = 0xFFFFFFFF,
+  ): RayHit | null {
+    if (!this.world) return null;
+    return parseHit(this.world.box_cast(...center, ...halfExtents, ...rotation, ...dir, maxDist, layerMask));
+  }
+
+  /** Rayon monde sous un point du viewport (coordonnées NDC, Y vers le haut). */
+  screenRay(ndcX: number, ndcY: number): { origin: [number, number, number]; dir: [number, number, number] } | null {
+    if (!this.world) return null;
+    const r = this.world.screen_ray(ndcX, ndcY);
+    return { origin: [r[0], r[1], r[2]], dir: [r[3], r[4], r[5]] };
+  }
+
   // ── Scène ───────────────────────────────────────────────────────────────────
 
   saveScene(): string {
@@ -290,4 +480,14 @@ class EngineBridge {
 }
 
 // Singleton partagé par toute l'app
+function parseHit(raw: Float64Array): RayHit | null {
+  if (raw.length < 8) return null;
+  return {
+    entity:   raw[0],
+    point:    [raw[1], raw[2], raw[3]],
+    normal:   [raw[4], raw[5], raw[6]],
+    distance: raw[7],
+  };
+}
+
 export const bridge = new EngineBridge();
@@ -2,6 +2,7 @@ import { bridge } from './engineBridge';
 import { useSceneStore } from '../store/sceneStore';
 import { useComponentStore } from '../store/componentStore';
 import { useConsoleStore } from '../store/consoleStore';
+import type { CollisionEvent, CollisionPhase, RayHit } from './types';
 
 type ScriptFn = (entity: { id: number }, engine: typeof engineProxy, deltaMs: number) => void;
 
@@ -11,6 +12,9 @@ const _pressedKeys = new Set<string>();
 // Entity ID en cours d'exécution (pour attribuer les logs à la bonne entité)
 let _currentEntityId = -1;
 
+// Événements de collision drainés au début de chaque tick (engine.getCollisions)
+let _frameCollisions: CollisionEvent[] = [];
+
 let _keyListenersAttached = false;
 let _onKeyDown: ((e: KeyboardEvent) => void) | null = null;
 let _onKeyUp:   ((e: KeyboardEvent) => void) | null = null;
@@ -76,6 +80,74 @@ const engineProxy = {
   setVelocity: (id: number, x: number, y: number, z: number) => {
     bridge.setVelocity(id, x, y, z);
   },
+  // Forces (requires RigidBody): force/torque last for the current frame, impulse is instant
+  applyForce: (id: number, x: number, y: number, z: number) => {
+    bridge.applyForce(id, x, y, z);
+  },
+  applyImpulse: (id: number, x: number, y: number, z: number) => {
+    bridge.applyImpulse(id, x, y, z);
+  },
+  applyTorque: (id: number, x: number, y: number, z: number) => {
+    bridge.applyTorque(id, x, y, z);
+  },
+  // Sleeping bodies are skipped by the simulation until a contact, impulse or move wakes them
+  isSleeping: (id: number): boolean => {
+    return bridge.isSleeping(id);
+  },
+  wakeUp: (id: number) => {
+    bridge.wakeUp(id);
+  },
+  getAngularVelocity: (id: number): [number, number, number] => {
+    return bridge.getAngularVelocity(id);
+  },
+  setAngularVelocity: (id: number, x: number, y: number, z: number) => {
+    bridge.setAngularVelocity(id, x, y, z);
+  },
+  setGravityScale: (id: number, scale: number) => {
+    bridge.setGravityScale(id, scale);
+  },
+  setGravity: (x: number, y: number, z: number) => {
+    bridge.setGravity(x, y, z);
+  },
+  // Non-player CharacterController: walk direction (world x, z, length <= 1) kept until the next call
+  setCharacterIntent: (id: number, x: number, z: number, jump = false) => {
+    bridge.setCharacterIntent(id, x, z, jump);
+  },
+  // Joints between rigid bodies (target null = fixed point in the world)
+  addDistanceJoint: (id: number, target: number | null, anchor: [number, number, number],
+                     targetAnchor: [number, number, number], length?: number) => {
+    bridge.addDistanceJoint(id, target, anchor, targetAnchor, length);
+  },
+  addBallJoint: (id: number, target: number | null, pivot: [number, number, number]) => {
+    bridge.addBallJoint(id, target, pivot);
+  },
+  addHingeJoint: (id: number, target: number | null, pivot: [number, number, number],
+                  axis: [number, number, number], limits?: [number, number]) => {
+    bridge.addHingeJoint(id, target, pivot, axis, limits);
+  },
+  addFixedJoint: (id: number, target: number | null) => {
+    bridge.addFixedJoint(id, target);
+  },
+  removeJoint: (id: number) => {
+    bridge.removeJoint(id);
+  },
+  // Collisions / triggers touching `id` since the previous frame
+  getCollisions: (id: number): { other: number; phase: CollisionPhase; trigger: boolean }[] => {
+    return _frameCollisions
+      .filter(e => e.a === id || e.b === id)
+      .map(e => ({ other: e.a === id ? e.b : e.a, phase: e.phase, trigger: e.trigger }));
+  },
+  // Spatial queries (colliders, triggers excluded): first hit or null
+  raycast: (origin: [number, number, number], dir: [number, number, number], maxDist = 1000, layerMask?: number): RayHit | null => {
+    return bridge.raycast(origin, dir, maxDist, layerMask);
+  },
+  sphereCast: (origin: [number, number, number], radius: number, dir: [number, number, number], maxDist = 1000, layerMask?: number): RayHit | null => {
+    return bridge.sphereCast(origin, radius, dir, maxDist, layerMask);
+  },
+  boxCast: (center: [number, number, number], halfExtents: [number, number, number], rotation: [number, number, number],
+            dir: [number, number, number], maxDist = 1000, layerMask?: number): RayHit | null => {
+    return bridge.boxCast(center, halfExtents, rotation, dir, maxDist, layerMask);
+  },
   // Input
   getKey: (key: string): boolean => {
     return _pressedKeys.has(key.toLowerCase());
@@ -120,6 +192,8 @@ let compiledScripts: CompiledScript[] = [];
 
 export function initScripts() {
   compiledScripts = [];
+  _frameCollisions = [];
+  bridge.drainCollisionEvents();  // événements antérieurs au Play
   initInputTracking();
   const entities = useSceneStore.getState().entities;
   const compStore = useComponentStore.getState();
@@ -151,6 +225,7 @@ export function initScripts() {
 }
 
 export function tickScripts(deltaMs: number) {
+  _frameCollisions = bridge.drainCollisionEvents();
   for (const cs of compiledScripts) {
     if (cs.hasError) continue;
     try {
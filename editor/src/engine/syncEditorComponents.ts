@@ -19,12 +19,19 @@ export function syncEditorComponentsToEngine(): void {
     }
 
     if (comps.rigidbody !== undefined) {
-      bridge.addRigidBody(id, comps.rigidbody.isStatic);
+      const rb = comps.rigidbody;
+      bridge.addRigidBody(id, rb.isStatic);
+      if (rb.isKinematic && !rb.isStatic) bridge.setBodyType(id, 'kinematic');
+      bridge.setCcd(id, rb.ccd ?? false);
+      bridge.setRigidBodyMaterial(id, rb.mass ?? 1, rb.restitution ?? 0, rb.friction ?? 0.5);
+      bridge.setDamping(id, rb.linearDamping ?? 0, rb.angularDamping ?? 0.05);
+      bridge.setGravityScale(id, rb.gravityScale ?? 1);
     }
 
     if (comps.collider !== undefined) {
       const c = comps.collider;
-      bridge.addCollider(id, c.hx, c.hy, c.hz);
+      if (!(c.mesh && bridge.addMeshCollider(id))) bridge.addCollider(id, c.hx, c.hy, c.hz);
+      bridge.setColliderLayers(id, c.layer ?? 1, c.mask ?? 0xFFFFFFFF);
     }
 
     if (comps.pointLight !== undefined) {
@@ -39,6 +46,7 @@ export function syncEditorComponentsToEngine(): void {
 
     if (comps.isPlayer !== undefined) {
       bridge.setPlayer(id);
+      if (comps.characterController) bridge.setCharacterController(id, comps.characterController);
     }
 
     if (comps.camera !== undefined) {
@@ -23,13 +23,54 @@ export interface MaterialData {
 }
 
 export interface RigidbodyData {
-  isStatic: boolean;
+  isStatic:     boolean;
+  isKinematic?: boolean; // déplacé par velocity / script, transporte les corps posés dessus
+  ccd?:         boolean; // détection continue : ne traverse pas les murs fins à grande vitesse
+  mass?:        number;  // kg (défaut 1)
+  restitution?: number;  // 0 = pas de rebond, 1 = rebond parfait (défaut 0)
+  friction?:    number;  // coefficient de Coulomb (défaut 0.5)
+  linearDamping?:  number;  // 1/s (défaut 0)
+  angularDamping?: number;  // 1/s (défaut 0.05)
+  gravityScale?:   number;  // × gravité du monde (défaut 1)
 }
 
 export interface ColliderData {
   hx: number;
   hy: number;
   hz: number;
+  isTrigger?: boolean;  // chevauchements signalés aux scripts, jamais résolus
+  layer?:     number;   // bits de couche (défaut 0x1)
+  mask?:      number;   // couches avec lesquelles il interagit (défaut 0xFFFFFFFF)
+  mesh?:      boolean;  // triangles du mesh custom au lieu de la boîte (statiques / kinematics)
+}
+
+/** Réglages du CharacterController moteur (Player Controller). */
+export interface CharacterControllerData {
+  speed:        number;  // m/s
+  acceleration: number;  // m/s² au sol
+  airControl:   number;  // fraction de l'accélération en l'air (0..1)
+  jumpHeight:   number;  // m
+  stepHeight:   number;  // m, marche franchie sans sauter
+  slopeLimit:   number;  // degrés, pente max considérée comme un sol
+  coyoteTime:   number;  // s, saut encore permis après avoir quitté le sol
+  jumpBuffer:   number;  // s, saut mémorisé avant l'atterrissage
+}
+
+export type CollisionPhase = 'enter' | 'stay' | 'exit';
+
+export interface CollisionEvent {
+  a:       EntityId;
+  b:       EntityId;
+  phase:   CollisionPhase;
+  trigger: boolean;
+}
+
+/** Premier collider touché par un raycast / sphereCast / boxCast. */
+export interface RayHit {
+  entity:   EntityId;
+  point:    [number, number, number];
+  normal:   [number, number, number];
+  distance: number;
 }
 
 export interface PointLightData {
@@ -77,6 +118,7 @@ export interface EntityComponents {
   pointLight?:       PointLightData;
   directionalLight?: DirectionalLightData;
   isPlayer?:         boolean;
+  characterController?: CharacterControllerData;  // absent = réglages par défaut du moteur
   script?:           string;
   camera?:           CameraData;
   particle?:         ParticleData;
@@ -161,8 +161,25 @@ export const useSceneStore = create<SceneState>((set, get) => ({
         bridge.addPbrMaterial(newId, comps.material.texId, comps.material.metallic, comps.material.roughness);
         bridge.setEmissive(newId, comps.material.emissive[0], comps.material.emissive[1], comps.material.emissive[2]);
       }
-      if (comps.rigidbody) bridge.addRigidBody(newId, comps.rigidbody.isStatic);
-      if (comps.collider)  bridge.addCollider(newId, comps.collider.hx, comps.collider.hy, comps.collider.hz);
+      if (comps.rigidbody) {
+        const rb = comps.rigidbody;
+        bridge.addRigidBody(newId, rb.isStatic);
+        if (rb.isKinematic && !rb.isStatic) bridge.setBodyType(newId, 'kinematic');
+        bridge.setCcd(newId, rb.ccd ?? false);
+        bridge.setRigidBodyMaterial(newId, rb.mass ?? 1, rb.restitution ?? 0, rb.friction ?? 0.5);
+        bridge.setDamping(newId, rb.linearDamping ?? 0, rb.angularDamping ?? 0.05);
+        bridge.setGravityScale(newId, rb.gravityScale ?? 1);
+      }
+      if (comps.collider) {
+        const c = comps.collider;
+        if (!(c.mesh && bridge.addMeshCollider(newId))) bridge.addCollider(newId, c.hx, c.hy, c.hz);
+        bridge.setColliderTrigger(newId, c.isTrigger ?? false);
+        bridge.setColliderLayers(newId, c.layer ?? 1, c.mask ?? 0xFFFFFFFF);
+      }
+      if (comps.isPlayer !== undefined) {
+        bridge.setPlayer(newId);
+        if (comps.characterController) bridge.setCharacterController(newId, comps.characterController);
+      }
       if (comps.pointLight) bridge.addPointLight(newId, comps.pointLight.r, comps.pointLight.g, comps.pointLight.b, comps.pointLight.intensity);
       const tag = bridge.getTag(srcId);
       if (tag) bridge.setTag(newId, tag);
@@ -176,7 +193,9 @@ export const useSceneStore = create<SceneState>((set, get) => ({
       if (comps.pointLight) metaToCopy.pointLight = { ...comps.pointLight };
       if (comps.script)     metaToCopy.script     = comps.script;
       if (comps.particle)   metaToCopy.particle   = { ...comps.particle };
-      // isPlayer et camera sont des singletons/complexes : non copiés.
+      if (comps.isPlayer !== undefined) metaToCopy.isPlayer = comps.isPlayer;
+      if (comps.characterController)    metaToCopy.characterController = { ...comps.characterController };
+      // camera est complexe (caméra active unique) : non copiée.
       for (const [k, v] of Object.entries(metaToCopy)) {
         compStore.setComponent(newId, k as keyof EntityComponents, v as EntityComponents[keyof EntityComponents]);
       }
@@ -0,0 +1,102 @@
+//! Mesure du coût d'un pas de physique selon le nombre de colliders.
+//!
+//!     cargo run --release --example physics_bench
+//!
+//! Scène : un sol, N caisses statiques en grille et N / 4 corps dynamiques qui
+//! tombent dessus. Avec la broadphase, le temps par pas doit croître à peu près
+//! linéairement avec N (et non en N²).
+//!
+//! En regard, la référence sans broadphase : le test AABB de toutes les paires de
+//! colliders (n(n-1)/2 tests), chronométré seul sur la même scène, avec le nombre de
+//! paires candidates qui se chevauchent réellement.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use engine_core::WorldCore;
+use engine_core::ecs::{Entity, Transform};
+use glam::Vec3;
+
+const STEP_MS: f32 = 1000.0 / 60.0;
+const WARMUP:  u32 = 30;
+const FRAMES:  u32 = 120;
+const BRUTE_ROUNDS: u32 = 5;
+
+/// Collider de la scène et demi-taille de sa boîte englobante, invariante par rotation.
+type Bounds = Vec<(Entity, Vec3)>;
+
+fn build(statics: usize) -> (WorldCore, Bounds) {
+    let mut world  = WorldCore::new();
+    let mut bounds = Bounds::new();
+
+    let floor = world.create_entity();
+    world.add_transform(floor, 0.0, -0.5, 0.0);
+    world.add_collider_aabb(floor, 500.0, 0.5, 500.0);
+    world.add_rigid_body(floor, true);
+    bounds.push((floor, Vec3::new(500.0, 0.5, 500.0)));
+
+    let side = (statics as f32).sqrt().ceil() as usize;
+    for i in 0..statics {
+        let (x, z) = ((i % side) as f32 * 3.0, (i / side) as f32 * 3.0);
+        let crate_id = world.create_entity();
+        world.add_transform(crate_id, x, 0.5, z);
+        world.add_collider_aabb(crate_id, 0.5, 0.5, 0.5);
+        world.add_rigid_body(crate_id, true);
+        bounds.push((crate_id, Vec3::splat(0.5)));
+    }
+
+    for i in 0..statics / 4 {
+        let (x, z) = ((i % side) as f32 * 6.0 + 1.5, (i / side) as f32 * 6.0 + 1.5);
+        let body = world.create_entity();
+        world.add_transform(body, x, 3.0 + (i % 3) as f32, z);
+        if i % 2 == 0 {
+            world.add_collider_sphere(body, 0.4);
+            bounds.push((body, Vec3::splat(0.4)));
+        } else {
+            world.add_collider_capsule(body, 0.3, 0.4);
+            bounds.push((body, Vec3::splat(0.7)));
+        }
+        world.add_rigid_body(body, false);
+    }
+    (world, bounds)
+}
+
+/// Référence sans broadphase : (paires qui se chevauchent, µs par passe) sur toutes les paires.
+fn brute_force(world: &WorldCore, bounds: &Bounds) -> (usize, f64) {
+    let boxes: Vec<(Vec3, Vec3)> = bounds.iter().map(|&(id, half)| {
+        let center = world.components().get::<Transform>(id).expect("transform").position;
+        (center - half, center + half)
+    }).collect();
+    let mut pairs = 0;
+    let start = Instant::now();
+    for _ in 0..BRUTE_ROUNDS {
+        pairs = 0;
+        for (i, &(min_a, max_a)) in boxes.iter().enumerate() {
+            for &(min_b, max_b) in &boxes[i + 1..] {
+                if min_a.cmple(max_b).all() && min_b.cmple(max_a).all() {
+                    pairs += 1;
+                }
+            }
+        }
+        black_box(pairs);
+    }
+    (pairs, start.elapsed().as_secs_f64() * 1e6 / BRUTE_ROUNDS as f64)
+}
+
+fn main() {
+    println!("{:>8} {:>8} {:>12} {:>10} {:>12} {:>14}", "statics", "dynamics", "us/step", "pairs", "n(n-1)/2", "brute us/pass");
+    for statics in [100, 200, 400, 800, 1600, 3200] {
+        let (mut world, bounds) = build(statics);
+        for _ in 0..WARMUP {
+            world.update(STEP_MS);
+        }
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            world.update(STEP_MS);
+        }
+        let per_step = start.elapsed().as_secs_f64() * 1e6 / FRAMES as f64;
+        let n = bounds.len();
+        let (pairs, brute) = brute_force(&world, &bounds);
+        println!("{:>8} {:>8} {:>12.1} {:>10} {:>12} {:>14.1}", statics, statics / 4, per_step, pairs, n * (n - 1) / 2, brute);
+    }
+}
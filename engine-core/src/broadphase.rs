@@ -0,0 +1,294 @@
+//! Broadphase : arbre de boîtes englobantes (BVH) sur les colliders.
+//!
+//! Reconstruit à chaque pas fixe (tri par médiane, O(n log n)) puis interrogé par
+//! chaque corps dynamique : seules les paires dont les AABB se chevauchent sont
+//! transmises à la narrow-phase (`collision::contact`).
+
+use glam::Vec3;
+
+/// Nombre max d'éléments par feuille.
+const LEAF_SIZE: usize = 4;
+
+/// Boîte englobante alignée sur les axes monde.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub const EMPTY: Aabb = Aabb { min: Vec3::INFINITY, max: Vec3::NEG_INFINITY };
+
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+
+    pub fn union(self, other: Aabb) -> Aabb {
+        Aabb { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// Agrandie de `margin` dans toutes les directions.
+    pub fn expand(self, margin: f32) -> Aabb {
+        Aabb { min: self.min - Vec3::splat(margin), max: self.max + Vec3::splat(margin) }
+    }
+
+    /// Agrandie de `half` par axe (somme de Minkowski avec une boîte centrée).
+    pub fn expand_by(self, half: Vec3) -> Aabb {
+        Aabb { min: self.min - half, max: self.max + half }
+    }
+
+    /// Portion [entrée, sortie] du rayon `origin + t * dir`, 0 ≤ t ≤ `max_dist`,
+    /// contenue dans la boîte ; None si le rayon la manque.
+    pub fn ray_interval(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<(f32, f32)> {
+        let (mut enter, mut exit) = (0.0_f32, max_dist);
+        for axis in 0..3 {
+            if dir[axis] == 0.0 {
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inv = 1.0 / dir[axis];
+            let t0  = (self.min[axis] - origin[axis]) * inv;
+            let t1  = (self.max[axis] - origin[axis]) * inv;
+            enter = enter.max(t0.min(t1));
+            exit  = exit.min(t0.max(t1));
+        }
+        (enter <= exit).then_some((enter, exit))
+    }
+
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+}
+
+/// Nœud : feuille si `count > 0` (éléments `items[first..first + count]`),
+/// sinon nœud interne d'enfants `first` et `first + 1`.
+struct Node {
+    aabb:  Aabb,
+    first: u32,
+    count: u32,
+}
+
+/// BVH statique sur une liste d'AABB, identifiées par leur indice dans cette liste.
+pub(crate) struct Bvh {
+    nodes: Vec<Node>,
+    items: Vec<u32>,
+    aabbs: Vec<Aabb>,
+}
+
+impl Bvh {
+    pub fn build(aabbs: Vec<Aabb>) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(aabbs.len().div_ceil(LEAF_SIZE) * 2),
+            items: (0..aabbs.len() as u32).collect(),
+            aabbs,
+        };
+        if !bvh.aabbs.is_empty() {
+            bvh.nodes.push(Node { aabb: Aabb::EMPTY, first: 0, count: 0 });
+            bvh.subdivide(0, 0, bvh.items.len());
+        }
+        bvh
+    }
+
+    /// Remplit le nœud `node` avec `items[start..end]`, en le découpant si besoin.
+    fn subdivide(&mut self, node: usize, start: usize, end: usize) {
+        let aabbs = &self.aabbs;
+        let items = &mut self.items[start..end];
+        let bounds = items.iter().fold(Aabb::EMPTY, |b, &i| b.union(aabbs[i as usize]));
+        self.nodes[node].aabb = bounds;
+
+        if items.len() <= LEAF_SIZE {
+            self.nodes[node].first = start as u32;
+            self.nodes[node].count = items.len() as u32;
+            return;
+        }
+
+        // Coupe à la médiane des centres, sur l'axe où ils sont le plus étalés.
+        let centers = items.iter().fold(Aabb::EMPTY, |b, &i| {
+            let c = aabbs[i as usize].center();
+            b.union(Aabb::new(c, c))
+        });
+        let spread = centers.max - centers.min;
+        let axis = if spread.x >= spread.y && spread.x >= spread.z { 0 } else if spread.y >= spread.z { 1 } else { 2 };
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |&a, &b| {
+            aabbs[a as usize].center()[axis].total_cmp(&aabbs[b as usize].center()[axis])
+        });
+
+        let left = self.nodes.len();
+        self.nodes.push(Node { aabb: Aabb::EMPTY, first: 0, count: 0 });
+        self.nodes.push(Node { aabb: Aabb::EMPTY, first: 0, count: 0 });
+        self.nodes[node].first = left as u32;
+        self.subdivide(left,     start,       start + mid);
+        self.subdivide(left + 1, start + mid, end);
+    }
+
+    /// Ajoute à `out` les indices des AABB qui chevauchent `aabb` (ordre quelconque).
+    pub fn query(&self, aabb: &Aabb, out: &mut Vec<usize>) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0usize];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !node.aabb.overlaps(aabb) {
+                continue;
+            }
+            if node.count > 0 {
+                let leaf = &self.items[node.first as usize..(node.first + node.count) as usize];
+                out.extend(leaf.iter().map(|&i| i as usize).filter(|&i| self.aabbs[i].overlaps(aabb)));
+            } else {
+                stack.push(node.first as usize);
+                stack.push(node.first as usize + 1);
+            }
+        }
+    }
+
+    /// Ajoute à `out` les indices des AABB traversées par le rayon `origin + t * dir`,
+    /// 0 ≤ t ≤ `max_dist` (ordre quelconque).
+    pub fn query_ray(&self, origin: Vec3, dir: Vec3, max_dist: f32, out: &mut Vec<usize>) {
+        self.query_sweep(origin, Vec3::ZERO, dir, max_dist, out);
+    }
+
+    /// Comme `query_ray`, pour une boîte de demi-taille `half` balayée le long du rayon :
+    /// chaque AABB est agrandie de `half` avant le test.
+    pub fn query_sweep(&self, origin: Vec3, half: Vec3, dir: Vec3, max_dist: f32, out: &mut Vec<usize>) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let hits = |aabb: &Aabb| aabb.expand_by(half).ray_interval(origin, dir, max_dist).is_some();
+        let mut stack = vec![0usize];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !hits(&node.aabb) {
+                continue;
+            }
+            if node.count > 0 {
+                let leaf = &self.items[node.first as usize..(node.first + node.count) as usize];
+                out.extend(leaf.iter().map(|&i| i as usize).filter(|&i| hits(&self.aabbs[i])));
+            } else {
+                stack.push(node.first as usize);
+                stack.push(node.first as usize + 1);
+            }
+        }
+    }
+
+    /// Toutes les paires (i, j), i < j, d'AABB qui se chevauchent, triées.
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        let mut candidates = Vec::new();
+        for (i, aabb) in self.aabbs.iter().enumerate() {
+            candidates.clear();
+            self.query(aabb, &mut candidates);
+            pairs.extend(candidates.iter().filter(|&&j| j > i).map(|&j| (i, j)));
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Générateur congruentiel : scène pseudo-aléatoire reproductible sans dépendance.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> f32 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 40) as f32 / (1u64 << 24) as f32
+        }
+
+        fn vec3(&mut self, scale: f32) -> Vec3 {
+            Vec3::new(self.next(), self.next(), self.next()) * scale
+        }
+    }
+
+    /// `count` boîtes de 0.1 à 2 m de côté dans un cube de 20 m.
+    fn scene(rng: &mut Lcg, count: usize) -> Vec<Aabb> {
+        (0..count)
+            .map(|_| {
+                let center = rng.vec3(20.0);
+                let half   = Vec3::splat(0.05) + rng.vec3(0.95);
+                Aabb::new(center - half, center + half)
+            })
+            .collect()
+    }
+
+    fn brute_pairs(aabbs: &[Aabb]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..aabbs.len() {
+            for j in i + 1..aabbs.len() {
+                if aabbs[i].overlaps(&aabbs[j]) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn assert_matches_brute_force(aabbs: &[Aabb], rng: &mut Lcg) {
+        let bvh = Bvh::build(aabbs.to_vec());
+        let expected = brute_pairs(aabbs);
+        assert!(!expected.is_empty(), "scène sans chevauchement : test sans objet");
+        assert_eq!(bvh.overlapping_pairs(), expected);
+
+        for _ in 0..50 {
+            let center = rng.vec3(22.0) - Vec3::ONE;
+            let probe  = Aabb::new(center, center + rng.vec3(4.0));
+            let mut found = Vec::new();
+            bvh.query(&probe, &mut found);
+            found.sort_unstable();
+            let expected: Vec<usize> = (0..aabbs.len()).filter(|&i| aabbs[i].overlaps(&probe)).collect();
+            assert_eq!(found, expected, "requête {probe:?}");
+
+            let (origin, dir) = (rng.vec3(20.0), rng.vec3(2.0) - Vec3::ONE);
+            let mut found = Vec::new();
+            bvh.query_ray(origin, dir, 15.0, &mut found);
+            found.sort_unstable();
+            let expected: Vec<usize> = (0..aabbs.len())
+                .filter(|&i| aabbs[i].ray_interval(origin, dir, 15.0).is_some())
+                .collect();
+            assert_eq!(found, expected, "rayon {origin:?} {dir:?}");
+        }
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let mut rng = Lcg(7);
+        let aabbs = scene(&mut rng, 300);
+        assert_matches_brute_force(&aabbs, &mut rng);
+    }
+
+    #[test]
+    fn empty_tree_returns_nothing() {
+        let bvh = Bvh::build(Vec::new());
+        assert!(bvh.overlapping_pairs().is_empty());
+        let mut found = Vec::new();
+        bvh.query(&Aabb::new(Vec3::splat(-1.0), Vec3::ONE), &mut found);
+        bvh.query_ray(Vec3::ZERO, Vec3::X, 10.0, &mut found);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn rebuilt_tree_follows_moved_boxes() {
+        let mut rng = Lcg(42);
+        let mut aabbs = scene(&mut rng, 200);
+        let before = Bvh::build(aabbs.clone()).overlapping_pairs();
+
+        // Le BVH est reconstruit à chaque pas : déplacer les boîtes puis reconstruire
+        // doit donner exactement les chevauchements des nouvelles positions.
+        for aabb in aabbs.iter_mut().step_by(3) {
+            let shift = rng.vec3(6.0) - Vec3::splat(3.0);
+            *aabb = Aabb::new(aabb.min + shift, aabb.max + shift);
+        }
+        assert_matches_brute_force(&aabbs, &mut rng);
+        assert_ne!(Bvh::build(aabbs).overlapping_pairs(), before, "les déplacements changent les paires");
+    }
+}
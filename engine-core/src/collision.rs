@@ -0,0 +1,973 @@
+//! Narrow-phase : contact entre deux formes de collision en espace monde.
+//!
+//! - boîte / boîte : théorème des axes séparateurs. Deux OBB convexes sont disjointes
+//!   ssi l'une des 15 directions suivantes les sépare : les 3 axes de A, les 3 axes de B
+//!   et les 9 produits vectoriels axe A × axe B. L'axe de plus faible recouvrement donne
+//!   la normale et la profondeur du contact.
+//! - sphère / capsule entre elles : points les plus proches des segments centraux,
+//!   puis test sphère / sphère.
+//! - boîte / sphère : point de la boîte le plus proche du centre (face de moindre
+//!   pénétration si le centre est dedans) ; boîte / capsule : même test au point du
+//!   segment le plus enfoncé dans la boîte.
+//! - maillage : triangles candidats (BVH local du `TriMesh`) posés en espace monde, puis
+//!   triangle / sphère et triangle / capsule par points les plus proches, triangle / boîte
+//!   par axes séparateurs (13 axes). Le contact retenu est le plus profond ; les triangles
+//!   ont deux faces. Deux maillages ne se touchent jamais.
+//!
+//! Chaque contact porte ses points (couple des impulsions, cf. systems.rs) : milieu du
+//! recouvrement pour les formes rondes ; pour une boîte, face de l'autre forme tournée vers
+//! la face de référence (axe retenu), découpée par les côtés de celle-ci et gardée là où elle
+//! passe dessous (quatre points au plus), ou milieu des points les plus proches des deux
+//! arêtes si l'axe retenu est un produit d'arêtes.
+//!
+//! Requêtes (cf. raycast.rs) : `ray_cast` intersecte analytiquement un rayon avec
+//! chaque forme ; `shape_cast` déplace une forme le long d'un rayon et réutilise
+//! `contact` pour trouver le premier instant de contact.
+
+use std::sync::Arc;
+
+use glam::{Mat4, Vec3};
+
+use crate::broadphase::Aabb;
+use crate::ecs::{Collider, ColliderShape};
+use crate::trimesh::TriMesh;
+
+/// Produits vectoriels plus courts que ça : arêtes parallèles, axe ignoré.
+const PARALLEL_EPS: f32 = 1e-6;
+
+/// Un axe d'arête ne remplace un axe de face que s'il est nettement meilleur :
+/// évite que le contact bascule d'un frame à l'autre sur des boîtes presque alignées.
+const EDGE_BIAS: f32 = 0.95;
+
+/// Itérations de la recherche du point de capsule le plus enfoncé dans une boîte.
+const SEGMENT_SEARCH_ITERS: usize = 24;
+
+/// Un point de la face incidente à moins de cette distance au-dessus de la face de référence
+/// compte dans le contact : une boîte presque à plat s'appuie sur toute sa face et ne bascule
+/// pas d'un coin à l'autre.
+const CONTACT_SLOP: f32 = 0.01;
+
+/// Points de contact retenus au plus : les coins d'une face.
+pub(crate) const MAX_CONTACT_POINTS: usize = 4;
+
+/// Itérations de la dichotomie sur l'instant de contact d'un `shape_cast`.
+const CAST_BISECT_ITERS: usize = 20;
+
+/// Pas minimal d'avancée d'un `shape_cast` (forme très fine dans la direction du cast).
+const MIN_CAST_STEP: f32 = 0.01;
+
+/// Boîte orientée en espace monde.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Obb {
+    pub center:       Vec3,
+    pub axes:         [Vec3; 3],  // axes locaux X, Y, Z (unitaires)
+    pub half_extents: Vec3,       // déjà multipliés par le scale
+}
+
+/// Maillage de triangles posé en espace monde.
+#[derive(Debug, Clone)]
+pub(crate) struct MeshShape {
+    pub mesh:     Arc<TriMesh>,
+    pub to_world: Mat4,
+    pub to_local: Mat4,
+}
+
+/// Forme de collision posée en espace monde.
+#[derive(Debug, Clone)]
+pub(crate) enum Shape {
+    Box(Obb),
+    Sphere  { center: Vec3, radius: f32 },
+    /// Segment [a, b] épaissi de `radius`.
+    Capsule { a: Vec3, b: Vec3, radius: f32 },
+    Mesh(MeshShape),
+}
+
+/// Contact entre A et B : `normal` unitaire orientée de A vers B, `depth` > 0.
+/// Translater A de `-normal * depth` (ou B de `+normal * depth`) les sépare.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Contact {
+    pub normal: Vec3,
+    pub depth:  f32,
+    pub points: ContactPoints,
+}
+
+/// Points d'un contact, en monde dans la zone de recouvrement (au moins un).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ContactPoints {
+    points: [Vec3; MAX_CONTACT_POINTS],
+    len:    usize,
+}
+
+impl ContactPoints {
+    fn one(point: Vec3) -> Self {
+        ContactPoints { points: [point; MAX_CONTACT_POINTS], len: 1 }
+    }
+
+    /// `points` d'une face de normale `normal`, réduits à `MAX_CONTACT_POINTS` : le plus
+    /// avancé le long de `dir`, le plus loin de lui, puis les plus écartés de part et d'autre
+    /// de leur segment. None si vide.
+    fn reduce(points: &[Vec3], normal: Vec3, dir: Vec3) -> Option<Self> {
+        let mut kept = ContactPoints::one(*points.first()?);
+        if points.len() <= MAX_CONTACT_POINTS {
+            kept.len = points.len();
+            kept.points[..kept.len].copy_from_slice(points);
+            return Some(kept);
+        }
+        let pick = |key: &dyn Fn(Vec3) -> f32| *points.iter().max_by(|p, q| key(**p).total_cmp(&key(**q))).unwrap();
+        let p0 = pick(&|p| p.dot(dir));
+        let p1 = pick(&|p| p.distance_squared(p0));
+        let side = |p: Vec3| (p1 - p0).cross(p - p0).dot(normal);
+        kept.points = [p0, p1, pick(&side), pick(&|p| -side(p))];
+        kept.len = MAX_CONTACT_POINTS;
+        Some(kept)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.points[..self.len].iter().copied()
+    }
+}
+
+impl Contact {
+    /// Même contact vu depuis B.
+    fn flip(self) -> Self {
+        Contact { normal: -self.normal, ..self }
+    }
+
+    /// Contact avec une sphère B de centre `center` et de rayon `radius` : point au milieu du recouvrement.
+    fn with_sphere(normal: Vec3, depth: f32, center: Vec3, radius: f32) -> Self {
+        Contact { normal, depth, points: ContactPoints::one(center - normal * (radius - depth * 0.5)) }
+    }
+}
+
+/// Origine de l'axe retenu par un test SAT où A est une boîte, pour placer les points de contact.
+#[derive(Debug, Clone, Copy)]
+enum SatAxis {
+    /// Normale de face de A.
+    FaceA,
+    /// Normale de face de B (ou du triangle).
+    FaceB,
+    /// Axe `i` de A × arête `j` de B.
+    Edges(usize, usize),
+}
+
+/// Premier impact d'un rayon ou d'une forme lancée : `distance` parcourue le long
+/// de la direction, `point` de contact et `normal` unitaire de la surface touchée
+/// (orientée vers l'extérieur, donc contre la direction du lancer).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CastHit {
+    pub distance: f32,
+    pub point:    Vec3,
+    pub normal:   Vec3,
+}
+
+impl Obb {
+    /// OBB de demi-extents locaux `half_extents` posée sur la matrice monde `world`.
+    pub fn from_matrix(world: Mat4, half_extents: Vec3) -> Self {
+        let (scale, rotation, center) = world.to_scale_rotation_translation();
+        Obb {
+            center,
+            axes: [rotation * Vec3::X, rotation * Vec3::Y, rotation * Vec3::Z],
+            half_extents: half_extents * scale.abs(),
+        }
+    }
+
+    /// Demi-longueur de la projection de la boîte sur `axis` (unitaire).
+    fn radius_along(&self, axis: Vec3) -> f32 {
+        self.half_extents.x * self.axes[0].dot(axis).abs()
+            + self.half_extents.y * self.axes[1].dot(axis).abs()
+            + self.half_extents.z * self.axes[2].dot(axis).abs()
+    }
+
+    /// Coordonnées de `p` dans le repère de la boîte.
+    fn local_point(&self, p: Vec3) -> Vec3 {
+        self.local_dir(p - self.center)
+    }
+
+    fn local_dir(&self, v: Vec3) -> Vec3 {
+        Vec3::new(v.dot(self.axes[0]), v.dot(self.axes[1]), v.dot(self.axes[2]))
+    }
+
+    fn world_dir(&self, v: Vec3) -> Vec3 {
+        self.axes[0] * v.x + self.axes[1] * v.y + self.axes[2] * v.z
+    }
+
+    /// Arête parallèle à `axes[i]` la plus avancée dans la direction `dir`.
+    fn support_edge(&self, i: usize, dir: Vec3) -> (Vec3, Vec3) {
+        let mut mid = self.center;
+        for k in (0..3).filter(|&k| k != i) {
+            mid += self.axes[k] * (self.half_extents[k] * self.axes[k].dot(dir).signum());
+        }
+        let half = self.axes[i] * self.half_extents[i];
+        (mid - half, mid + half)
+    }
+
+    /// Sommets de la face dont la normale sortante est la plus proche de `dir`.
+    fn face_toward(&self, dir: Vec3) -> [Vec3; 4] {
+        let r = (0..3).max_by(|&i, &j| self.axes[i].dot(dir).abs().total_cmp(&self.axes[j].dot(dir).abs())).unwrap_or(0);
+        let face = self.center + self.axes[r] * (self.half_extents[r] * self.axes[r].dot(dir).signum());
+        let u = self.axes[(r + 1) % 3] * self.half_extents[(r + 1) % 3];
+        let v = self.axes[(r + 2) % 3] * self.half_extents[(r + 2) % 3];
+        [face + u + v, face - u + v, face - u - v, face + u - v]
+    }
+
+    /// Points de contact sur la face de référence de normale sortante `normal` (un axe de la
+    /// boîte) : polygone `incident` de l'autre forme découpé par les côtés de la face, gardé
+    /// là où il passe sous elle.
+    fn face_contact_points(&self, normal: Vec3, incident: &[Vec3]) -> Option<ContactPoints> {
+        let mut polygon = incident.to_vec();
+        for (axis, half) in self.axes.iter().zip(self.half_extents.to_array()) {
+            if axis.dot(normal).abs() > 0.5 {
+                continue;
+            }
+            let offset = self.center.dot(*axis);
+            polygon = clip_polygon(&polygon, *axis, offset + half);
+            polygon = clip_polygon(&polygon, -*axis, half - offset);
+        }
+        let face = self.center.dot(normal) + self.radius_along(normal) + CONTACT_SLOP;
+        polygon.retain(|p| p.dot(normal) <= face);
+        ContactPoints::reduce(&polygon, normal, -normal)
+    }
+
+    /// Distance signée de `p` à la boîte (négative à l'intérieur).
+    fn signed_distance(&self, p: Vec3) -> f32 {
+        let q = self.local_point(p).abs() - self.half_extents;
+        q.max(Vec3::ZERO).length() + q.max_element().min(0.0)
+    }
+}
+
+impl MeshShape {
+    /// Triangle `index` en espace monde.
+    fn triangle(&self, index: usize) -> [Vec3; 3] {
+        self.mesh.triangle(index).map(|p| self.to_world.transform_point3(p))
+    }
+
+    /// Boîte locale qui contient la boîte monde `aabb`.
+    fn local_aabb(&self, aabb: &Aabb) -> Aabb {
+        transform_aabb(&self.to_local, aabb)
+    }
+}
+
+/// Boîte englobante de `aabb` transformée par `m` (ses 8 coins).
+fn transform_aabb(m: &Mat4, aabb: &Aabb) -> Aabb {
+    (0..8).fold(Aabb::EMPTY, |acc, i| {
+        let corner = Vec3::new(
+            if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+            if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+            if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+        );
+        let p = m.transform_point3(corner);
+        acc.union(Aabb::new(p, p))
+    })
+}
+
+impl Shape {
+    /// Forme monde d'un `Collider` posé sur la matrice monde `world`.
+    pub fn from_collider(world: Mat4, collider: &Collider) -> Self {
+        match collider.shape {
+            ColliderShape::Box { half_extents } => Shape::Box(Obb::from_matrix(world, half_extents)),
+            ColliderShape::Mesh { ref mesh } => {
+                Shape::Mesh(MeshShape { mesh: Arc::clone(mesh), to_world: world, to_local: world.inverse() })
+            }
+            ColliderShape::Sphere { radius } => {
+                let (scale, _, center) = world.to_scale_rotation_translation();
+                Shape::Sphere { center, radius: radius * scale.abs().max_element() }
+            }
+            ColliderShape::Capsule { radius, half_height } => {
+                let (scale, rotation, center) = world.to_scale_rotation_translation();
+                let scale = scale.abs();
+                let axis  = rotation * Vec3::Y * (half_height * scale.y);
+                Shape::Capsule { a: center - axis, b: center + axis, radius: radius * scale.x.max(scale.z) }
+            }
+        }
+    }
+
+    /// Boîte englobante monde, pour la broadphase.
+    pub fn aabb(&self) -> Aabb {
+        match *self {
+            Shape::Box(obb) => {
+                let extent = Vec3::new(obb.radius_along(Vec3::X), obb.radius_along(Vec3::Y), obb.radius_along(Vec3::Z));
+                Aabb::new(obb.center - extent, obb.center + extent)
+            }
+            Shape::Sphere { center, radius } => Aabb::new(center - Vec3::splat(radius), center + Vec3::splat(radius)),
+            Shape::Capsule { a, b, radius }  => Aabb::new(a.min(b) - Vec3::splat(radius), a.max(b) + Vec3::splat(radius)),
+            Shape::Mesh(ref m)               => transform_aabb(&m.to_world, &m.mesh.bounds()),
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        match *self {
+            Shape::Box(obb)              => obb.center,
+            Shape::Sphere { center, .. } => center,
+            Shape::Capsule { a, b, .. }  => (a + b) * 0.5,
+            Shape::Mesh(_)               => self.aabb().center(),
+        }
+    }
+
+    /// Demi-longueur de la projection de la forme sur `axis` (unitaire).
+    pub fn radius_along(&self, axis: Vec3) -> f32 {
+        match *self {
+            Shape::Box(obb)              => obb.radius_along(axis),
+            Shape::Sphere { radius, .. } => radius,
+            Shape::Capsule { a, b, radius } => radius + 0.5 * (b - a).dot(axis).abs(),
+            Shape::Mesh(_) => {
+                let aabb = self.aabb();
+                ((aabb.max - aabb.min) * 0.5).dot(axis.abs())
+            }
+        }
+    }
+
+    pub fn translate(&mut self, delta: Vec3) {
+        match self {
+            Shape::Box(obb)               => obb.center += delta,
+            Shape::Sphere { center, .. }  => *center += delta,
+            Shape::Capsule { a, b, .. }   => { *a += delta; *b += delta; }
+            Shape::Mesh(m) => {
+                m.to_world = Mat4::from_translation(delta) * m.to_world;
+                m.to_local = m.to_world.inverse();
+            }
+        }
+    }
+
+    pub fn is_mesh(&self) -> bool {
+        matches!(self, Shape::Mesh(_))
+    }
+}
+
+/// Contact entre deux formes. Retourne None si elles ne se chevauchent pas.
+pub(crate) fn contact(a: &Shape, b: &Shape) -> Option<Contact> {
+    use Shape::*;
+    match (a, b) {
+        (Mesh(_), Mesh(_)) => None,
+        (shape, Mesh(mesh)) => mesh_contact(shape, mesh),
+        (Mesh(mesh), shape) => mesh_contact(shape, mesh).map(Contact::flip),
+        (&Box(a), &Box(b)) => obb_contact(&a, &b),
+        (&Box(obb), &Sphere { center, radius }) => box_sphere(&obb, center, radius),
+        (&Sphere { center, radius }, &Box(obb)) => box_sphere(&obb, center, radius).map(Contact::flip),
+        (&Box(obb), &Capsule { a, b, radius }) => box_capsule(&obb, a, b, radius),
+        (&Capsule { a, b, radius }, &Box(obb)) => box_capsule(&obb, a, b, radius).map(Contact::flip),
+        (&Sphere { center: ca, radius: ra }, &Sphere { center: cb, radius: rb }) => spheres(ca, ra, cb, rb),
+        (&Sphere { center, radius: ra }, &Capsule { a, b, radius: rb }) => {
+            spheres(center, ra, closest_on_segment(center, a, b), rb)
+        }
+        (&Capsule { a, b, radius: ra }, &Sphere { center, radius: rb }) => {
+            spheres(closest_on_segment(center, a, b), ra, center, rb)
+        }
+        (&Capsule { a: a0, b: a1, radius: ra }, &Capsule { a: b0, b: b1, radius: rb }) => {
+            let (p, q) = closest_between_segments(a0, a1, b0, b1);
+            spheres(p, ra, q, rb)
+        }
+    }
+}
+
+/// Comme `contact`, mais un maillage donne un contact par triangle touché (au lieu du
+/// plus profond) : utile pour distinguer un mur d'un sol dans le même maillage.
+pub(crate) fn contacts(a: &Shape, b: &Shape) -> Vec<Contact> {
+    match (a, b) {
+        (Shape::Mesh(_), Shape::Mesh(_)) => Vec::new(),
+        (shape, Shape::Mesh(mesh)) => mesh_contacts(shape, mesh).collect(),
+        (Shape::Mesh(mesh), shape) => mesh_contacts(shape, mesh).map(Contact::flip).collect(),
+        _ => contact(a, b).into_iter().collect(),
+    }
+}
+
+/// Forme convexe (A) / maillage (B) : contact le plus profond parmi les triangles candidats.
+fn mesh_contact(shape: &Shape, mesh: &MeshShape) -> Option<Contact> {
+    mesh_contacts(shape, mesh).max_by(|x, y| x.depth.total_cmp(&y.depth))
+}
+
+/// Contacts de la forme (A) avec chacun des triangles du maillage (B).
+fn mesh_contacts<'a>(shape: &'a Shape, mesh: &'a MeshShape) -> impl Iterator<Item = Contact> + 'a {
+    let mut candidates = Vec::new();
+    mesh.mesh.query(&mesh.local_aabb(&shape.aabb()), &mut candidates);
+    candidates.into_iter().filter_map(move |i| triangle_contact(shape, mesh.triangle(i)))
+}
+
+/// Forme convexe (A) / triangle (B), deux faces.
+fn triangle_contact(shape: &Shape, tri: [Vec3; 3]) -> Option<Contact> {
+    match *shape {
+        Shape::Sphere { center, radius } => {
+            sphere_triangle(center, closest_on_triangle(center, tri), radius, tri, 0.0)
+        }
+        Shape::Capsule { a, b, radius } => {
+            let (p, q) = closest_segment_triangle(a, b, tri);
+            // Segment qui traverse le triangle : sortir par la face, depuis l'extrémité la plus enfoncée.
+            let sink = if p.distance_squared(q) < 1e-12 {
+                let n    = triangle_normal(tri);
+                let side = if ((a + b) * 0.5 - tri[0]).dot(n) >= 0.0 { n } else { -n };
+                (a - tri[0]).dot(side).min((b - tri[0]).dot(side)).min(0.0)
+            } else {
+                0.0
+            };
+            sphere_triangle(p, q, radius, tri, -sink)
+        }
+        Shape::Box(obb) => obb_triangle(&obb, tri),
+        Shape::Mesh(_)  => None,
+    }
+}
+
+/// Sphère de centre `center` dont le point le plus proche du triangle est `closest` ;
+/// `extra` s'ajoute à la profondeur (centre passé de l'autre côté).
+fn sphere_triangle(center: Vec3, closest: Vec3, radius: f32, tri: [Vec3; 3], extra: f32) -> Option<Contact> {
+    let diff  = closest - center;
+    let dist2 = diff.length_squared();
+    if dist2 >= radius * radius {
+        return None;
+    }
+    let dist = dist2.sqrt();
+    if dist > 1e-6 {
+        return Some(Contact { normal: diff / dist, depth: radius - dist + extra, points: ContactPoints::one(closest) });
+    }
+    // Centre sur le triangle : sortir par la face qui regarde le centre de la forme.
+    let n = triangle_normal(tri);
+    let n = if (center - tri[0]).dot(n) >= 0.0 { n } else { -n };
+    Some(Contact { normal: -n, depth: radius + extra, points: ContactPoints::one(closest) })
+}
+
+/// Test SAT boîte (A) / triangle (B) : normale du triangle, 3 axes de la boîte et
+/// 9 produits vectoriels arête × axe.
+fn obb_triangle(obb: &Obb, tri: [Vec3; 3]) -> Option<Contact> {
+    let mut best: Option<(f32, Vec3, f32, SatAxis)> = None;  // (score, normale, profondeur, origine)
+    let mut test = |axis: Vec3, origin: SatAxis| -> bool {
+        let center = obb.center.dot(axis);
+        let radius = obb.radius_along(axis);
+        let proj   = tri.map(|p| p.dot(axis));
+        let (lo, hi) = (proj[0].min(proj[1]).min(proj[2]), proj[0].max(proj[1]).max(proj[2]));
+        // Sortie côté + (triangle devant la boîte) ou côté - : la plus courte.
+        let (ahead, behind) = (center + radius - lo, hi - (center - radius));
+        if ahead <= 0.0 || behind <= 0.0 {
+            return false;
+        }
+        let (normal, depth) = if ahead <= behind { (axis, ahead) } else { (-axis, behind) };
+        let score = if matches!(origin, SatAxis::Edges(..)) { depth / EDGE_BIAS } else { depth };
+        if best.is_none_or(|(best_score, ..)| score < best_score) {
+            best = Some((score, normal, depth, origin));
+        }
+        true
+    };
+
+    if !test(triangle_normal(tri), SatAxis::FaceB) {
+        return None;
+    }
+    for axis in obb.axes {
+        if !test(axis, SatAxis::FaceA) {
+            return None;
+        }
+    }
+    for j in 0..3 {
+        let edge = tri[(j + 1) % 3] - tri[j];
+        for (i, axis) in obb.axes.into_iter().enumerate() {
+            let cross = edge.cross(axis);
+            if cross.length_squared() < PARALLEL_EPS {
+                continue;
+            }
+            if !test(cross.normalize(), SatAxis::Edges(i, j)) {
+                return None;
+            }
+        }
+    }
+    let (_, normal, depth, origin) = best?;
+    let points = match origin {
+        SatAxis::FaceA => obb.face_contact_points(normal, &tri),
+        // Face de la boîte tournée vers le triangle, découpée par ses arêtes, gardée au-delà de son plan.
+        SatAxis::FaceB => {
+            let mut polygon = obb.face_toward(normal).to_vec();
+            let facing = triangle_normal(tri);
+            for j in 0..3 {
+                let side = (tri[(j + 1) % 3] - tri[j]).cross(facing);
+                polygon = clip_polygon(&polygon, side, side.dot(tri[j]));
+            }
+            let plane = tri[0].dot(normal) - CONTACT_SLOP;
+            polygon.retain(|p| p.dot(normal) >= plane);
+            ContactPoints::reduce(&polygon, normal, normal)
+        }
+        SatAxis::Edges(i, j) => {
+            let (a0, a1) = obb.support_edge(i, normal);
+            let (p, q)   = closest_between_segments(a0, a1, tri[j], tri[(j + 1) % 3]);
+            Some(ContactPoints::one((p + q) * 0.5))
+        }
+    };
+    let points = points.unwrap_or_else(|| ContactPoints::one(closest_on_triangle(obb.center, tri)));
+    Some(Contact { normal, depth, points })
+}
+
+/// Polygone convexe `polygon` découpé par le demi-espace `normal · p <= offset` (Sutherland-Hodgman).
+fn clip_polygon(polygon: &[Vec3], normal: Vec3, offset: f32) -> Vec<Vec3> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (da, db) = (a.dot(normal) - offset, b.dot(normal) - offset);
+        if da <= 0.0 {
+            clipped.push(a);
+        }
+        if (da <= 0.0) != (db <= 0.0) {
+            clipped.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+    clipped
+}
+
+/// Normale unitaire (sens direct a → b → c).
+fn triangle_normal([a, b, c]: [Vec3; 3]) -> Vec3 {
+    (b - a).cross(c - a).normalize_or_zero()
+}
+
+/// Point du triangle le plus proche de `p` (régions de Voronoï, cf. Ericson).
+fn closest_on_triangle(p: Vec3, [a, b, c]: [Vec3; 3]) -> Vec3 {
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Points les plus proches entre le segment [p0, p1] et le triangle : point de traversée
+/// si le segment coupe le triangle, sinon meilleur des extrémités et des arêtes.
+fn closest_segment_triangle(p0: Vec3, p1: Vec3, tri: [Vec3; 3]) -> (Vec3, Vec3) {
+    let d = p1 - p0;
+    if let Some(t) = crate::trimesh::ray_triangle(p0, d, tri).filter(|&t| t <= 1.0) {
+        let hit = p0 + d * t;
+        return (hit, hit);
+    }
+    let mut best = (p0, closest_on_triangle(p0, tri));
+    let mut consider = |p: Vec3, q: Vec3| {
+        if p.distance_squared(q) < best.0.distance_squared(best.1) {
+            best = (p, q);
+        }
+    };
+    consider(p1, closest_on_triangle(p1, tri));
+    for i in 0..3 {
+        let (p, q) = closest_between_segments(p0, p1, tri[i], tri[(i + 1) % 3]);
+        consider(p, q);
+    }
+    best
+}
+
+/// Test SAT entre deux OBB.
+fn obb_contact(a: &Obb, b: &Obb) -> Option<Contact> {
+    let diff = b.center - a.center;
+    let mut best: Option<(f32, Vec3, f32, SatAxis)> = None;  // (score, normale, profondeur, origine)
+
+    let mut test = |axis: Vec3, origin: SatAxis| -> bool {
+        let dist    = diff.dot(axis);
+        let overlap = a.radius_along(axis) + b.radius_along(axis) - dist.abs();
+        if overlap <= 0.0 {
+            return false;
+        }
+        let score = if matches!(origin, SatAxis::Edges(..)) { overlap / EDGE_BIAS } else { overlap };
+        if best.is_none_or(|(best_score, ..)| score < best_score) {
+            // Orienter la normale de A vers B.
+            let normal = if dist < 0.0 { -axis } else { axis };
+            best = Some((score, normal, overlap, origin));
+        }
+        true
+    };
+
+    for axis in a.axes {
+        if !test(axis, SatAxis::FaceA) {
+            return None;
+        }
+    }
+    for axis in b.axes {
+        if !test(axis, SatAxis::FaceB) {
+            return None;
+        }
+    }
+    for (i, ea) in a.axes.into_iter().enumerate() {
+        for (j, eb) in b.axes.into_iter().enumerate() {
+            let axis = ea.cross(eb);
+            if axis.length_squared() < PARALLEL_EPS {
+                continue;
+            }
+            if !test(axis.normalize(), SatAxis::Edges(i, j)) {
+                return None;
+            }
+        }
+    }
+    let (_, normal, depth, origin) = best?;
+    let points = match origin {
+        SatAxis::FaceA => a.face_contact_points(normal, &b.face_toward(-normal)),
+        SatAxis::FaceB => b.face_contact_points(-normal, &a.face_toward(normal)),
+        SatAxis::Edges(i, j) => {
+            let (a0, a1) = a.support_edge(i, normal);
+            let (b0, b1) = b.support_edge(j, -normal);
+            let (p, q)   = closest_between_segments(a0, a1, b0, b1);
+            Some(ContactPoints::one((p + q) * 0.5))
+        }
+    };
+    let points = points.unwrap_or_else(|| ContactPoints::one((a.center + b.center) * 0.5));
+    Some(Contact { normal, depth, points })
+}
+
+/// Deux sphères ; centres confondus → normale arbitraire vers +Y.
+fn spheres(ca: Vec3, ra: f32, cb: Vec3, rb: f32) -> Option<Contact> {
+    let diff  = cb - ca;
+    let reach = ra + rb;
+    let dist2 = diff.length_squared();
+    if dist2 >= reach * reach {
+        return None;
+    }
+    let dist   = dist2.sqrt();
+    let normal = if dist > 1e-6 { diff / dist } else { Vec3::Y };
+    Some(Contact::with_sphere(normal, reach - dist, cb, rb))
+}
+
+/// Boîte (A) / sphère (B).
+fn box_sphere(obb: &Obb, center: Vec3, radius: f32) -> Option<Contact> {
+    let local   = obb.local_point(center);
+    let clamped = local.clamp(-obb.half_extents, obb.half_extents);
+    let outside = local - clamped;
+
+    if outside != Vec3::ZERO {
+        let dist2 = outside.length_squared();
+        if dist2 >= radius * radius {
+            return None;
+        }
+        let dist = dist2.sqrt();
+        return Some(Contact::with_sphere(obb.world_dir(outside / dist), radius - dist, center, radius));
+    }
+
+    // Centre dans la boîte : sortir par la face la plus proche.
+    let gap  = obb.half_extents - local.abs();
+    let axis = if gap.x <= gap.y && gap.x <= gap.z { 0 } else if gap.y <= gap.z { 1 } else { 2 };
+    let mut dir = Vec3::ZERO;
+    dir[axis] = if local[axis] < 0.0 { -1.0 } else { 1.0 };
+    Some(Contact::with_sphere(obb.world_dir(dir), gap[axis] + radius, center, radius))
+}
+
+/// Boîte (A) / capsule (B) : la distance signée le long du segment est convexe,
+/// on cherche son minimum par section dorée puis on teste la sphère en ce point.
+fn box_capsule(obb: &Obb, a: Vec3, b: Vec3, radius: f32) -> Option<Contact> {
+    const INV_PHI: f32 = 0.618_034;
+    let at = |t: f32| a.lerp(b, t);
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    for _ in 0..SEGMENT_SEARCH_ITERS {
+        let t1 = hi - (hi - lo) * INV_PHI;
+        let t2 = lo + (hi - lo) * INV_PHI;
+        if obb.signed_distance(at(t1)) <= obb.signed_distance(at(t2)) {
+            hi = t2;
+        } else {
+            lo = t1;
+        }
+    }
+    box_sphere(obb, at((lo + hi) * 0.5), radius)
+}
+
+/// Point du segment [a, b] le plus proche de `p`.
+fn closest_on_segment(p: Vec3, a: Vec3, b: Vec3) -> Vec3 {
+    let ab   = b - a;
+    let len2 = ab.length_squared();
+    if len2 < 1e-12 {
+        return a;
+    }
+    a + ab * ((p - a).dot(ab) / len2).clamp(0.0, 1.0)
+}
+
+/// Points les plus proches entre les segments [p0, p1] et [q0, q1].
+fn closest_between_segments(p0: Vec3, p1: Vec3, q0: Vec3, q1: Vec3) -> (Vec3, Vec3) {
+    let d1 = p1 - p0;
+    let d2 = q1 - q0;
+    let r  = p0 - q0;
+    let a  = d1.length_squared();
+    let e  = d2.length_squared();
+    let f  = d2.dot(r);
+
+    if a < 1e-12 && e < 1e-12 {
+        return (p0, q0);
+    }
+    if a < 1e-12 {
+        return (p0, q0 + d2 * (f / e).clamp(0.0, 1.0));
+    }
+    let c = d1.dot(r);
+    if e < 1e-12 {
+        return (p0 + d1 * (-c / a).clamp(0.0, 1.0), q0);
+    }
+
+    let b     = d1.dot(d2);
+    let denom = a * e - b * b;
+    // Segments parallèles (denom ≈ 0) : n'importe quel s convient, on part de 0.
+    let mut s = if denom > 1e-12 { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+    (p0 + d1 * s, q0 + d2 * t)
+}
+
+/// Premier impact du rayon `origin + t * dir` (`dir` unitaire, 0 ≤ t ≤ `max_dist`)
+/// sur la forme. Un rayon qui part de l'intérieur de la forme l'ignore.
+pub(crate) fn ray_cast(shape: &Shape, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<CastHit> {
+    let (distance, normal) = match *shape {
+        Shape::Box(obb) => ray_obb(&obb, origin, dir)?,
+        Shape::Sphere { center, radius } => ray_sphere(origin, dir, center, radius)?,
+        Shape::Capsule { a, b, radius } => ray_capsule(origin, dir, a, b, radius)?,
+        Shape::Mesh(ref mesh) => ray_mesh(mesh, origin, dir, max_dist)?,
+    };
+    (distance <= max_dist).then(|| CastHit { distance, point: origin + dir * distance, normal })
+}
+
+/// Premier contact de la forme `moving` translatée de `t * dir` (`dir` unitaire,
+/// 0 ≤ t ≤ `max_dist`) avec `target`. Une cible déjà chevauchée au départ est ignorée.
+///
+/// Avance par pas d'une demi-épaisseur de `moving` le long de `dir` (deux positions
+/// successives se recouvrent, la cible ne peut pas passer entre elles), puis affine
+/// l'instant du premier chevauchement par dichotomie avec `contact`.
+pub(crate) fn shape_cast(moving: &Shape, dir: Vec3, max_dist: f32, target: &Shape) -> Option<CastHit> {
+    if contact(moving, target).is_some() {
+        return None;
+    }
+    // Fenêtre où les boîtes englobantes peuvent se toucher.
+    let reach = moving.aabb();
+    let half  = (reach.max - reach.min) * 0.5;
+    let window = target.aabb().expand_by(half);
+    let (enter, exit) = window.ray_interval(moving.center(), dir, max_dist)?;
+
+    let at = |t: f32| {
+        let mut s = moving.clone();
+        s.translate(dir * t);
+        s
+    };
+    let step = moving.radius_along(dir).max(MIN_CAST_STEP);
+    let mut free = 0.0;
+    let mut t    = enter;
+    loop {
+        if contact(&at(t), target).is_some() {
+            break;
+        }
+        if t >= exit {
+            return None;
+        }
+        free = t;
+        t = (t + step).min(exit);
+    }
+
+    let mut touching = t;
+    for _ in 0..CAST_BISECT_ITERS {
+        let mid = (free + touching) * 0.5;
+        if contact(&at(mid), target).is_some() {
+            touching = mid;
+        } else {
+            free = mid;
+        }
+    }
+    let c     = contact(&at(touching), target)?;
+    let point = at(free).center() + c.normal * moving.radius_along(c.normal);
+    Some(CastHit { distance: free, point, normal: -c.normal })
+}
+
+/// Rayon / boîte : intersection des trois tranches dans le repère de la boîte.
+fn ray_obb(obb: &Obb, origin: Vec3, dir: Vec3) -> Option<(f32, Vec3)> {
+    let o = obb.local_point(origin);
+    let d = obb.local_dir(dir);
+    let (mut near, mut far) = (f32::NEG_INFINITY, f32::INFINITY);
+    let mut near_axis = 0;
+    for axis in 0..3 {
+        let he = obb.half_extents[axis];
+        if d[axis].abs() < PARALLEL_EPS {
+            if o[axis].abs() > he {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = ((-he - o[axis]) / d[axis], (he - o[axis]) / d[axis]);
+        let (t0, t1) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+        if t0 > near {
+            near = t0;
+            near_axis = axis;
+        }
+        far = far.min(t1);
+    }
+    // near < 0 : origine dans la boîte (ou boîte derrière le rayon).
+    if near > far || near < 0.0 {
+        return None;
+    }
+    let mut normal = Vec3::ZERO;
+    normal[near_axis] = -d[near_axis].signum();
+    Some((near, obb.world_dir(normal)))
+}
+
+/// Rayon / maillage : rayon ramené en espace local (t inchangé), normale de la face
+/// touchée tournée vers l'origine du rayon.
+fn ray_mesh(mesh: &MeshShape, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<(f32, Vec3)> {
+    let local_origin = mesh.to_local.transform_point3(origin);
+    let local_dir    = mesh.to_local.transform_vector3(dir);
+    let (t, index)   = mesh.mesh.ray(local_origin, local_dir, max_dist)?;
+    let normal = triangle_normal(mesh.triangle(index));
+    Some((t, if normal.dot(dir) > 0.0 { -normal } else { normal }))
+}
+
+/// Rayon / sphère.
+fn ray_sphere(origin: Vec3, dir: Vec3, center: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+    let m = origin - center;
+    let b = m.dot(dir);
+    let c = m.length_squared() - radius * radius;
+    // Origine dedans, ou sphère derrière le rayon.
+    if c <= 0.0 || b > 0.0 {
+        return None;
+    }
+    let disc = b * b - c;
+    if disc < 0.0 {
+        return None;
+    }
+    let t = -b - disc.sqrt();
+    Some((t, (m + dir * t) / radius))
+}
+
+/// Rayon / capsule : premier impact sur le cylindre central ou sur l'une des sphères
+/// d'extrémité (la capsule est leur union).
+fn ray_capsule(origin: Vec3, dir: Vec3, a: Vec3, b: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+    if (origin - closest_on_segment(origin, a, b)).length_squared() <= radius * radius {
+        return None;
+    }
+    let caps = [ray_sphere(origin, dir, a, radius), ray_sphere(origin, dir, b, radius)];
+    let mut best = caps.into_iter().flatten().min_by(|x, y| x.0.total_cmp(&y.0));
+
+    let ab  = b - a;
+    let len = ab.length();
+    if len > 1e-6 {
+        // Composantes orthogonales à l'axe : intersection avec le cylindre infini.
+        let n  = ab / len;
+        let ao = origin - a;
+        let dp = dir - n * dir.dot(n);
+        let op = ao - n * ao.dot(n);
+        let qa = dp.length_squared();
+        let qb = op.dot(dp);
+        let qc = op.length_squared() - radius * radius;
+        let disc = qb * qb - qa * qc;
+        if qa > PARALLEL_EPS && disc >= 0.0 {
+            let t = (-qb - disc.sqrt()) / qa;
+            let s = (ao + dir * t).dot(n);
+            if t >= 0.0 && (0.0..=len).contains(&s) && best.is_none_or(|(bt, _)| t < bt) {
+                best = Some((t, (op + dp * t) / radius));
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use glam::Quat;
+
+    use super::*;
+
+    const EPS: f32 = 1e-4;
+
+    fn cube(at: Vec3, rotation: Quat) -> Shape {
+        Shape::Box(Obb::from_matrix(Mat4::from_rotation_translation(rotation, at), Vec3::splat(0.5)))
+    }
+
+    fn assert_contact(c: Option<Contact>, normal: Vec3, depth: f32) -> Contact {
+        let c = c.expect("contact attendu");
+        assert!(c.normal.abs_diff_eq(normal, EPS), "normale {:?}, attendue {normal:?}", c.normal);
+        assert!((c.depth - depth).abs() < EPS, "profondeur {}, attendue {depth}", c.depth);
+        c
+    }
+
+    #[test]
+    fn box_on_box_edge_rotated_45() {
+        // Cube tourné de 45° autour de Z, posé sur son arête (y = 0.4) : 0.1 dans le cube du bas.
+        let half_diag = 0.5 * std::f32::consts::SQRT_2;
+        let top = cube(Vec3::new(0.0, 0.4 + half_diag, 0.0), Quat::from_rotation_z(FRAC_PI_4));
+        let c = assert_contact(contact(&cube(Vec3::ZERO, Quat::IDENTITY), &top), Vec3::Y, 0.1);
+        for p in c.points.iter() {
+            assert!(p.x.abs() < 0.05 && (0.4..=0.5).contains(&p.y), "point {p:?} hors de l'arête");
+        }
+        // Vu depuis la boîte tournée : même profondeur, normale opposée.
+        assert_contact(contact(&top, &cube(Vec3::ZERO, Quat::IDENTITY)), -Vec3::Y, 0.1);
+    }
+
+    #[test]
+    fn box_beside_box_rotated_45_about_y() {
+        // Étendue de la boîte tournée selon X : 0.5·√2 ; recouvrement 0.5 + 0.707 - 1.1.
+        let b = cube(Vec3::new(1.1, 0.0, 0.0), Quat::from_rotation_y(FRAC_PI_4));
+        let depth = 0.5 + 0.5 * std::f32::consts::SQRT_2 - 1.1;
+        assert_contact(contact(&cube(Vec3::ZERO, Quat::IDENTITY), &b), Vec3::X, depth);
+    }
+
+    #[test]
+    fn separated_boxes_return_none() {
+        let a = cube(Vec3::ZERO, Quat::IDENTITY);
+        assert!(contact(&a, &cube(Vec3::new(1.01, 0.0, 0.0), Quat::IDENTITY)).is_none());
+        // Les AABB se chevauchent, mais la diagonale sépare les deux boîtes.
+        let b = cube(Vec3::new(1.1, 1.1, 0.0), Quat::from_rotation_z(FRAC_PI_4));
+        assert!(a.aabb().overlaps(&b.aabb()));
+        assert!(contact(&a, &b).is_none(), "axe séparateur ignoré");
+    }
+
+    #[test]
+    fn sphere_against_box_edge() {
+        // Arête (0.5, 0.5, z) à 0.3·√2 du centre : normale diagonale, profondeur 0.5 - 0.424.
+        let sphere = Shape::Sphere { center: Vec3::new(0.8, 0.8, 0.0), radius: 0.5 };
+        let normal = Vec3::new(1.0, 1.0, 0.0).normalize();
+        let depth  = 0.5 - 0.3 * std::f32::consts::SQRT_2;
+        assert_contact(contact(&cube(Vec3::ZERO, Quat::IDENTITY), &sphere), normal, depth);
+        assert_contact(contact(&sphere, &cube(Vec3::ZERO, Quat::IDENTITY)), -normal, depth);
+    }
+
+    #[test]
+    fn parallel_capsules() {
+        let a = Shape::Capsule { a: Vec3::NEG_Y, b: Vec3::Y, radius: 0.5 };
+        let b = Shape::Capsule { a: Vec3::new(0.8, -0.5, 0.0), b: Vec3::new(0.8, 1.5, 0.0), radius: 0.5 };
+        assert_contact(contact(&a, &b), Vec3::X, 0.2);
+    }
+
+    #[test]
+    fn crossed_capsules() {
+        let a = Shape::Capsule { a: Vec3::NEG_X, b: Vec3::X, radius: 0.5 };
+        let b = Shape::Capsule { a: Vec3::new(0.0, 0.8, -1.0), b: Vec3::new(0.0, 0.8, 1.0), radius: 0.5 };
+        let c = assert_contact(contact(&a, &b), Vec3::Y, 0.2);
+        let point = c.points.iter().next().unwrap();
+        assert!(point.abs_diff_eq(Vec3::new(0.0, 0.4, 0.0), EPS), "point {point:?} hors du recouvrement");
+    }
+
+    #[test]
+    fn capsule_on_box() {
+        let cube = cube(Vec3::ZERO, Quat::IDENTITY);
+        let lying = Shape::Capsule { a: Vec3::new(-1.0, 0.9, 0.0), b: Vec3::new(1.0, 0.9, 0.0), radius: 0.5 };
+        assert_contact(contact(&cube, &lying), Vec3::Y, 0.1);
+        // Capsule inclinée : seule l'extrémité basse (0, 0.7, 0) touche la face du dessus.
+        let tilted = Shape::Capsule { a: Vec3::new(0.0, 0.7, 0.0), b: Vec3::new(1.0, 1.7, 0.0), radius: 0.5 };
+        assert_contact(contact(&tilted, &cube), -Vec3::Y, 0.3);
+    }
+
+    #[test]
+    fn separated_round_shapes_return_none() {
+        // Coin (0.5, 0.5, 0.5) à 0.3·√3 ≈ 0.52 du centre, plus que le rayon.
+        let sphere = Shape::Sphere { center: Vec3::splat(0.8), radius: 0.5 };
+        assert!(contact(&cube(Vec3::ZERO, Quat::IDENTITY), &sphere).is_none());
+        let a = Shape::Capsule { a: Vec3::NEG_X, b: Vec3::X, radius: 0.5 };
+        let b = Shape::Capsule { a: Vec3::new(0.0, 1.01, -1.0), b: Vec3::new(0.0, 1.01, 1.0), radius: 0.5 };
+        assert!(contact(&a, &b).is_none());
+        assert!(contact(&a, &Shape::Sphere { center: Vec3::new(1.9, 0.0, 0.0), radius: 0.3 }).is_none());
+    }
+}
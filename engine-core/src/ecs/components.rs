@@ -1,8 +1,14 @@
-use glam::Vec3;
+use std::sync::Arc;
 
+use glam::{EulerRot, Mat3, Mat4, Quat, Vec3};
+
+use super::Entity;
+use crate::trimesh::TriMesh;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Transform {
     pub position: Vec3,
-    pub rotation: Vec3,
+    pub rotation: Quat,
     pub scale:    Vec3,
 }
 
@@ -10,12 +16,61 @@ impl Default for Transform {
     fn default() -> Self {
         Transform {
             position: Vec3::ZERO,
-            rotation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
             scale:    Vec3::ONE,
         }
     }
 }
 
+impl Transform {
+    /// Angles d'Euler XYZ en degrés → quaternion (convention de l'éditeur et des scènes).
+    pub fn quat_from_euler_deg(euler: Vec3) -> Quat {
+        Quat::from_euler(
+            EulerRot::XYZ,
+            euler.x.to_radians(),
+            euler.y.to_radians(),
+            euler.z.to_radians(),
+        )
+    }
+
+    /// Quaternion → angles d'Euler XYZ en degrés. Pour l'affichage uniquement :
+    /// la décomposition n'est pas unique (y ∈ [-90°, 90°]).
+    pub fn euler_deg_from_quat(q: Quat) -> Vec3 {
+        let (x, y, z) = q.to_euler(EulerRot::XYZ);
+        Vec3::new(x.to_degrees(), y.to_degrees(), z.to_degrees())
+    }
+
+    /// Rotation en angles d'Euler XYZ, en degrés (cf. `euler_deg_from_quat`).
+    pub fn euler_deg(&self) -> Vec3 {
+        Self::euler_deg_from_quat(self.rotation)
+    }
+
+    /// Matrice locale translation * rotation * scale.
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.position)
+    }
+
+    /// Matrice locale interpolée entre `self` (état précédent) et `next` ; `alpha` ∈ [0, 1].
+    pub fn lerp_matrix(&self, next: &Transform, alpha: f32) -> Mat4 {
+        Mat4::from_scale_rotation_translation(
+            self.scale.lerp(next.scale, alpha),
+            self.rotation.slerp(next.rotation, alpha),
+            self.position.lerp(next.position, alpha),
+        )
+    }
+}
+
+/// Matrices monde en cache, recalculées par `WorldCore::propagate_transforms`.
+/// Lire via `WorldCore::world_matrix` / `render_matrix`, qui retombent sur le calcul direct si le cache est périmé.
+pub struct GlobalTransform {
+    pub world:  Mat4,   // état physique courant
+    pub render: Mat4,   // interpolé entre les deux derniers pas fixes
+}
+
+/// Transform au début du dernier pas fixe de physique.
+/// Le rendu interpole entre cet état et le Transform courant (cf. `WorldCore::compute_render_matrix`).
+pub struct PreviousTransform(pub Transform);
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum MeshType {
     #[default]
@@ -43,26 +98,277 @@ pub struct Material {
 
 // ── RigidBody ───────────────────────────────────────────────────────────────
 
+/// Comportement d'un RigidBody face à la physique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyType {
+    /// Gravité, forces et contacts.
+    #[default]
+    Dynamic,
+    /// Fixe (sol, murs) — pas d'intégration.
+    Static,
+    /// Déplacé par sa `velocity` ou par script (`set_position`), jamais par les contacts ;
+    /// pousse les corps dynamiques et transporte ceux qui reposent dessus.
+    Kinematic,
+}
+
 pub struct RigidBody {
-    pub velocity:  Vec3,
-    pub is_static: bool,   // true = entité fixe (sol, murs) — pas d'intégration
-    pub on_ground: bool,   // mis à jour par PhysicsSystem chaque frame
+    pub velocity:    Vec3,
+    pub angular_velocity: Vec3,   // rad/s, axe monde × vitesse
+    pub body_type:   BodyType,
+    pub on_ground:   bool,            // mis à jour par PhysicsSystem chaque frame
+    pub ground:      Option<Entity>,  // statique / kinematic sous le corps au dernier pas
+    pub mass:        f32,    // kg, > 0 ; ignorée hors Dynamic (masse infinie)
+    pub restitution: f32,    // 0 = pas de rebond, 1 = rebond parfait
+    pub friction:    f32,    // coefficient de Coulomb (0 = glace)
+    /// Détection continue : le collider est balayé le long du déplacement du pas et
+    /// s'arrête au premier impact (projectiles, chutes rapides). Dynamic uniquement.
+    pub ccd:         bool,
+    /// Amortissements (1/s) : la vitesse est divisée par `1 + damping·dt` à chaque pas.
+    pub linear_damping:  f32,
+    pub angular_damping: f32,
+    /// Multiplie la gravité du monde pour ce corps (0 = flotte, -1 = tombe vers le haut).
+    pub gravity_scale:   f32,
+    // Forces (N) et couples (N·m) cumulés par `apply_force` / `apply_torque`, appliqués à
+    // chaque pas fixe de l'update en cours puis remis à zéro.
+    pub(crate) force:  Vec3,
+    pub(crate) torque: Vec3,
+    // Kinematic : position en fin de pas précédent et déplacement pendant le pas courant
+    // (vitesse + scripts), cf. systems::integrate.
+    pub(crate) settled_position: Option<Vec3>,
+    pub(crate) step_motion:      Vec3,
+    // Sommeil (cf. systems::update_sleep) : un corps endormi n'est ni intégré ni testé contre
+    // les statiques et les autres dormeurs. `sleep_timer` : secondes passées sous les seuils
+    // de repos ; `island` : clé partagée par les corps endormis ensemble.
+    pub(crate) sleeping:    bool,
+    pub(crate) sleep_timer: f32,
+    pub(crate) island:      Option<Entity>,
+}
+
+impl RigidBody {
+    /// Masse minimale : évite une masse inverse infinie.
+    pub const MIN_MASS: f32 = 1e-3;
+    /// Moment d'inertie minimal (kg·m²) : collider de taille nulle.
+    pub const MIN_INERTIA: f32 = 1e-6;
+
+    pub fn is_dynamic(&self) -> bool {
+        self.body_type == BodyType::Dynamic
+    }
+
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// 1 / masse, 0 pour un corps statique ou kinematic.
+    pub fn inverse_mass(&self) -> f32 {
+        if self.is_dynamic() { 1.0 / self.mass.max(Self::MIN_MASS) } else { 0.0 }
+    }
+
+    /// Tenseur d'inertie inverse en axes monde, d'après la forme du collider posée avec
+    /// `rotation` et `scale` ; sphère pleine d'1 m de diamètre à défaut. Nul hors Dynamic.
+    pub fn inverse_inertia(&self, shape: Option<&ColliderShape>, rotation: Quat, scale: Vec3) -> Mat3 {
+        if !self.is_dynamic() {
+            return Mat3::ZERO;
+        }
+        let mass    = self.mass.max(Self::MIN_MASS);
+        let moments = shape.and_then(|s| s.principal_inertia(mass, scale)).unwrap_or(Vec3::splat(0.1 * mass));
+        let r = Mat3::from_quat(rotation);
+        r * Mat3::from_diagonal(moments.max(Vec3::splat(Self::MIN_INERTIA)).recip()) * r.transpose()
+    }
 }
 
 impl Default for RigidBody {
     fn default() -> Self {
         RigidBody {
-            velocity:  Vec3::ZERO,
-            is_static: false,
-            on_ground: false,
+            velocity:    Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            body_type:   BodyType::Dynamic,
+            on_ground:   false,
+            ground:      None,
+            mass:        1.0,
+            restitution: 0.0,
+            friction:    0.5,
+            ccd:         false,
+            linear_damping:  0.0,
+            angular_damping: 0.05,
+            gravity_scale:   1.0,
+            force:  Vec3::ZERO,
+            torque: Vec3::ZERO,
+            settled_position: None,
+            step_motion:      Vec3::ZERO,
+            sleeping:    false,
+            sleep_timer: 0.0,
+            island:      None,
         }
     }
 }
 
-// ── Collider AABB ───────────────────────────────────────────────────────────
+// ── Collider ──────────────────────────────────────────────────────────────
+
+/// Forme de collision, centrée sur Transform.position et orientée par la rotation monde.
+/// Dimensions locales, multipliées par le scale monde.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColliderShape {
+    Box     { half_extents: Vec3 },
+    Sphere  { radius: f32 },
+    /// Axe local Y ; `half_height` = demi-longueur du segment central, hémisphères non compris.
+    Capsule { radius: f32, half_height: f32 },
+    /// Triangles d'un mesh custom (cf. `WorldCore::add_mesh_collider`). Statiques et
+    /// kinematics uniquement : un corps dynamique ignore un collider maillage.
+    Mesh    { mesh: Arc<TriMesh> },
+}
+
+impl ColliderShape {
+    /// Demi-dimensions de la boîte englobante locale (centrée sur l'origine).
+    pub fn half_extents(&self) -> Vec3 {
+        match self {
+            ColliderShape::Box { half_extents }            => *half_extents,
+            ColliderShape::Sphere { radius }               => Vec3::splat(*radius),
+            ColliderShape::Capsule { radius, half_height } => Vec3::new(*radius, half_height + radius, *radius),
+            ColliderShape::Mesh { mesh } => {
+                let bounds = mesh.bounds();
+                bounds.min.abs().max(bounds.max.abs())
+            }
+        }
+    }
+
+    pub fn is_mesh(&self) -> bool {
+        matches!(self, ColliderShape::Mesh { .. })
+    }
+
+    /// Moments d'inertie principaux (kg·m², axes locaux) du solide plein de masse `mass`,
+    /// dimensions multipliées par `scale` comme pour la collision. None pour un maillage.
+    pub fn principal_inertia(&self, mass: f32, scale: Vec3) -> Option<Vec3> {
+        let scale = scale.abs();
+        match *self {
+            ColliderShape::Box { half_extents } => {
+                let h2 = (half_extents * scale).powf(2.0);
+                Some(Vec3::new(h2.y + h2.z, h2.x + h2.z, h2.x + h2.y) * (mass / 3.0))
+            }
+            ColliderShape::Sphere { radius } => {
+                let r = radius * scale.max_element();
+                Some(Vec3::splat(0.4 * mass * r * r))
+            }
+            // Cylindre + deux hémisphères, masse répartie selon les volumes.
+            ColliderShape::Capsule { radius, half_height } => {
+                let (r, h) = (radius * scale.x.max(scale.z), half_height * scale.y);
+                let cylinder = 2.0 * h;
+                let sphere   = 4.0 / 3.0 * r;
+                let mc = mass * cylinder / (cylinder + sphere);
+                let ms = mass - mc;
+                let axial = mc * r * r / 2.0 + ms * 0.4 * r * r;
+                let cross = mc * (h * h / 3.0 + r * r / 4.0) + ms * (0.4 * r * r + h * h + 0.75 * h * r);
+                Some(Vec3::new(cross, axial, cross))
+            }
+            ColliderShape::Mesh { .. } => None,
+        }
+    }
+}
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Collider {
-    pub half_extents: Vec3,  // demi-dimensions ; centre = Transform.position
+    pub shape:      ColliderShape,
+    /// Trigger : détecte les chevauchements (événements) sans jamais être repoussé.
+    pub is_trigger: bool,
+    /// Couches auxquelles appartient le collider (un bit par couche).
+    pub layer:      u32,
+    /// Couches avec lesquelles il interagit : contacts et triggers.
+    pub mask:       u32,
+}
+
+impl Collider {
+    /// Couche par défaut : la couche 0.
+    pub const DEFAULT_LAYER: u32 = 1;
+    /// Masque par défaut : toutes les couches.
+    pub const DEFAULT_MASK: u32 = u32::MAX;
+
+    pub fn new(shape: ColliderShape) -> Self {
+        Collider { shape, is_trigger: false, layer: Self::DEFAULT_LAYER, mask: Self::DEFAULT_MASK }
+    }
+
+    /// Deux colliders interagissent si chacun a une couche de l'autre dans son masque.
+    pub fn interacts_with(&self, other: &Collider) -> bool {
+        self.layer & other.mask != 0 && other.layer & self.mask != 0
+    }
+}
+
+// ── CharacterController ───────────────────────────────────────────────────
+
+/// Déplacement (marche + saut) d'un RigidBody dynamique. Le joueur suit le clavier (WASD +
+/// saut) orienté par la caméra ; les autres suivent une intention posée par script.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharacterController {
+    pub speed:        f32,   // m/s, vitesse horizontale max
+    pub acceleration: f32,   // m/s², au sol
+    pub air_control:  f32,   // fraction de `acceleration` en l'air (0..1)
+    pub jump_height:  f32,   // m, hauteur du saut
+    pub step_height:  f32,   // m, marche franchie sans sauter
+    pub slope_limit:  f32,   // degrés, pente max considérée comme un sol
+    pub coyote_time:  f32,   // s, saut encore permis après avoir quitté le sol
+    pub jump_buffer:  f32,   // s, saut mémorisé avant de toucher le sol
+    pub player:       bool,  // suit l'input et le yaw caméra (cf. WorldCore::set_player)
+    // Intention des non-joueurs, cf. WorldCore::set_character_intent.
+    pub(crate) intent:       Vec3,  // direction XZ voulue, longueur ≤ 1
+    pub(crate) jump_request: bool,  // saut demandé, consommé au pas suivant
+    // État entre les pas, cf. systems::drive_characters.
+    pub(crate) air_time:    f32,   // temps depuis le dernier contact sol
+    pub(crate) jump_queued: f32,   // temps restant de la demande de saut
+    pub(crate) jump_held:   bool,  // touche de saut enfoncée au pas précédent
+}
+
+impl CharacterController {
+    /// Composante Y minimale d'une normale de sol, d'après `slope_limit`.
+    pub fn min_ground_normal_y(&self) -> f32 {
+        self.slope_limit.clamp(0.0, 89.0).to_radians().cos()
+    }
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterController {
+            speed:        5.0,
+            acceleration: 60.0,
+            air_control:  0.3,
+            jump_height:  1.25,
+            step_height:  0.3,
+            slope_limit:  45.0,
+            coyote_time:  0.1,
+            jump_buffer:  0.1,
+            player:       false,
+            intent:       Vec3::ZERO,
+            jump_request: false,
+            air_time:     f32::INFINITY,
+            jump_queued:  0.0,
+            jump_held:    false,
+        }
+    }
+}
+
+// ── Joint ─────────────────────────────────────────────────────────────────
+
+/// Liaison d'un `Joint`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointKind {
+    /// Les deux ancres restent à `length` l'une de l'autre (tige rigide).
+    Distance { length: f32 },
+    /// Les ancres coïncident, rotation libre autour du pivot (lampe suspendue, chaîne).
+    BallSocket,
+    /// Les ancres coïncident, rotation autour de `axis` seulement (repère de la cible).
+    /// `limits` : angles min / max en degrés, 0 = pose à la création du joint.
+    Hinge { axis: Vec3, limits: Option<(f32, f32)> },
+    /// Pose relative figée (soudure).
+    Fixed,
+}
+
+/// Liaison entre le RigidBody de l'entité et celui de `target`, ou un point fixe du monde.
+/// Hors `Distance`, la rotation de l'entité est déduite de la liaison (cf. `joints::solve_joints`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Joint {
+    pub kind:          JointKind,
+    pub target:        Option<Entity>,
+    pub anchor:        Vec3,   // repère local de l'entité
+    pub target_anchor: Vec3,   // repère local de `target`, monde sans target
+    /// Rotation de l'entité relative à la cible à la création (angle 0 d'une charnière).
+    pub rest_rotation: Quat,
 }
 
 // ── PointLight ────────────────────────────────────────────────────────────
@@ -75,7 +381,7 @@ pub struct PointLight {
 // ── Parent ────────────────────────────────────────────────────────────────
 
 pub struct Parent {
-    pub parent_id: usize,
+    pub parent_id: Entity,
 }
 
 // ── Camera ────────────────────────────────────────────────────────────────
@@ -92,3 +398,70 @@ impl Default for CameraComponent {
         CameraComponent { fov: 60.0, near: 0.1, far: 1000.0, follow_entity: true }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_moments(actual: Option<Vec3>, expected: Vec3) {
+        let actual = actual.expect("moments");
+        assert!(actual.abs_diff_eq(expected, 1e-4), "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn box_inertia() {
+        // Pavé plein 2 × 1 × 0.5 m : m(b² + c²)/12 par axe.
+        let shape = ColliderShape::Box { half_extents: Vec3::new(1.0, 0.5, 0.25) };
+        assert_moments(shape.principal_inertia(12.0, Vec3::ONE), Vec3::new(1.25, 4.25, 5.0));
+        // Le scale étire la boîte comme la collision : 4 × 1 × 0.5 m.
+        assert_moments(shape.principal_inertia(12.0, Vec3::new(-2.0, 1.0, 1.0)), Vec3::new(1.25, 16.25, 17.0));
+    }
+
+    #[test]
+    fn sphere_inertia() {
+        let shape = ColliderShape::Sphere { radius: 0.5 };
+        assert_moments(shape.principal_inertia(10.0, Vec3::ONE), Vec3::splat(1.0));
+        // Rayon × plus grand scale, comme la collision : 2/5 · 10 · 1.5².
+        assert_moments(shape.principal_inertia(10.0, Vec3::new(1.0, 3.0, 2.0)), Vec3::splat(9.0));
+    }
+
+    #[test]
+    fn capsule_inertia() {
+        // Sans segment : une sphère.
+        let ball = ColliderShape::Capsule { radius: 0.5, half_height: 0.0 };
+        assert_moments(ball.principal_inertia(10.0, Vec3::ONE), Vec3::splat(1.0));
+
+        // Cylindre de hauteur H = 2 et hémisphères de rayon r = 0.5, masse selon les volumes.
+        let (m, r, big_h) = (6.0_f32, 0.5_f32, 2.0_f32);
+        let shape = ColliderShape::Capsule { radius: r, half_height: big_h / 2.0 };
+        let mc = m * big_h / (big_h + 4.0 / 3.0 * r);
+        let ms = m - mc;
+        let axial = mc * r * r / 2.0 + ms * 2.0 / 5.0 * r * r;
+        let cross = mc * (big_h * big_h / 12.0 + r * r / 4.0) + ms * (2.0 / 5.0 * r * r + big_h * big_h / 4.0 + 3.0 * big_h * r / 8.0);
+        assert_moments(shape.principal_inertia(m, Vec3::ONE), Vec3::new(cross, axial, cross));
+
+        // Très fine : une tige, m·H²/12 en travers.
+        let rod = ColliderShape::Capsule { radius: 1e-3, half_height: 1.0 };
+        let moments = rod.principal_inertia(3.0, Vec3::ONE).expect("moments");
+        assert!((moments.x - 1.0).abs() < 1e-2 && moments.y < 1e-5, "{moments:?}");
+    }
+
+    #[test]
+    fn inverse_inertia_follows_rotation() {
+        let body  = RigidBody { mass: 12.0, ..RigidBody::default() };
+        let shape = ColliderShape::Box { half_extents: Vec3::new(1.0, 0.5, 0.25) };
+        let inv   = body.inverse_inertia(Some(&shape), Quat::IDENTITY, Vec3::ONE);
+        assert!(inv.abs_diff_eq(Mat3::from_diagonal(Vec3::new(1.25, 4.25, 5.0).recip()), 1e-5));
+
+        // Tournée de 90° autour de Z : la longueur passe sur Y, les moments X et Y s'échangent.
+        let turned = body.inverse_inertia(Some(&shape), Quat::from_rotation_z(std::f32::consts::FRAC_PI_2), Vec3::ONE);
+        assert!(turned.abs_diff_eq(Mat3::from_diagonal(Vec3::new(4.25, 1.25, 5.0).recip()), 1e-5));
+
+        // Sans forme : sphère pleine d'1 m de diamètre.
+        let fallback = body.inverse_inertia(None, Quat::IDENTITY, Vec3::ONE);
+        assert!(fallback.abs_diff_eq(Mat3::from_diagonal(Vec3::splat(1.0 / 1.2)), 1e-5));
+
+        let fixed = RigidBody { body_type: BodyType::Static, ..body };
+        assert_eq!(fixed.inverse_inertia(Some(&shape), Quat::IDENTITY, Vec3::ONE), Mat3::ZERO);
+    }
+}
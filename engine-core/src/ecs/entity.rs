@@ -0,0 +1,189 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Nombre de bits réservés à l'index dans la forme packée (`to_bits`).
+const INDEX_BITS: u32 = 20;
+const INDEX_MASK: u32 = (1 << INDEX_BITS) - 1;
+/// Génération sur les 12 bits hauts : revient à 0 après 4096 réutilisations d'un même slot.
+const GENERATION_MASK: u32 = u32::MAX >> INDEX_BITS;
+
+/// Handle d'entité générationnel.
+///
+/// - `index`      → slot dans les vecteurs `sparse` des `SparseSet`
+/// - `generation` → incrémentée à chaque destruction du slot ; un handle gardé
+///   après `remove_entity` / `load_scene` ne correspond plus à aucune entité vivante.
+///
+/// Côté JS le handle circule sous forme packée `u32` (`to_bits` / `from_bits`).
+/// `u32::MAX` reste le sentinel "aucune entité" : l'index `INDEX_MASK` n'est jamais alloué.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index:      u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+
+    /// Forme packée pour l'API wasm : `generation << 20 | index`.
+    pub fn to_bits(self) -> u32 {
+        (self.generation << INDEX_BITS) | self.index
+    }
+
+    pub fn from_bits(bits: u32) -> Entity {
+        Entity {
+            index:      bits & INDEX_MASK,
+            generation: bits >> INDEX_BITS,
+        }
+    }
+}
+
+/// Allocateur d'entités : table des générations + free-list des slots libérés.
+///
+/// Source de vérité pour "ce handle est-il vivant ?". Les slots libérés par
+/// `kill` sont recyclés par `create` (plus petit index d'abord, pour garder les
+/// vecteurs `sparse` compacts) avec une génération incrémentée. Un slot dont la
+/// génération atteindrait `GENERATION_MASK` est retiré définitivement plutôt que
+/// de revenir à 0, ce qui ferait revivre d'anciens handles.
+#[derive(Default)]
+pub struct EntityAllocator {
+    generations: Vec<u32>,
+    alive:       Vec<bool>,
+    free:        BinaryHeap<Reverse<u32>>,
+    live:        usize,
+}
+
+impl EntityAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Alloue un slot : recycle le plus petit index libre, sinon en ajoute un.
+    pub fn create(&mut self) -> Entity {
+        let index = match self.free.pop() {
+            Some(Reverse(i)) => i as usize,
+            None => {
+                let i = self.generations.len();
+                assert!((i as u32) < INDEX_MASK, "entity index space exhausted ({i})");
+                self.generations.push(0);
+                self.alive.push(false);
+                i
+            }
+        };
+        debug_assert!(!self.alive[index], "entity slot {index} is still alive");
+        self.alive[index] = true;
+        self.live += 1;
+        Entity { index: index as u32, generation: self.generations[index] }
+    }
+
+    /// Détruit l'entité et rend son slot à la free-list. Retourne false si le handle était déjà mort.
+    pub fn kill(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        let i = entity.index();
+        self.alive[i] = false;
+        self.live -= 1;
+        if self.generations[i] < GENERATION_MASK {
+            self.generations[i] += 1;
+            self.free.push(Reverse(i as u32));
+        }
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let i = entity.index();
+        i < self.alive.len() && self.alive[i] && self.generations[i] == entity.generation
+    }
+
+    /// Retourne le handle vivant correspondant à la forme packée, ou None si mort/invalide.
+    pub fn resolve(&self, bits: u32) -> Option<Entity> {
+        let entity = Entity::from_bits(bits);
+        self.is_alive(entity).then_some(entity)
+    }
+
+    /// Itère sur toutes les entités vivantes.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive.iter().enumerate()
+            .filter(|&(_, &alive)| alive)
+            .map(|(i, _)| Entity { index: i as u32, generation: self.generations[i] })
+    }
+
+    /// Nombre d'entités vivantes.
+    pub fn live_count(&self) -> usize {
+        self.live
+    }
+
+    /// Nombre de slots morts : en attente de recyclage ou retirés (génération épuisée).
+    pub fn dead_count(&self) -> usize {
+        self.generations.len() - self.live
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recycled_slot_rejects_old_generation() {
+        let mut entities = EntityAllocator::new();
+        let old = entities.create();
+        assert!(entities.is_alive(old));
+        assert!(entities.kill(old));
+        assert!(!entities.kill(old), "déjà mort");
+
+        let new = entities.create();
+        assert_eq!(new.index(), old.index());
+        assert_ne!(new.generation(), old.generation());
+        assert!(entities.is_alive(new));
+        assert!(!entities.is_alive(old));
+        assert_eq!(entities.resolve(old.to_bits()), None);
+        assert_eq!(entities.resolve(new.to_bits()), Some(new));
+    }
+
+    #[test]
+    fn free_list_reuses_smallest_index_first() {
+        let mut entities = EntityAllocator::new();
+        let ids: Vec<Entity> = (0..5).map(|_| entities.create()).collect();
+        for i in [3, 1, 4] {
+            entities.kill(ids[i]);
+        }
+        assert_eq!((entities.live_count(), entities.dead_count()), (2, 3));
+
+        let reused: Vec<Entity> = (0..3).map(|_| entities.create()).collect();
+        assert_eq!(reused.iter().map(|e| e.index()).collect::<Vec<_>>(), [1, 3, 4]);
+        assert!(reused.iter().all(|e| e.generation() == 1));
+        assert_eq!((entities.live_count(), entities.dead_count()), (5, 0));
+
+        // Free-list vide : nouvel index.
+        assert_eq!(entities.create().index(), 5);
+        assert_eq!(entities.iter().count(), 6);
+    }
+
+    #[test]
+    fn generation_increments_on_each_reuse() {
+        let mut entities = EntityAllocator::new();
+        let mut id = entities.create();
+        for generation in 1..=3 {
+            entities.kill(id);
+            id = entities.create();
+            assert_eq!((id.index(), id.generation()), (0, generation));
+        }
+        assert_eq!((entities.live_count(), entities.dead_count()), (1, 0));
+    }
+
+    #[test]
+    fn packed_bits_round_trip() {
+        let mut entities = EntityAllocator::new();
+        let first = entities.create();
+        entities.kill(first);
+        let id = entities.create();
+        assert_eq!(Entity::from_bits(id.to_bits()), id);
+        assert_eq!(entities.resolve(u32::MAX), None, "sentinel « aucune entité »");
+    }
+}
@@ -1,5 +1,11 @@
 pub mod components;
+pub mod entity;
+pub mod query;
+pub mod registry;
 pub mod sparse_set;
 
-pub use components::{CameraComponent, Collider, Material, MeshRenderer, MeshType, Parent, PointLight, RigidBody, Transform};
+pub use components::{BodyType, CameraComponent, CharacterController, Collider, ColliderShape, GlobalTransform, Joint, JointKind, Material, MeshRenderer, MeshType, Parent, PointLight, PreviousTransform, RigidBody, Transform};
+pub use entity::{Entity, EntityAllocator};
+pub use query::Query;
+pub use registry::Components;
 pub use sparse_set::SparseSet;
@@ -0,0 +1,324 @@
+use std::marker::PhantomData;
+
+use super::Entity;
+use super::sparse_set::{dense_index, SparseSet};
+
+/// Requête typée multi-composants sur des `SparseSet`.
+///
+/// Joint un ou plusieurs sets (`&set` en lecture, `&mut set` en écriture, ou un tuple
+/// de ceux-ci) et itère sur les entités présentes dans tous. L'itération part du set
+/// le plus petit (parmi les sets joints et les filtres `with`) et teste les autres par
+/// handle : coût O(min) au lieu de O(transforms).
+///
+/// ```ignore
+/// let (rbs, trs, cos, parents) = components.storages_mut::<(RigidBody, Transform, Collider, Parent)>();
+/// for (id, (rb, tr)) in Query::new((&*rbs, trs)).with(cos).without(parents) {
+///     tr.position += rb.velocity * dt;
+/// }
+/// ```
+///
+/// Le borrow checker garantit qu'un même set n'est pas emprunté deux fois en `&mut` ;
+/// chaque entité n'étant visitée qu'une fois, les `&mut` rendus ne se recouvrent jamais.
+/// (`Components::storages_mut` panique de même si un type y figure deux fois.)
+///
+/// ```compile_fail
+/// use engine_core::ecs::{Query, SparseSet};
+/// let mut set = SparseSet::<u32>::new();
+/// for (_, (a, b)) in Query::new((&mut set, &mut set)) {
+///     *a += *b;
+/// }
+/// ```
+pub struct Query<'a, F> {
+    fetch:   F,
+    with:    Vec<&'a dyn ComponentSet>,
+    without: Vec<&'a dyn ComponentSet>,
+}
+
+impl<'a, F: Fetch<'a>> Query<'a, F> {
+    pub fn new<Q: IntoFetch<'a, Fetch = F>>(sets: Q) -> Self {
+        Query { fetch: sets.into_fetch(), with: Vec::new(), without: Vec::new() }
+    }
+
+    /// Ne garde que les entités possédant aussi un composant de `set` (sans l'emprunter).
+    pub fn with<T: 'a>(mut self, set: &'a SparseSet<T>) -> Self {
+        self.with.push(set);
+        self
+    }
+
+    /// Exclut les entités possédant un composant de `set`.
+    pub fn without<T: 'a>(mut self, set: &'a SparseSet<T>) -> Self {
+        self.without.push(set);
+        self
+    }
+}
+
+impl<'a, F: Fetch<'a>> IntoIterator for Query<'a, F> {
+    type Item     = (Entity, F::Item);
+    type IntoIter = QueryIter<'a, F>;
+
+    fn into_iter(self) -> QueryIter<'a, F> {
+        let ids = self.with.iter()
+            .map(|set| set.ids())
+            .fold(self.fetch.ids(), |best, ids| if ids.len() < best.len() { ids } else { best });
+        QueryIter { fetch: self.fetch, ids: ids.iter(), with: self.with, without: self.without }
+    }
+}
+
+pub struct QueryIter<'a, F> {
+    fetch:   F,
+    ids:     std::slice::Iter<'a, Entity>,
+    with:    Vec<&'a dyn ComponentSet>,
+    without: Vec<&'a dyn ComponentSet>,
+}
+
+impl<'a, F: Fetch<'a>> Iterator for QueryIter<'a, F> {
+    type Item = (Entity, F::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let &id = self.ids.next()?;
+            if !self.with.iter().all(|set| set.contains(id))
+                || self.without.iter().any(|set| set.contains(id))
+            {
+                continue;
+            }
+            if !self.fetch.contains(id) {
+                continue;
+            }
+            // SAFETY : `ids` vient d'un SparseSet, chaque handle y est unique.
+            return Some((id, unsafe { self.fetch.fetch(id) }));
+        }
+    }
+}
+
+/// Vue non typée d'un SparseSet, utilisée par les filtres `with` / `without`.
+pub trait ComponentSet {
+    fn ids(&self) -> &[Entity];
+    fn contains(&self, entity: Entity) -> bool;
+}
+
+impl<T> ComponentSet for SparseSet<T> {
+    fn ids(&self) -> &[Entity] {
+        &self.ids
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        SparseSet::contains(self, entity)
+    }
+}
+
+/// Accès aux composants d'un ou plusieurs sets joints.
+pub trait Fetch<'a> {
+    type Item;
+
+    /// Handles du set directeur (le plus petit pour un tuple).
+    fn ids(&self) -> &'a [Entity];
+
+    /// True si `entity` possède tous les composants joints.
+    fn contains(&self, entity: Entity) -> bool;
+
+    /// Composant(s) de `entity`. Appelé seulement si `contains(entity)` ; un `&mut`
+    /// marque l'entrée comme changée.
+    ///
+    /// # Safety
+    /// Un même handle ne doit pas être demandé deux fois tant que l'item précédent
+    /// est vivant : deux appels produiraient deux `&mut` vers le même composant.
+    unsafe fn fetch(&mut self, entity: Entity) -> Self::Item;
+}
+
+/// Conversion `&set` / `&mut set` / tuple → `Fetch`.
+pub trait IntoFetch<'a> {
+    type Fetch: Fetch<'a>;
+    fn into_fetch(self) -> Self::Fetch;
+}
+
+pub struct FetchRef<'a, T> {
+    set: &'a SparseSet<T>,
+}
+
+impl<'a, T> Fetch<'a> for FetchRef<'a, T> {
+    type Item = &'a T;
+
+    fn ids(&self) -> &'a [Entity] {
+        &self.set.ids
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.set.contains(entity)
+    }
+
+    unsafe fn fetch(&mut self, entity: Entity) -> &'a T {
+        self.set.get(entity).expect("fetch sans contains")
+    }
+}
+
+impl<'a, T> IntoFetch<'a> for &'a SparseSet<T> {
+    type Fetch = FetchRef<'a, T>;
+    fn into_fetch(self) -> FetchRef<'a, T> {
+        FetchRef { set: self }
+    }
+}
+
+/// Accès mutable : `sparse` / `ids` restent en lecture, `dense` est muté via pointeur.
+/// Toute la requête partage un seul tick de changement.
+pub struct FetchMut<'a, T> {
+    sparse:  &'a [usize],
+    ids:     &'a [Entity],
+    dense:   *mut T,
+    changed: &'a mut [u64],
+    tick:    u64,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Fetch<'a> for FetchMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn ids(&self) -> &'a [Entity] {
+        self.ids
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        dense_index(self.sparse, self.ids, entity).is_some()
+    }
+
+    unsafe fn fetch(&mut self, entity: Entity) -> &'a mut T {
+        let idx = dense_index(self.sparse, self.ids, entity).expect("fetch sans contains");
+        self.changed[idx] = self.tick;
+        // SAFETY : idx < dense.len() (sparse/ids/dense synchronisés) ; l'unicité du
+        // handle est garantie par l'appelant.
+        unsafe { &mut *self.dense.add(idx) }
+    }
+}
+
+impl<'a, T> IntoFetch<'a> for &'a mut SparseSet<T> {
+    type Fetch = FetchMut<'a, T>;
+    fn into_fetch(self) -> FetchMut<'a, T> {
+        let SparseSet { sparse, dense, ids, changed, tick, .. } = self;
+        *tick += 1;
+        FetchMut { sparse, ids, dense: dense.as_mut_ptr(), changed, tick: *tick, _marker: PhantomData }
+    }
+}
+
+macro_rules! impl_tuple_fetch {
+    ($($name:ident),+) => {
+        impl<'a, $($name: Fetch<'a>),+> Fetch<'a> for ($($name,)+) {
+            type Item = ($($name::Item,)+);
+
+            fn ids(&self) -> &'a [Entity] {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                let mut best: Option<&'a [Entity]> = None;
+                $(
+                    let ids = $name.ids();
+                    if best.is_none_or(|b| ids.len() < b.len()) { best = Some(ids); }
+                )+
+                best.unwrap_or(&[])
+            }
+
+            fn contains(&self, entity: Entity) -> bool {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $($name.contains(entity))&&+
+            }
+
+            unsafe fn fetch(&mut self, entity: Entity) -> Self::Item {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                // SAFETY : même contrat que l'appelant, chaque set est distinct.
+                ($(unsafe { $name.fetch(entity) },)+)
+            }
+        }
+
+        impl<'a, $($name: IntoFetch<'a>),+> IntoFetch<'a> for ($($name,)+) {
+            type Fetch = ($($name::Fetch,)+);
+
+            fn into_fetch(self) -> Self::Fetch {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.into_fetch(),)+)
+            }
+        }
+    };
+}
+
+impl_tuple_fetch!(A);
+impl_tuple_fetch!(A, B);
+impl_tuple_fetch!(A, B, C);
+impl_tuple_fetch!(A, B, C, D);
+impl_tuple_fetch!(A, B, C, D, E);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Components, EntityAllocator};
+
+    /// `big` : 10 entités avec leur rang ; `small` : les rangs 7 et 3 (dans cet ordre) ;
+    /// `tag` : les rangs pairs.
+    fn sets() -> (Vec<Entity>, SparseSet<usize>, SparseSet<&'static str>, SparseSet<()>) {
+        let mut entities = EntityAllocator::new();
+        let ids: Vec<Entity> = (0..10).map(|_| entities.create()).collect();
+        let (mut big, mut small, mut tag) = (SparseSet::new(), SparseSet::new(), SparseSet::new());
+        for (rank, &id) in ids.iter().enumerate() {
+            big.insert(id, rank);
+            if rank % 2 == 0 {
+                tag.insert(id, ());
+            }
+        }
+        small.insert(ids[7], "sept");
+        small.insert(ids[3], "trois");
+        (ids, big, small, tag)
+    }
+
+    #[test]
+    fn join_is_driven_by_the_smallest_set() {
+        let (ids, big, small, tag) = sets();
+        let query = Query::new((&big, &small)).into_iter();
+        assert_eq!(query.ids.len(), small.len(), "itère sur `small`, pas sur `big`");
+        let found: Vec<_> = query.map(|(id, (&rank, &name))| (id, rank, name)).collect();
+        assert_eq!(found, [(ids[7], 7, "sept"), (ids[3], 3, "trois")], "ordre du set directeur");
+
+        // Un filtre `with` plus petit que les sets joints prend la main.
+        let mut one = SparseSet::new();
+        one.insert(ids[4], ());
+        assert_eq!(Query::new((&big, &tag)).with(&one).into_iter().ids.len(), 1);
+    }
+
+    #[test]
+    fn with_and_without_filters() {
+        let (_, big, small, tag) = sets();
+        let ranks = |query: Query<'_, _>| -> Vec<usize> {
+            let mut ranks: Vec<usize> = query.into_iter().map(|(_, &rank)| rank).collect();
+            ranks.sort();
+            ranks
+        };
+        assert_eq!(ranks(Query::new(&big).with(&tag)), [0, 2, 4, 6, 8]);
+        assert_eq!(ranks(Query::new(&big).without(&tag)), [1, 3, 5, 7, 9]);
+        assert_eq!(ranks(Query::new(&big).without(&tag).with(&small)), [3, 7]);
+        assert_eq!(ranks(Query::new(&big).with(&tag).with(&small)), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn mutable_fetch_marks_only_visited_entries() {
+        let (ids, mut big, small, _) = sets();
+        let before = big.change_tick();
+        for _ in Query::new(&big).with(&small) {}
+        assert_eq!(big.change_tick(), before, "lecture seule : pas de tick");
+
+        for (_, (rank, _)) in Query::new((&mut big, &small)) {
+            *rank *= 10;
+        }
+        assert!(big.change_tick() > before);
+        let mut changed: Vec<Entity> = big.changed_since(before).collect();
+        changed.sort_by_key(|id| id.index());
+        assert_eq!(changed, [ids[3], ids[7]], "seules les entrées rendues en `&mut` changent");
+        assert_eq!((big.get(ids[7]), big.get(ids[8])), (Some(&70), Some(&8)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn same_storage_twice_panics() {
+        let mut components = Components::new();
+        components.register::<u32>();
+        let _ = components.storages_mut::<(u32, u32)>();
+    }
+}
@@ -0,0 +1,219 @@
+use std::any::{type_name, Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{Entity, SparseSet};
+
+/// Registre de composants type-erased : `TypeId` → `SparseSet<T>`.
+///
+/// Chaque type de composant est enregistré une fois (`register`) ; le registre
+/// se charge ensuite de `remove_entity` / `clear_scene` pour tous les types, sans
+/// liste à maintenir à la main. Un composant enregistré avec `register_serialized`
+/// est en plus sauvegardé / rechargé automatiquement dans `SceneEntityData::components`
+/// sous sa clé.
+///
+/// Accéder à un type non enregistré panique : c'est une erreur de programmation.
+/// De même pour un handle périmé dont le slot a été réutilisé (cf. `SparseSet`) ; les
+/// handles venus de l'extérieur se valident avec `EntityAllocator::is_alive`.
+#[derive(Default)]
+pub struct Components {
+    storages:    HashMap<TypeId, Box<dyn ErasedStorage>>,
+    serializers: Vec<Serializer>,
+}
+
+/// Opérations communes à tous les `SparseSet<T>`, quel que soit `T`.
+trait ErasedStorage {
+    fn remove(&mut self, entity: Entity) -> bool;
+    fn ids(&self) -> &[Entity];
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> ErasedStorage for SparseSet<T> {
+    fn remove(&mut self, entity: Entity) -> bool {
+        SparseSet::remove(self, entity)
+    }
+
+    fn ids(&self) -> &[Entity] {
+        &self.ids
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Sérialiseur JSON d'un type de composant (fonctions monomorphisées à l'enregistrement).
+struct Serializer {
+    key:     &'static str,
+    type_id: TypeId,
+    save:    fn(&dyn ErasedStorage, Entity) -> Option<serde_json::Value>,
+    load:    fn(&mut dyn ErasedStorage, Entity, serde_json::Value) -> Result<(), serde_json::Error>,
+}
+
+fn save_component<T: Serialize + 'static>(storage: &dyn ErasedStorage, entity: Entity) -> Option<serde_json::Value> {
+    let set = storage.as_any().downcast_ref::<SparseSet<T>>()?;
+    match serde_json::to_value(set.get(entity)?) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            crate::log::error(&format!("[Components] {} : {}", type_name::<T>(), e));
+            None
+        }
+    }
+}
+
+fn load_component<T: DeserializeOwned + 'static>(
+    storage: &mut dyn ErasedStorage,
+    entity:  Entity,
+    value:   serde_json::Value,
+) -> Result<(), serde_json::Error> {
+    let component: T = serde_json::from_value(value)?;
+    if let Some(set) = storage.as_any_mut().downcast_mut::<SparseSet<T>>() {
+        set.insert(entity, component);
+    }
+    Ok(())
+}
+
+impl Components {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enregistre le type `T` (sans effet s'il l'est déjà).
+    pub fn register<T: 'static>(&mut self) {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(SparseSet::<T>::new()));
+    }
+
+    /// Enregistre `T` et le sérialise automatiquement dans les scènes sous `key`.
+    pub fn register_serialized<T>(&mut self, key: &'static str)
+    where
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        self.register::<T>();
+        assert!(
+            self.serializers.iter().all(|s| s.key != key),
+            "component key '{key}' already registered",
+        );
+        self.serializers.push(Serializer {
+            key,
+            type_id: TypeId::of::<T>(),
+            save:    save_component::<T>,
+            load:    load_component::<T>,
+        });
+    }
+
+    /// Le `SparseSet` du type `T`.
+    pub fn storage<T: 'static>(&self) -> &SparseSet<T> {
+        self.storages
+            .get(&TypeId::of::<T>())
+            .and_then(|s| s.as_any().downcast_ref())
+            .unwrap_or_else(|| panic!("component {} not registered", type_name::<T>()))
+    }
+
+    pub fn storage_mut<T: 'static>(&mut self) -> &mut SparseSet<T> {
+        self.storages
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|s| s.as_any_mut().downcast_mut())
+            .unwrap_or_else(|| panic!("component {} not registered", type_name::<T>()))
+    }
+
+    /// Plusieurs `SparseSet` distincts en `&mut` simultanés, ex. `storages_mut::<(RigidBody, Transform)>()`.
+    /// Panique si un type apparaît deux fois.
+    pub fn storages_mut<S: StorageTuple>(&mut self) -> S::Refs<'_> {
+        S::fetch(self)
+    }
+
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<&T> {
+        self.storage::<T>().get(entity)
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, entity: Entity) -> Option<&mut T> {
+        self.storage_mut::<T>().get_mut(entity)
+    }
+
+    pub fn contains<T: 'static>(&self, entity: Entity) -> bool {
+        self.storage::<T>().contains(entity)
+    }
+
+    /// Insère ou remplace le composant (le type est déduit de `value`).
+    pub fn insert<T: 'static>(&mut self, entity: Entity, value: T) {
+        self.storage_mut::<T>().insert(entity, value);
+    }
+
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> bool {
+        self.storage_mut::<T>().remove(entity)
+    }
+
+    /// Retire tous les composants enregistrés de `entity`.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        for storage in self.storages.values_mut() {
+            storage.remove(entity);
+        }
+    }
+
+    /// Entités possédant au moins un composant (doublons possibles entre types).
+    pub fn ids(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.storages.values().flat_map(|s| s.ids().iter().copied())
+    }
+
+    /// Composants sérialisables de `entity`, indexés par clé.
+    pub fn save_entity(&self, entity: Entity) -> BTreeMap<String, serde_json::Value> {
+        self.serializers
+            .iter()
+            .filter_map(|s| {
+                let value = (s.save)(self.storages[&s.type_id].as_ref(), entity)?;
+                Some((s.key.to_string(), value))
+            })
+            .collect()
+    }
+
+    /// Recharge les composants sérialisés de `entity` ; les clés inconnues sont ignorées avec un warning.
+    pub fn load_entity(&mut self, entity: Entity, components: BTreeMap<String, serde_json::Value>) {
+        for (key, value) in components {
+            let Some(s) = self.serializers.iter().find(|s| s.key == key) else {
+                crate::log::warn(&format!("[load_scene] composant '{key}' non enregistré"));
+                continue;
+            };
+            let storage = self.storages.get_mut(&s.type_id).expect("serialized component without storage");
+            if let Err(e) = (s.load)(storage.as_mut(), entity, value) {
+                crate::log::warn(&format!("[load_scene] composant '{key}' invalide : {e}"));
+            }
+        }
+    }
+}
+
+/// Tuple de types de composants empruntables ensemble en `&mut` (cf. `Components::storages_mut`).
+pub trait StorageTuple {
+    type Refs<'a>;
+    fn fetch(components: &mut Components) -> Self::Refs<'_>;
+}
+
+macro_rules! impl_storage_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: 'static),+> StorageTuple for ($($name,)+) {
+            type Refs<'a> = ($(&'a mut SparseSet<$name>,)+);
+
+            #[allow(non_snake_case)]
+            fn fetch(components: &mut Components) -> Self::Refs<'_> {
+                let [$($name),+] = components.storages.get_disjoint_mut([$(&TypeId::of::<$name>()),+]);
+                ($(
+                    $name
+                        .and_then(|s| s.as_any_mut().downcast_mut::<SparseSet<$name>>())
+                        .unwrap_or_else(|| panic!("component {} not registered", type_name::<$name>())),
+                )+)
+            }
+        }
+    };
+}
+
+impl_storage_tuple!(A, B);
+impl_storage_tuple!(A, B, C);
+impl_storage_tuple!(A, B, C, D);
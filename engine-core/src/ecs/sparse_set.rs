@@ -1,15 +1,52 @@
+use std::collections::VecDeque;
+
+use super::Entity;
+
 /// Sentinel : indique l'absence d'un composant dans le vecteur sparse.
 const EMPTY: usize = usize::MAX;
 
+/// Nombre de suppressions conservées pour `removed_since`.
+const REMOVED_LOG_CAP: usize = 1024;
+
 /// Conteneur de composants basé sur un Sparse Set.
 ///
-/// - `sparse[entity_id]` → index dans `dense` (ou EMPTY si absent)
-/// - `dense`             → composants compactés (itération rapide)
-/// - `ids`               → entity_id correspondant à chaque slot dense
+/// - `sparse[entity.index]` → index dans `dense` (ou EMPTY si absent)
+/// - `dense`                → composants compactés (itération rapide)
+/// - `ids`                  → handle (index + génération) correspondant à chaque slot dense
+///
+/// Un handle dont la génération ne correspond pas à celle stockée dans `ids`
+/// désigne une entité morte dont le slot a été réutilisé : l'accès panique
+/// au lieu de renvoyer le composant d'une autre entité.
+///
+/// Détection de changements : chaque mutation (`insert`, `get_mut`, `remove`,
+/// requête `&mut`) incrémente `tick` et estampille l'entrée. Un consommateur
+/// mémorise `change_tick()` après son passage puis demande `changed_since(t)`
+/// au passage suivant. `get_mut` compte comme un changement même sans écriture.
 pub struct SparseSet<T> {
-    sparse: Vec<usize>,
-    dense:  Vec<T>,
-    ids:    Vec<usize>,
+    pub(super) sparse:  Vec<usize>,
+    pub(super) dense:   Vec<T>,
+    pub(super) ids:     Vec<Entity>,
+    pub(super) added:   Vec<u64>,   // tick d'insertion, parallèle à dense
+    pub(super) changed: Vec<u64>,   // tick de dernière mutation, parallèle à dense
+    pub(super) tick:    u64,
+    removed:       VecDeque<(Entity, u64)>,
+    removed_floor: u64,             // tick de la plus récente suppression oubliée
+}
+
+/// Index dense de `entity` dans les vues `sparse` / `ids` d'un SparseSet, None si absent.
+/// Panique si le slot appartient à une autre génération (handle périmé).
+pub(super) fn dense_index(sparse: &[usize], ids: &[Entity], entity: Entity) -> Option<usize> {
+    let i = entity.index();
+    if i >= sparse.len() || sparse[i] == EMPTY {
+        return None;
+    }
+    let idx   = sparse[i];
+    let owner = ids[idx];
+    assert!(
+        owner == entity,
+        "stale entity handle {entity:?}: slot {i} now belongs to {owner:?}",
+    );
+    Some(idx)
 }
 
 impl<T> Default for SparseSet<T> {
@@ -21,80 +58,262 @@ impl<T> Default for SparseSet<T> {
 impl<T> SparseSet<T> {
     pub fn new() -> Self {
         SparseSet {
-            sparse: Vec::new(),
-            dense:  Vec::new(),
-            ids:    Vec::new(),
+            sparse:  Vec::new(),
+            dense:   Vec::new(),
+            ids:     Vec::new(),
+            added:   Vec::new(),
+            changed: Vec::new(),
+            tick:    0,
+            removed:       VecDeque::new(),
+            removed_floor: 0,
         }
     }
 
-    /// Insère ou remplace le composant pour `id`.
-    pub fn insert(&mut self, id: usize, value: T) {
-        assert!(id != usize::MAX, "entity id usize::MAX is reserved as sentinel");
-        if id >= self.sparse.len() {
-            self.sparse.resize(id + 1, EMPTY);
+    fn dense_index(&self, entity: Entity) -> Option<usize> {
+        dense_index(&self.sparse, &self.ids, entity)
+    }
+
+    /// True si `entity` possède ce composant.
+    /// Panique si `entity` est un handle périmé dont le slot a été réutilisé (cf. `SparseSet`).
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.dense_index(entity).is_some()
+    }
+
+    /// Nombre de composants stockés.
+    pub fn len(&self) -> usize {
+        self.dense.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dense.is_empty()
+    }
+
+    /// Insère ou remplace le composant pour `entity`.
+    /// Panique si `entity` est un handle périmé dont le slot a été réutilisé (cf. `SparseSet`).
+    pub fn insert(&mut self, entity: Entity, value: T) {
+        let i = entity.index();
+        if i >= self.sparse.len() {
+            self.sparse.resize(i + 1, EMPTY);
         }
-        if self.sparse[id] != EMPTY {
-            let idx = self.sparse[id];
-            self.dense[idx] = value;
+        let tick = self.bump_tick();
+        if let Some(idx) = self.dense_index(entity) {
+            self.dense[idx]   = value;
+            self.changed[idx] = tick;
         } else {
             let idx = self.dense.len();
-            self.sparse[id] = idx;
+            self.sparse[i] = idx;
             self.dense.push(value);
-            self.ids.push(id);
+            self.ids.push(entity);
+            self.added.push(tick);
+            self.changed.push(tick);
         }
     }
 
     /// Retourne une référence immutable, ou None si absent.
-    pub fn get(&self, id: usize) -> Option<&T> {
-        assert!(id != usize::MAX, "entity id usize::MAX is reserved as sentinel");
-        if id >= self.sparse.len() || self.sparse[id] == EMPTY {
-            return None;
-        }
-        Some(&self.dense[self.sparse[id]])
+    /// Panique si `entity` est un handle périmé dont le slot a été réutilisé (cf. `SparseSet`).
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        let idx = self.dense_index(entity)?;
+        Some(&self.dense[idx])
     }
 
-    /// Retourne une référence mutable, ou None si absent.
-    pub fn get_mut(&mut self, id: usize) -> Option<&mut T> {
-        assert!(id != usize::MAX, "entity id usize::MAX is reserved as sentinel");
-        if id >= self.sparse.len() || self.sparse[id] == EMPTY {
-            return None;
-        }
-        let idx = self.sparse[id];
+    /// Retourne une référence mutable, ou None si absent. Marque l'entrée comme changée.
+    /// Panique si `entity` est un handle périmé dont le slot a été réutilisé (cf. `SparseSet`).
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        let idx = self.dense_index(entity)?;
+        self.changed[idx] = self.bump_tick();
         Some(&mut self.dense[idx])
     }
 
-    /// Itère sur tous les composants : (entity_id, &T).
-    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+    /// Itère sur tous les composants : (entity, &T).
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
         debug_assert_eq!(self.ids.len(), self.dense.len(), "ids/dense désynchronisés");
         self.ids.iter().copied().zip(self.dense.iter())
     }
 
-    /// Itère sur tous les IDs d'entités présents dans ce SparseSet.
-    pub fn iter_ids(&self) -> impl Iterator<Item = usize> + '_ {
+    /// Itère sur tous les handles d'entités présents dans ce SparseSet.
+    pub fn iter_ids(&self) -> impl Iterator<Item = Entity> + '_ {
         self.ids.iter().copied()
     }
 
-    /// Supprime le composant pour `id`. Retourne true si existait.
+    /// Supprime le composant pour `entity`. Retourne true si existait.
     /// Utilise swap-remove : O(1), réordonne les éléments dense.
-    pub fn remove(&mut self, id: usize) -> bool {
-        if id >= self.sparse.len() || self.sparse[id] == EMPTY {
-            return false;
-        }
-        let idx      = self.sparse[id];
-        let last_idx = self.dense.len() - 1;
+    /// Panique si `entity` est un handle périmé dont le slot a été réutilisé (cf. `SparseSet`).
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(idx) = self.dense_index(entity) else { return false };
 
-        // Swap-remove dans dense + ids
+        // Swap-remove dans dense + ids + ticks
         self.dense.swap_remove(idx);
         self.ids.swap_remove(idx);
+        self.added.swap_remove(idx);
+        self.changed.swap_remove(idx);
 
         // L'élément qui était au dernier slot est maintenant à idx
         // → mettre à jour son entrée sparse (sauf si on a supprimé le dernier)
-        if idx <= last_idx && idx < self.ids.len() {
-            let moved_id = self.ids[idx];
-            self.sparse[moved_id] = idx;
+        if idx < self.ids.len() {
+            let moved = self.ids[idx];
+            self.sparse[moved.index()] = idx;
         }
 
-        self.sparse[id] = EMPTY;
+        self.sparse[entity.index()] = EMPTY;
+
+        let tick = self.bump_tick();
+        self.removed.push_back((entity, tick));
+        if self.removed.len() > REMOVED_LOG_CAP
+            && let Some((_, oldest)) = self.removed.pop_front()
+        {
+            self.removed_floor = oldest;
+        }
         true
     }
+
+    fn bump_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Tick de la dernière mutation. À mémoriser pour le prochain `*_since`.
+    pub fn change_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Entités dont le composant a été inséré après `tick`.
+    pub fn added_since(&self, tick: u64) -> impl Iterator<Item = Entity> + '_ {
+        self.ids.iter().zip(&self.added)
+            .filter(move |&(_, &t)| t > tick)
+            .map(|(&id, _)| id)
+    }
+
+    /// Entités dont le composant a été inséré, remplacé ou muté après `tick`.
+    pub fn changed_since(&self, tick: u64) -> impl Iterator<Item = Entity> + '_ {
+        self.ids.iter().zip(&self.changed)
+            .filter(move |&(_, &t)| t > tick)
+            .map(|(&id, _)| id)
+    }
+
+    /// Entités dont le composant a été retiré après `tick`.
+    /// None si l'historique (borné) ne remonte plus jusque-là : le consommateur doit tout resynchroniser.
+    pub fn removed_since(&self, tick: u64) -> Option<impl Iterator<Item = Entity> + '_> {
+        if tick < self.removed_floor {
+            return None;
+        }
+        Some(self.removed.iter()
+            .filter(move |&&(_, t)| t > tick)
+            .map(|&(id, _)| id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::EntityAllocator;
+
+    #[test]
+    fn insert_get_remove() {
+        let mut entities = EntityAllocator::new();
+        let (a, b) = (entities.create(), entities.create());
+        let mut set = SparseSet::new();
+        set.insert(a, 1);
+        set.insert(b, 2);
+        set.insert(a, 10);
+        assert_eq!((set.get(a), set.get(b), set.len()), (Some(&10), Some(&2), 2));
+
+        // swap-remove : `b` change de slot dense mais reste accessible.
+        assert!(set.remove(a));
+        assert!(!set.remove(a));
+        assert_eq!((set.get(a), set.get(b), set.len()), (None, Some(&2), 1));
+    }
+
+    fn sorted(ids: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        let mut ids: Vec<Entity> = ids.collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn change_ticks_across_insert_mutate_remove() {
+        let mut entities = EntityAllocator::new();
+        let (a, b, c) = (entities.create(), entities.create(), entities.create());
+        let mut set = SparseSet::new();
+        set.insert(a, 0);
+        set.insert(b, 0);
+        let t0 = set.change_tick();
+        assert_eq!(sorted(set.added_since(0)), [a, b]);
+        assert_eq!(set.added_since(t0).count(), 0);
+
+        // Mutation, remplacement, ajout, suppression : chacun estampillé après t0.
+        *set.get_mut(a).unwrap() += 1;
+        set.insert(c, 0);
+        let t1 = set.change_tick();
+        set.insert(b, 5);
+        set.remove(c);
+        assert_eq!(sorted(set.added_since(t0)), Vec::<Entity>::new(), "c ajouté puis retiré");
+        assert_eq!(sorted(set.changed_since(t0)), [a, b]);
+        assert_eq!(sorted(set.changed_since(t1)), [b]);
+        assert_eq!(sorted(set.removed_since(t0).unwrap()), [c]);
+
+        // Rien depuis le dernier tick.
+        let t2 = set.change_tick();
+        assert_eq!(set.changed_since(t2).count(), 0);
+        assert_eq!(set.removed_since(t2).unwrap().count(), 0);
+        // Une lecture ne compte pas comme un changement.
+        set.get(a);
+        assert_eq!(set.change_tick(), t2);
+    }
+
+    #[test]
+    fn removed_log_overflow_requests_resync() {
+        let mut entities = EntityAllocator::new();
+        let mut set = SparseSet::new();
+        let ids: Vec<Entity> = (0..REMOVED_LOG_CAP + 10).map(|_| entities.create()).collect();
+        for &id in &ids {
+            set.insert(id, ());
+        }
+        let before = set.change_tick();
+        for &id in &ids {
+            set.remove(id);
+        }
+        // Les 10 premières suppressions sont oubliées : l'historique ne couvre plus `before`.
+        assert!(set.removed_since(before).is_none());
+        // Un tick récent reste couvert.
+        let recent = set.change_tick() - 5;
+        assert_eq!(set.removed_since(recent).unwrap().count(), 5);
+        assert_eq!(set.removed.len(), REMOVED_LOG_CAP);
+    }
+
+    #[test]
+    fn stale_handle_without_component_is_absent() {
+        let mut entities = EntityAllocator::new();
+        let old = entities.create();
+        let mut set = SparseSet::new();
+        set.insert(old, "old");
+        set.remove(old);
+        entities.kill(old);
+        let new = entities.create();
+        assert_eq!(new.index(), old.index());
+        assert!(!set.contains(old) && !set.contains(new));
+    }
+
+    #[test]
+    #[should_panic(expected = "stale entity handle")]
+    fn stale_handle_panics_instead_of_aliasing() {
+        let mut entities = EntityAllocator::new();
+        let old = entities.create();
+        entities.kill(old);
+        let new = entities.create();
+        let mut set = SparseSet::new();
+        set.insert(new, "new");
+        set.get(old);
+    }
+
+    #[test]
+    #[should_panic(expected = "stale entity handle")]
+    fn stale_insert_panics() {
+        let mut entities = EntityAllocator::new();
+        let old = entities.create();
+        entities.kill(old);
+        let new = entities.create();
+        let mut set = SparseSet::new();
+        set.insert(new, 1);
+        set.insert(old, 2);
+    }
 }
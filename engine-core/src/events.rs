@@ -0,0 +1,190 @@
+//! Événements de collision et de trigger.
+//!
+//! Pendant un pas fixe, la résolution (`resolve_static` / `resolve_dynamic`) et
+//! `detect_triggers` enregistrent chaque paire en contact ; en fin de pas,
+//! `ContactTracker::end_step` compare avec le pas précédent et émet Enter / Stay / Exit.
+//! JS (ou un système Rust) vide la file avec `WorldCore::drain_collision_events`.
+//! Les contacts d'un îlot endormi, qui n'est plus testé, sont reconduits tels quels
+//! jusqu'à son réveil.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+use crate::ecs::Entity;
+
+/// Taille max de la file : au-delà, les événements les plus anciens sont perdus
+/// (file jamais vidée côté JS).
+const MAX_QUEUED_EVENTS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionPhase {
+    Enter = 0,
+    Stay  = 1,
+    Exit  = 2,
+}
+
+/// Paire en contact. `a < b` (ordre des handles) ; un Exit peut désigner une entité
+/// supprimée entre-temps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionEvent {
+    pub a:       Entity,
+    pub b:       Entity,
+    pub phase:   CollisionPhase,
+    /// Au moins un des deux colliders est un trigger (pas de résolution).
+    pub trigger: bool,
+}
+
+/// Paires en contact au pas courant / précédent (valeur : trigger ?) + file d'événements.
+#[derive(Default)]
+pub(crate) struct ContactTracker {
+    current:  BTreeMap<(Entity, Entity), bool>,
+    previous: BTreeMap<(Entity, Entity), bool>,
+    resting:  BTreeSet<(Entity, Entity)>,   // contacts solides figés par l'endormissement
+    queue:    VecDeque<CollisionEvent>,
+}
+
+impl ContactTracker {
+    /// Note un contact entre `a` et `b` pendant le pas courant (ordre indifférent).
+    pub fn record(&mut self, a: Entity, b: Entity, trigger: bool) {
+        let key = if a < b { (a, b) } else { (b, a) };
+        *self.current.entry(key).or_default() |= trigger;
+    }
+
+    /// Paires solides (hors triggers) notées pendant le pas courant.
+    pub fn touching(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.current.iter().filter(|(_, trigger)| !**trigger).map(|(&pair, _)| pair)
+    }
+
+    /// Fige les contacts solides courants des corps `ids`, qui s'endorment.
+    pub fn sleep(&mut self, ids: &HashSet<Entity>) {
+        for (&(a, b), &trigger) in &self.current {
+            if !trigger && (ids.contains(&a) || ids.contains(&b)) {
+                self.resting.insert((a, b));
+            }
+        }
+    }
+
+    /// Libère les contacts figés de `id` (qui se réveille) : ils comptent encore pour le pas
+    /// courant, la détection reprend ensuite. Retourne les entités de l'autre côté.
+    pub fn wake(&mut self, id: Entity) -> Vec<Entity> {
+        let mut partners = Vec::new();
+        self.resting.retain(|&(a, b)| {
+            if a != id && b != id {
+                return true;
+            }
+            partners.push(if a == id { b } else { a });
+            self.current.entry((a, b)).or_insert(false);
+            false
+        });
+        partners
+    }
+
+    /// Oublie les contacts de `id` (entité supprimée) : Exit au prochain `end_step`.
+    pub fn forget(&mut self, id: Entity) {
+        self.resting.retain(|&(a, b)| a != id && b != id);
+        self.current.retain(|&(a, b), _| a != id && b != id);
+    }
+
+    /// Fin de pas : émet Enter / Stay pour les paires courantes, Exit pour celles disparues.
+    pub fn end_step(&mut self) {
+        for &pair in &self.resting {
+            self.current.entry(pair).or_insert(false);
+        }
+        let current = std::mem::take(&mut self.current);
+        let queue   = &mut self.queue;
+
+        for (&(a, b), &trigger) in &current {
+            let phase = if self.previous.contains_key(&(a, b)) { CollisionPhase::Stay } else { CollisionPhase::Enter };
+            push_bounded(queue, CollisionEvent { a, b, phase, trigger });
+        }
+        for (&(a, b), &trigger) in &self.previous {
+            if !current.contains_key(&(a, b)) {
+                push_bounded(queue, CollisionEvent { a, b, phase: CollisionPhase::Exit, trigger });
+            }
+        }
+        self.previous = current;
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = CollisionEvent> + '_ {
+        self.queue.drain(..)
+    }
+
+    /// Oublie contacts et événements (changement de scène).
+    pub fn clear(&mut self) {
+        self.current.clear();
+        self.previous.clear();
+        self.resting.clear();
+        self.queue.clear();
+    }
+}
+
+fn push_bounded(queue: &mut VecDeque<CollisionEvent>, event: CollisionEvent) {
+    if queue.len() == MAX_QUEUED_EVENTS {
+        queue.pop_front();
+    }
+    queue.push_back(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair() -> (Entity, Entity) {
+        (Entity::from_bits(1), Entity::from_bits(2))
+    }
+
+    fn phases(tracker: &mut ContactTracker) -> Vec<CollisionPhase> {
+        tracker.drain().map(|e| e.phase).collect()
+    }
+
+    #[test]
+    fn enter_stay_exit() {
+        let (a, b) = pair();
+        let mut tracker = ContactTracker::default();
+        tracker.record(b, a, false);
+        tracker.end_step();
+        tracker.record(a, b, false);
+        tracker.end_step();
+        tracker.end_step();
+        assert_eq!(phases(&mut tracker), [CollisionPhase::Enter, CollisionPhase::Stay, CollisionPhase::Exit]);
+    }
+
+    #[test]
+    fn resting_pairs_stay_until_woken() {
+        let (a, b) = pair();
+        let mut tracker = ContactTracker::default();
+        tracker.record(a, b, false);
+        tracker.sleep(&HashSet::from([a]));
+        tracker.end_step();
+        tracker.end_step();  // plus de narrow-phase : le contact figé est reconduit
+        assert_eq!(phases(&mut tracker), [CollisionPhase::Enter, CollisionPhase::Stay]);
+
+        assert_eq!(tracker.wake(b), vec![a]);
+        assert!(tracker.resting.is_empty());
+        tracker.end_step();  // compte encore pour le pas du réveil
+        tracker.end_step();
+        assert_eq!(phases(&mut tracker), [CollisionPhase::Stay, CollisionPhase::Exit]);
+    }
+
+    #[test]
+    fn triggers_are_not_frozen() {
+        let (a, b) = pair();
+        let mut tracker = ContactTracker::default();
+        tracker.record(a, b, true);
+        tracker.sleep(&HashSet::from([a, b]));
+        assert!(tracker.resting.is_empty());
+    }
+
+    #[test]
+    fn forget_drops_resting_and_current_pairs() {
+        let (a, b) = pair();
+        let mut tracker = ContactTracker::default();
+        tracker.record(a, b, false);
+        tracker.sleep(&HashSet::from([a, b]));
+        tracker.end_step();
+        tracker.wake(a);
+        tracker.forget(a);
+        assert!(tracker.resting.is_empty() && tracker.current.is_empty());
+        tracker.end_step();
+        assert_eq!(phases(&mut tracker), [CollisionPhase::Enter, CollisionPhase::Exit]);
+    }
+}
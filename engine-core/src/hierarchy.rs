@@ -0,0 +1,224 @@
+//! Cache des matrices monde (`GlobalTransform`).
+//!
+//! Les setters (`set_position`, `set_rotation`, `set_scale`, `set_parent`…) et la
+//! physique passent par `SparseSet::get_mut` / `insert` / `remove` : les ticks de
+//! changement de `Transform`, `Parent` et `PreviousTransform` servent de dirty flags.
+//! `propagate_transforms` ne recalcule que les entités sales et leurs descendants,
+//! parents avant enfants, chaque matrice en un seul produit `parent * local`.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::Mat4;
+
+use crate::ecs::{Entity, GlobalTransform, Parent, PreviousTransform, SparseSet, Transform};
+use crate::world_core::WorldCore;
+
+/// Ticks observés lors de la dernière propagation.
+#[derive(Default)]
+pub(crate) struct TransformSync {
+    transform: u64,
+    parent:    u64,
+    previous:  u64,
+    alpha:     f32,
+    primed:    bool,
+}
+
+/// Storages lus pendant la propagation + mémo des entités déjà traitées.
+struct Propagation<'a> {
+    transforms: &'a SparseSet<Transform>,
+    parents:    &'a SparseSet<Parent>,
+    previous:   &'a SparseSet<PreviousTransform>,
+    globals:    &'a mut SparseSet<GlobalTransform>,
+    dirty:      HashSet<Entity>,
+    done:       HashSet<Entity>,
+    alpha:      f32,
+}
+
+impl Propagation<'_> {
+    /// (monde, rendu) du parent de `id`, en le recalculant d'abord s'il est sale.
+    fn parent_matrices(&mut self, id: Entity) -> (Mat4, Mat4) {
+        let Some(parent) = self.parents.get(id).map(|p| p.parent_id) else {
+            return (Mat4::IDENTITY, Mat4::IDENTITY);
+        };
+        if self.dirty.contains(&parent) {
+            self.visit(parent);
+        }
+        match self.globals.get(parent) {
+            Some(g) => (g.world, g.render),
+            // Parent sans Transform : identité, mais on continue de remonter la chaîne.
+            None => self.parent_matrices(parent),
+        }
+    }
+
+    fn visit(&mut self, id: Entity) {
+        if !self.done.insert(id) {
+            return;
+        }
+        let (parent_world, parent_render) = self.parent_matrices(id);
+        let Some(t) = self.transforms.get(id) else {
+            self.globals.remove(id);
+            return;
+        };
+        let local  = t.matrix();
+        let render = match self.previous.get(id) {
+            Some(prev) => prev.0.lerp_matrix(t, self.alpha),
+            None       => local,
+        };
+        self.globals.insert(id, GlobalTransform {
+            world:  parent_world * local,
+            render: parent_render * render,
+        });
+    }
+}
+
+impl WorldCore {
+    /// Recalcule les `GlobalTransform` des entités sales et de leurs descendants.
+    /// No-op si aucun Transform / Parent n'a changé depuis le dernier appel.
+    /// Enregistré en PostUpdate et en Render (cf. systems.rs).
+    pub fn propagate_transforms(&mut self) {
+        let alpha = self.fixed_time.alpha();
+        let (globals, transforms, parents, previous) = self.components
+            .storages_mut::<(GlobalTransform, Transform, Parent, PreviousTransform)>();
+        let (transforms, parents, previous) = (&*transforms, &*parents, &*previous);
+        let entities = &self.entities;
+        let sync = &mut self.transform_sync;
+
+        let removed = (
+            transforms.removed_since(sync.transform),
+            parents.removed_since(sync.parent),
+            previous.removed_since(sync.previous),
+        );
+        let dirty: HashSet<Entity> = match removed {
+            (Some(tr_removed), Some(parent_removed), Some(prev_removed)) if sync.primed => {
+                let alpha_changed = alpha != sync.alpha;
+                transforms.changed_since(sync.transform)
+                    .chain(tr_removed)
+                    .chain(parents.changed_since(sync.parent))
+                    .chain(parent_removed)
+                    .chain(previous.changed_since(sync.previous))
+                    .chain(prev_removed)
+                    .chain(previous.iter_ids().filter(|_| alpha_changed))
+                    // Les handles supprimés restent dans l'historique : slot peut-être réutilisé.
+                    .filter(|&id| entities.is_alive(id))
+                    .collect()
+            }
+            // Historique de suppressions dépassé ou premier passage : tout recalculer.
+            _ => transforms.iter_ids().chain(globals.iter_ids()).collect(),
+        };
+
+        sync.transform = transforms.change_tick();
+        sync.parent    = parents.change_tick();
+        sync.previous  = previous.change_tick();
+        sync.alpha     = alpha;
+        sync.primed    = true;
+        if dirty.is_empty() {
+            return;
+        }
+
+        // Étendre aux descendants.
+        let mut children: HashMap<Entity, Vec<Entity>> = HashMap::new();
+        for (id, p) in parents.iter() {
+            children.entry(p.parent_id).or_default().push(id);
+        }
+        let mut stack: Vec<Entity> = dirty.iter().copied().collect();
+        let mut dirty = dirty;
+        while let Some(id) = stack.pop() {
+            for &child in children.get(&id).into_iter().flatten() {
+                if dirty.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+
+        let order: Vec<Entity> = dirty.iter().copied().collect();
+        let mut propagation = Propagation {
+            transforms, parents, previous, globals,
+            dirty,
+            done: HashSet::new(),
+            alpha,
+        };
+        for id in order {
+            propagation.visit(id);
+        }
+    }
+
+    /// True si le cache reflète l'état courant des Transform / Parent.
+    fn transforms_synced(&self) -> bool {
+        let sync = &self.transform_sync;
+        sync.primed
+            && self.components.storage::<Transform>().change_tick() == sync.transform
+            && self.components.storage::<Parent>().change_tick() == sync.parent
+    }
+
+    /// Matrice monde de l'entité : depuis le cache s'il est à jour, sinon recalculée.
+    pub fn world_matrix(&self, id: Entity) -> Mat4 {
+        match self.components.get::<GlobalTransform>(id) {
+            Some(g) if self.transforms_synced() => g.world,
+            _ => self.compute_world_matrix(id),
+        }
+    }
+
+    /// Matrice monde interpolée pour le rendu : depuis le cache s'il est à jour, sinon recalculée.
+    pub fn render_matrix(&self, id: Entity) -> Mat4 {
+        let sync = &self.transform_sync;
+        let fresh = self.transforms_synced()
+            && self.components.storage::<PreviousTransform>().change_tick() == sync.previous
+            && self.fixed_time.alpha() == sync.alpha;
+        match self.components.get::<GlobalTransform>(id) {
+            Some(g) if fresh => g.render,
+            _ => self.compute_render_matrix(id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec3;
+
+    use super::*;
+
+    fn global_position(world: &WorldCore, id: Entity) -> Vec3 {
+        world.components.get::<GlobalTransform>(id).expect("GlobalTransform").world.w_axis.truncate()
+    }
+
+    /// Racine → enfant → petit-enfant, décalés d'1 m en x, plus une entité sans lien.
+    fn chain(world: &mut WorldCore) -> [Entity; 4] {
+        let ids: [Entity; 4] = std::array::from_fn(|_| world.create_entity());
+        for (i, &id) in ids.iter().enumerate() {
+            world.add_transform(id, i as f32, 0.0, 0.0);
+        }
+        world.set_parent(ids[1], ids[0]);
+        world.set_parent(ids[2], ids[1]);
+        world.propagate_transforms();
+        ids
+    }
+
+    #[test]
+    fn moving_a_parent_updates_grandchildren() {
+        let mut world = WorldCore::new();
+        let [root, child, grandchild, _] = chain(&mut world);
+        assert_eq!(global_position(&world, grandchild), Vec3::new(2.0, 0.0, 0.0));
+
+        world.set_position(root, 0.0, 5.0, 0.0);
+        world.propagate_transforms();
+        assert_eq!(global_position(&world, child), Vec3::new(1.0, 5.0, 0.0));
+        assert_eq!(global_position(&world, grandchild), Vec3::new(2.0, 5.0, 0.0));
+        assert_eq!(world.world_matrix(grandchild), world.compute_world_matrix(grandchild));
+    }
+
+    #[test]
+    fn only_dirty_subtrees_are_recomputed() {
+        let mut world = WorldCore::new();
+        let [_, child, grandchild, _] = chain(&mut world);
+
+        let tick = world.components.storage::<GlobalTransform>().change_tick();
+        world.propagate_transforms();
+        assert_eq!(world.components.storage::<GlobalTransform>().change_tick(), tick, "rien de sale : no-op");
+
+        world.set_position(child, 1.0, 1.0, 0.0);
+        world.propagate_transforms();
+        let mut recomputed: Vec<Entity> = world.components.storage::<GlobalTransform>().changed_since(tick).collect();
+        recomputed.sort();
+        assert_eq!(recomputed, [child, grandchild], "ni la racine ni l'entité isolée");
+    }
+}
@@ -0,0 +1,339 @@
+//! Liaisons entre corps : tige (distance), rotule, charnière avec butées et soudure.
+//!
+//! `solve_joints` (FixedUpdate, après la résolution des contacts) projette les positions sur
+//! chaque liaison puis retire la vitesse relative qui la violerait, en plusieurs passes pour
+//! que les chaînes convergent. La correction est répartie selon les masses inverses ;
+//! statiques, kinematics et points du monde ont une masse infinie.
+//!
+//! Hors tige, la rotation de l'entité qui porte le joint est déduite de la position de son
+//! centre autour du pivot (une porte tourne sur ses gonds, une lampe poussée s'incline) et
+//! remplace sa vitesse angulaire. Deux corps liés ne se heurtent pas et s'endorment ensemble ;
+//! un corps endormi n'est recalé que si sa liaison s'écarte franchement (cible déplacée).
+
+use std::collections::HashSet;
+
+use glam::{Mat4, Quat, Vec3};
+
+use crate::ecs::{Entity, Joint, JointKind, RigidBody, Transform};
+use crate::world_core::WorldCore;
+
+/// Passes de projection par pas fixe.
+const JOINT_ITERATIONS: u32 = 8;
+/// Longueur sous laquelle un décalage (centre / pivot, ancres) est considéré nul.
+const JOINT_EPSILON: f32 = 1e-5;
+/// Écart (m) au-delà duquel une liaison réveille ses corps endormis ; en deçà, ils restent en place.
+const JOINT_WAKE_OFFSET: f32 = 1e-3;
+
+/// Pose monde d'un corps lié ; le monde lui-même pour une liaison sans cible.
+struct Pose {
+    matrix:   Mat4,
+    position: Vec3,
+    rotation: Quat,
+    scale:    Vec3,
+    velocity: Vec3,
+    inv_mass: f32,
+}
+
+impl Pose {
+    const WORLD: Pose = Pose {
+        matrix:   Mat4::IDENTITY,
+        position: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale:    Vec3::ONE,
+        velocity: Vec3::ZERO,
+        inv_mass: 0.0,
+    };
+}
+
+/// Vitesse relative (entité - cible) permise par une liaison.
+enum Freedom {
+    /// Aucune : les deux corps se déplacent ensemble.
+    Locked,
+    /// Toute vitesse perpendiculaire à `dir` (tige, rotule).
+    Across(Vec3),
+    /// Seulement le long de `dir` (charnière) ; `stop` = ±1 en butée, côté bloqué.
+    Along { dir: Vec3, stop: f32 },
+}
+
+/// Ce que la liaison impose à l'entité, la cible supposée immobile.
+struct Correction {
+    offset:   Vec3,           // déplacement qui satisfait la liaison
+    rotation: Option<Quat>,   // rotation déduite de l'entité
+    freedom:  Freedom,
+}
+
+/// Projection des liaisons, enregistrée après `resolve_dynamic`.
+pub(crate) fn solve_joints(world: &mut WorldCore, _dt: f32) {
+    let joints: Vec<(Entity, Joint)> = world.components.storage::<Joint>().iter().map(|(id, j)| (id, *j)).collect();
+    for _ in 0..JOINT_ITERATIONS {
+        for (id, joint) in &joints {
+            solve(world, *id, joint);
+        }
+    }
+}
+
+/// Paires (entité, cible) liées par un joint, dans les deux sens : leurs contacts sont ignorés.
+pub(crate) fn linked_pairs(world: &WorldCore) -> HashSet<(Entity, Entity)> {
+    world.components.storage::<Joint>().iter()
+        .filter_map(|(id, j)| j.target.map(|t| (id, t)))
+        .flat_map(|(a, b)| [(a, b), (b, a)])
+        .collect()
+}
+
+fn pose(world: &WorldCore, id: Option<Entity>) -> Option<Pose> {
+    let Some(id) = id else { return Some(Pose::WORLD) };
+    if !world.entities.is_alive(id) || !world.components.storage::<Transform>().contains(id) {
+        return None;
+    }
+    let matrix = world.compute_world_matrix(id);
+    let (scale, rotation, position) = matrix.to_scale_rotation_translation();
+    let rb = world.components.get::<RigidBody>(id);
+    Some(Pose {
+        matrix,
+        position,
+        rotation,
+        scale,
+        velocity: rb.map_or(Vec3::ZERO, |rb| rb.velocity),
+        inv_mass: rb.map_or(0.0, RigidBody::inverse_mass),
+    })
+}
+
+/// Une passe sur la liaison de `id` : positions, rotation déduite puis vitesses.
+fn solve(world: &mut WorldCore, id: Entity, joint: &Joint) {
+    let (Some(body), Some(target)) = (pose(world, Some(id)), pose(world, joint.target)) else { return };
+    let inv_sum = body.inv_mass + target.inv_mass;
+    if inv_sum <= 0.0 {
+        return;
+    }
+    let correction = correction(joint, &body, &target);
+    if world.is_sleeping(id) || joint.target.is_some_and(|t| world.is_sleeping(t)) {
+        if correction.offset.length() < JOINT_WAKE_OFFSET {
+            return;
+        }
+        world.wake_up(id);
+        if let Some(target_id) = joint.target {
+            world.wake_up(target_id);
+        }
+    }
+    let (body_share, target_share) = (body.inv_mass / inv_sum, target.inv_mass / inv_sum);
+
+    let relative = body.velocity - target.velocity;
+    let blocked = match correction.freedom {
+        Freedom::Locked       => relative,
+        Freedom::Across(dir)  => dir * relative.dot(dir),
+        Freedom::Along { dir, stop } => {
+            let along = relative.dot(dir);
+            if along * stop > 0.0 { relative } else { relative - dir * along }
+        }
+    };
+
+    // Corps mobiles : dynamiques, donc racines de la hiérarchie (local = monde).
+    if body.inv_mass > 0.0 {
+        if let Some(tr) = world.components.get_mut::<Transform>(id) {
+            tr.position += correction.offset * body_share;
+            if let Some(rotation) = correction.rotation {
+                tr.rotation = rotation;
+            }
+        }
+        if let Some(rb) = world.components.get_mut::<RigidBody>(id) {
+            rb.velocity -= blocked * body_share;
+            if correction.rotation.is_some() {
+                rb.angular_velocity = Vec3::ZERO;
+            }
+        }
+    }
+    if target.inv_mass > 0.0
+        && let Some(target_id) = joint.target
+    {
+        if let Some(tr) = world.components.get_mut::<Transform>(target_id) {
+            tr.position -= correction.offset * target_share;
+        }
+        if let Some(rb) = world.components.get_mut::<RigidBody>(target_id) {
+            rb.velocity += blocked * target_share;
+        }
+    }
+}
+
+fn correction(joint: &Joint, body: &Pose, target: &Pose) -> Correction {
+    match joint.kind {
+        JointKind::Distance { length } => {
+            let from = target.matrix.transform_point3(joint.target_anchor);
+            let span = body.matrix.transform_point3(joint.anchor) - from;
+            match span.try_normalize() {
+                Some(dir) => Correction { offset: dir * (length - span.length()), rotation: None, freedom: Freedom::Across(dir) },
+                None      => Correction { offset: Vec3::ZERO, rotation: None, freedom: Freedom::Locked },
+            }
+        }
+        JointKind::Fixed => {
+            let frame = PivotFrame::new(joint, body, target);
+            Correction { offset: frame.settle(Quat::IDENTITY), rotation: Some(frame.rest), freedom: Freedom::Locked }
+        }
+        JointKind::BallSocket => {
+            let frame  = PivotFrame::new(joint, body, target);
+            let radius = frame.arm.length();
+            if radius < JOINT_EPSILON {
+                return Correction { offset: frame.settle(Quat::IDENTITY), rotation: None, freedom: Freedom::Locked };
+            }
+            let dir   = frame.offset.try_normalize().unwrap_or(frame.arm / radius);
+            let swing = Quat::from_rotation_arc(frame.arm / radius, dir);
+            Correction { offset: frame.settle(swing), rotation: Some(swing * frame.rest), freedom: Freedom::Across(dir) }
+        }
+        JointKind::Hinge { axis, limits } => {
+            let frame = PivotFrame::new(joint, body, target);
+            let axis  = (target.rotation * axis).normalize();
+            let flat  = |v: Vec3| v - axis * v.dot(axis);
+            let (arm, offset) = (flat(frame.arm), flat(frame.offset));
+            // Angle autour de l'axe : d'après la position du centre, ou d'après la rotation
+            // de l'entité si son centre est sur l'axe.
+            let mut angle = if arm.length() > JOINT_EPSILON && offset.length() > JOINT_EPSILON {
+                axis.dot(arm.cross(offset)).atan2(arm.dot(offset))
+            } else {
+                twist_angle(body.rotation * frame.rest.inverse(), axis)
+            };
+            let mut stop = 0.0;
+            if let Some((min, max)) = limits {
+                let (min, max) = (min.to_radians(), max.to_radians().max(min.to_radians()));
+                let clamped = angle.clamp(min, max);
+                if clamped != angle {
+                    stop  = (angle - clamped).signum();
+                    angle = clamped;
+                }
+            }
+            let swing   = Quat::from_axis_angle(axis, angle);
+            let freedom = match axis.cross(swing * frame.arm).try_normalize() {
+                Some(dir) => Freedom::Along { dir, stop },
+                None      => Freedom::Locked,
+            };
+            Correction { offset: frame.settle(swing), rotation: Some(swing * frame.rest), freedom }
+        }
+    }
+}
+
+/// Rotule, charnière, soudure : l'ancre de l'entité rejoint le pivot.
+struct PivotFrame {
+    pivot:  Vec3,   // ancre de la cible, monde
+    rest:   Quat,   // rotation de l'entité à l'angle 0
+    arm:    Vec3,   // centre de l'entité vu du pivot, dans la pose `rest`
+    offset: Vec3,   // centre de l'entité vu du pivot, actuellement
+    center: Vec3,
+}
+
+impl PivotFrame {
+    fn new(joint: &Joint, body: &Pose, target: &Pose) -> Self {
+        let pivot = target.matrix.transform_point3(joint.target_anchor);
+        let rest  = target.rotation * joint.rest_rotation;
+        PivotFrame {
+            pivot,
+            rest,
+            arm:    rest * -(body.scale * joint.anchor),
+            offset: body.position - pivot,
+            center: body.position,
+        }
+    }
+
+    /// Déplacement qui amène le centre sur le bras tourné de `swing`.
+    fn settle(&self, swing: Quat) -> Vec3 {
+        self.pivot + swing * self.arm - self.center
+    }
+}
+
+/// Angle (radians, dans [-π, π]) de la composante de `q` autour de `axis` unitaire.
+fn twist_angle(q: Quat, axis: Vec3) -> f32 {
+    let angle = 2.0 * Vec3::new(q.x, q.y, q.z).dot(axis).atan2(q.w);
+    if angle > std::f32::consts::PI {
+        angle - std::f32::consts::TAU
+    } else if angle < -std::f32::consts::PI {
+        angle + std::f32::consts::TAU
+    } else {
+        angle
+    }
+}
+
+impl WorldCore {
+    /// Tige entre `anchor` (local à l'entité) et `target_anchor` (local à `target`, point du monde
+    /// sans target) ; `length` None = distance actuelle. False si `target` est l'entité ou est morte.
+    pub fn add_distance_joint(
+        &mut self,
+        id: Entity,
+        target: Option<Entity>,
+        anchor: Vec3,
+        target_anchor: Vec3,
+        length: Option<f32>,
+    ) -> bool {
+        let length = length.unwrap_or_else(|| {
+            let target_matrix = target.map_or(Mat4::IDENTITY, |t| self.compute_world_matrix(t));
+            self.compute_world_matrix(id).transform_point3(anchor).distance(target_matrix.transform_point3(target_anchor))
+        });
+        self.insert_joint(id, target, JointKind::Distance { length: length.max(0.0) }, anchor, target_anchor)
+    }
+
+    /// Rotule autour de `pivot` (monde).
+    pub fn add_ball_joint(&mut self, id: Entity, target: Option<Entity>, pivot: Vec3) -> bool {
+        self.insert_pivot_joint(id, target, JointKind::BallSocket, pivot)
+    }
+
+    /// Charnière d'axe `axis` (monde) passant par `pivot` ; `limits` (min, max) en degrés,
+    /// 0 = pose actuelle. False si l'axe est nul.
+    pub fn add_hinge_joint(
+        &mut self,
+        id: Entity,
+        target: Option<Entity>,
+        pivot: Vec3,
+        axis: Vec3,
+        limits: Option<(f32, f32)>,
+    ) -> bool {
+        let Some(axis) = axis.try_normalize() else { return false };
+        let target_rotation = target.map_or(Quat::IDENTITY, |t| self.world_rotation(t));
+        let kind = JointKind::Hinge { axis: target_rotation.inverse() * axis, limits };
+        self.insert_pivot_joint(id, target, kind, pivot)
+    }
+
+    /// Soudure dans la pose actuelle.
+    pub fn add_fixed_joint(&mut self, id: Entity, target: Option<Entity>) -> bool {
+        let center = self.compute_world_matrix(id).w_axis.truncate();
+        self.insert_pivot_joint(id, target, JointKind::Fixed, center)
+    }
+
+    /// Retire le joint de l'entité.
+    pub fn remove_joint(&mut self, id: Entity) {
+        self.wake_up(id);
+        self.components.remove::<Joint>(id);
+    }
+
+    /// Liaison dont les deux ancres sont sur `pivot` (monde) dans la pose actuelle.
+    fn insert_pivot_joint(&mut self, id: Entity, target: Option<Entity>, kind: JointKind, pivot: Vec3) -> bool {
+        let local_point = |e: Entity| self.compute_world_matrix(e).inverse().transform_point3(pivot);
+        let anchor        = local_point(id);
+        let target_anchor = target.map_or(pivot, local_point);
+        self.insert_joint(id, target, kind, anchor, target_anchor)
+    }
+
+    fn insert_joint(&mut self, id: Entity, target: Option<Entity>, kind: JointKind, anchor: Vec3, target_anchor: Vec3) -> bool {
+        if target.is_some_and(|t| t == id || !self.entities.is_alive(t)) {
+            return false;
+        }
+        let target_rotation = target.map_or(Quat::IDENTITY, |t| self.world_rotation(t));
+        let rest_rotation   = target_rotation.inverse() * self.world_rotation(id);
+        self.wake_up(id);
+        if let Some(target) = target {
+            self.wake_up(target);
+        }
+        self.components.insert(id, Joint { kind, target, anchor, target_anchor, rest_rotation });
+        true
+    }
+
+    /// Retire les joints dont la cible vérifie `doomed` (entités sur le point d'être supprimées).
+    pub(crate) fn drop_joints_to(&mut self, doomed: impl Fn(Entity) -> bool) {
+        let orphans: Vec<Entity> = self.components.storage::<Joint>().iter()
+            .filter(|(_, j)| j.target.is_some_and(&doomed))
+            .map(|(id, _)| id)
+            .collect();
+        for id in orphans {
+            self.remove_joint(id);
+        }
+    }
+
+    fn world_rotation(&self, id: Entity) -> Quat {
+        self.compute_world_matrix(id).to_scale_rotation_translation().1
+    }
+}
@@ -0,0 +1,152 @@
+//! Requêtes spatiales : raycast, sphere-cast et box-cast contre les colliders.
+//!
+//! Les colliders (hors triggers) sont rangés dans un BVH (`QueryTree`), reconstruit à la
+//! première requête qui suit un changement de Transform, Parent ou Collider. Une requête
+//! en tire les colliders dont la boîte englobante, agrandie de la demi-taille de la forme
+//! lancée, est traversée par le rayon, les trie par distance d'entrée dans cette boîte et
+//! interroge la narrow-phase jusqu'à ce que la suivante commence après le meilleur impact :
+//! `collision::ray_cast` pour un rayon, `collision::shape_cast` (donc `collision::contact`)
+//! pour une forme. Les colliders qui contiennent / chevauchent déjà l'origine sont ignorés :
+//! un rayon tiré depuis le joueur ne touche pas sa propre capsule. `layer_mask` filtre sur
+//! `Collider::layer` (le masque du collider ne concerne que la physique).
+
+use glam::{Quat, Vec3};
+
+use crate::broadphase::{Aabb, Bvh};
+use crate::collision::{self, CastHit, Obb, Shape};
+use crate::ecs::{Collider, Entity, Parent, Transform};
+use crate::world_core::WorldCore;
+
+/// Couches touchées par défaut : toutes.
+pub const ALL_LAYERS: u32 = u32::MAX;
+
+/// Colliders non triggers posés en monde, et leur BVH.
+pub(crate) struct QueryTree {
+    ticks:     [u64; 3],  // Transform, Parent, Collider à la construction
+    colliders: Vec<QueryCollider>,
+    bvh:       Bvh,
+}
+
+struct QueryCollider {
+    entity: Entity,
+    layer:  u32,
+    shape:  Shape,
+    aabb:   Aabb,
+}
+
+impl QueryTree {
+    fn build(world: &WorldCore) -> Self {
+        let transforms = world.components.storage::<Transform>();
+        let colliders: Vec<QueryCollider> = world.components.storage::<Collider>().iter()
+            .filter(|&(id, collider)| !collider.is_trigger && transforms.contains(id))
+            .map(|(id, collider)| {
+                let shape = Shape::from_collider(world.world_matrix(id), collider);
+                QueryCollider { entity: id, layer: collider.layer, aabb: shape.aabb(), shape }
+            })
+            .collect();
+        QueryTree {
+            ticks: Self::ticks(world),
+            bvh: Bvh::build(colliders.iter().map(|c| c.aabb).collect()),
+            colliders,
+        }
+    }
+
+    fn ticks(world: &WorldCore) -> [u64; 3] {
+        [
+            world.components.storage::<Transform>().change_tick(),
+            world.components.storage::<Parent>().change_tick(),
+            world.components.storage::<Collider>().change_tick(),
+        ]
+    }
+}
+
+/// Premier collider touché par une requête.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub entity:   Entity,
+    /// Point de contact en espace monde.
+    pub point:    Vec3,
+    /// Normale unitaire de la surface touchée.
+    pub normal:   Vec3,
+    /// Distance parcourue depuis l'origine le long de la direction.
+    pub distance: f32,
+}
+
+impl WorldCore {
+    /// Premier collider touché par le rayon `origin + t * dir`, t ∈ [0, max_dist].
+    /// `dir` n'a pas besoin d'être normalisée ; None si nul ou si rien n'est touché.
+    pub fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32, layer_mask: u32) -> Option<RayHit> {
+        let dir = dir.try_normalize()?;
+        self.first_hit(origin, Vec3::ZERO, dir, max_dist, layer_mask, |shape| {
+            collision::ray_cast(shape, origin, dir, max_dist)
+        })
+    }
+
+    /// Comme `raycast`, pour une sphère de rayon `radius` centrée en `origin`.
+    pub fn sphere_cast(&self, origin: Vec3, radius: f32, dir: Vec3, max_dist: f32, layer_mask: u32) -> Option<RayHit> {
+        let dir    = dir.try_normalize()?;
+        let moving = Shape::Sphere { center: origin, radius };
+        self.first_hit(origin, Vec3::splat(radius), dir, max_dist, layer_mask, |shape| {
+            collision::shape_cast(&moving, dir, max_dist, shape)
+        })
+    }
+
+    /// Comme `raycast`, pour une boîte de demi-extents `half_extents` orientée par `rotation`.
+    pub fn box_cast(&self, center: Vec3, half_extents: Vec3, rotation: Quat, dir: Vec3, max_dist: f32, layer_mask: u32)
+        -> Option<RayHit>
+    {
+        let dir    = dir.try_normalize()?;
+        let moving = Shape::Box(Obb {
+            center,
+            axes: [rotation * Vec3::X, rotation * Vec3::Y, rotation * Vec3::Z],
+            half_extents: half_extents.abs(),
+        });
+        let reach = moving.aabb();
+        self.first_hit(center, (reach.max - reach.min) * 0.5, dir, max_dist, layer_mask, |shape| {
+            collision::shape_cast(&moving, dir, max_dist, shape)
+        })
+    }
+
+    /// Impact le plus proche parmi les colliders dont la boîte englobante, agrandie de
+    /// `half` (demi-taille de la forme lancée), est traversée par le rayon.
+    fn first_hit(
+        &self,
+        origin: Vec3,
+        half: Vec3,
+        dir: Vec3,
+        max_dist: f32,
+        layer_mask: u32,
+        cast: impl Fn(&Shape) -> Option<CastHit>,
+    ) -> Option<RayHit> {
+        if max_dist.is_nan() || max_dist < 0.0 {
+            return None;
+        }
+        let mut cache = self.query_tree.borrow_mut();
+        if cache.as_ref().is_none_or(|tree| tree.ticks != QueryTree::ticks(self)) {
+            *cache = Some(QueryTree::build(self));
+        }
+        let tree = cache.as_ref()?;
+
+        let mut candidates = Vec::new();
+        tree.bvh.query_sweep(origin, half, dir, max_dist, &mut candidates);
+        let mut candidates: Vec<(f32, &QueryCollider)> = candidates.into_iter()
+            .map(|i| &tree.colliders[i])
+            .filter(|c| c.layer & layer_mask != 0)
+            .filter_map(|c| Some((c.aabb.expand_by(half).ray_interval(origin, dir, max_dist)?.0, c)))
+            .collect();
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut best: Option<RayHit> = None;
+        for (enter, collider) in candidates {
+            if best.is_some_and(|b| enter > b.distance) {
+                break;
+            }
+            if let Some(hit) = cast(&collider.shape)
+                && best.is_none_or(|b| hit.distance < b.distance)
+            {
+                best = Some(RayHit { entity: collider.entity, point: hit.point, normal: hit.normal, distance: hit.distance });
+            }
+        }
+        best
+    }
+}
@@ -1,17 +1,40 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::ecs::{CharacterController, Entity, Joint, JointKind, Transform};
+
 #[derive(Serialize, Deserialize)]
 pub struct SceneTransform {
     pub position: [f32; 3],
-    pub rotation: [f32; 3],
+    pub rotation: SceneRotation,
     pub scale:    [f32; 3],
 }
 
+/// Rotation d'une scène : `[x, y, z]` en degrés (Euler XYZ, anciens fichiers / édition
+/// à la main) ou quaternion `[x, y, z, w]`. `save_scene` écrit toujours un quaternion.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(untagged)]
+pub enum SceneRotation {
+    Euler([f32; 3]),
+    Quat([f32; 4]),
+}
+
+impl SceneRotation {
+    pub fn to_quat(self) -> glam::Quat {
+        match self {
+            SceneRotation::Euler(e) => Transform::quat_from_euler_deg(glam::Vec3::from(e)),
+            // Quaternion nul ou non fini (fichier édité à la main) : pas de rotation.
+            SceneRotation::Quat(q)  => glam::Vec4::from_array(q).try_normalize().map_or(glam::Quat::IDENTITY, glam::Quat::from_vec4),
+        }
+    }
+}
+
 impl Default for SceneTransform {
     fn default() -> Self {
         Self {
             position: [0.0, 0.0, 0.0],
-            rotation: [0.0, 0.0, 0.0],
+            rotation: SceneRotation::Euler([0.0, 0.0, 0.0]),
             scale:    [1.0, 1.0, 1.0],
         }
     }
@@ -19,6 +42,10 @@ impl Default for SceneTransform {
 
 fn default_metallic()  -> f32 { 0.0 }
 fn default_roughness() -> f32 { 0.5 }
+fn default_mass()     -> f32 { 1.0 }
+fn default_friction() -> f32 { 0.5 }
+fn default_angular_damping() -> f32 { 0.05 }
+fn default_gravity_scale()   -> f32 { 1.0 }
 fn default_fov()  -> f32 { 60.0 }
 fn default_near() -> f32 { 0.1 }
 fn default_far()  -> f32 { 1000.0 }
@@ -73,6 +100,113 @@ pub struct SceneMaterial {
 #[derive(Serialize, Deserialize)]
 pub struct SceneRigidBody {
     pub is_static: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kinematic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ccd: Option<bool>,
+    #[serde(default = "default_mass")]
+    pub mass: f32,
+    #[serde(default)]
+    pub restitution: f32,
+    #[serde(default = "default_friction")]
+    pub friction: f32,
+    #[serde(default)]
+    pub linear_damping: f32,
+    #[serde(default = "default_angular_damping")]
+    pub angular_damping: f32,
+    #[serde(default = "default_gravity_scale")]
+    pub gravity_scale: f32,
+}
+
+/// Réglages d'un CharacterController ; champs absents = valeurs par défaut.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SceneCharacterController {
+    pub speed:        f32,
+    pub acceleration: f32,
+    pub air_control:  f32,
+    pub jump_height:  f32,
+    pub step_height:  f32,
+    pub slope_limit:  f32,   // degrés
+    pub coyote_time:  f32,
+    pub jump_buffer:  f32,
+    pub player:       bool,
+}
+
+impl Default for SceneCharacterController {
+    fn default() -> Self {
+        let c = CharacterController::default();
+        SceneCharacterController {
+            speed:        c.speed,
+            acceleration: c.acceleration,
+            air_control:  c.air_control,
+            jump_height:  c.jump_height,
+            step_height:  c.step_height,
+            slope_limit:  c.slope_limit,
+            coyote_time:  c.coyote_time,
+            jump_buffer:  c.jump_buffer,
+            player:       c.player,
+        }
+    }
+}
+
+/// Joint d'une entité ; `kind` : "distance" | "ball" | "hinge" | "fixed".
+#[derive(Serialize, Deserialize)]
+pub struct SceneJoint {
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_index: Option<usize>,   // index dans entities ; absent = point fixe du monde
+    pub anchor:        [f32; 3],
+    pub target_anchor: [f32; 3],
+    pub rest_rotation: [f32; 4],       // quaternion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<f32>,           // distance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub axis:   Option<[f32; 3]>,      // hinge, repère de la cible
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<[f32; 2]>,      // hinge, degrés
+}
+
+impl SceneJoint {
+    pub fn new(joint: &Joint, target_index: Option<usize>) -> Self {
+        let (kind, length, axis, limits) = match joint.kind {
+            JointKind::Distance { length }    => ("distance", Some(length), None, None),
+            JointKind::BallSocket             => ("ball", None, None, None),
+            JointKind::Hinge { axis, limits } => ("hinge", None, Some(axis.to_array()), limits.map(|(a, b)| [a, b])),
+            JointKind::Fixed                  => ("fixed", None, None, None),
+        };
+        SceneJoint {
+            kind: kind.to_string(),
+            target_index,
+            anchor:        joint.anchor.to_array(),
+            target_anchor: joint.target_anchor.to_array(),
+            rest_rotation: joint.rest_rotation.to_array(),
+            length,
+            axis,
+            limits,
+        }
+    }
+
+    /// Joint vers `target` ; None si `kind` est inconnu.
+    pub fn to_joint(&self, target: Option<Entity>) -> Option<Joint> {
+        let kind = match self.kind.as_str() {
+            "distance" => JointKind::Distance { length: self.length.unwrap_or(0.0) },
+            "ball"     => JointKind::BallSocket,
+            "hinge"    => JointKind::Hinge {
+                axis:   glam::Vec3::from(self.axis.unwrap_or([0.0, 1.0, 0.0])).normalize_or(glam::Vec3::Y),
+                limits: self.limits.map(|[a, b]| (a, b)),
+            },
+            "fixed"    => JointKind::Fixed,
+            _          => return None,
+        };
+        Some(Joint {
+            kind,
+            target,
+            anchor:        glam::Vec3::from(self.anchor),
+            target_anchor: glam::Vec3::from(self.target_anchor),
+            rest_rotation: SceneRotation::Quat(self.rest_rotation).to_quat(),
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -106,6 +240,20 @@ pub struct SceneEntityData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub collider_aabb: Option<[f32; 3]>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_sphere: Option<f32>,          // rayon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_capsule: Option<[f32; 2]>,    // [rayon, demi-hauteur du segment]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_mesh: Option<bool>,           // triangles du mesh custom (`mesh_type`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_trigger: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_layer: Option<u32>,           // bits ; absent = couche 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_mask: Option<u32>,            // bits ; absent = toutes les couches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_controller: Option<SceneCharacterController>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub point_light:   Option<ScenePointLight>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mesh_type: Option<String>,
@@ -117,6 +265,11 @@ pub struct SceneEntityData {
     pub camera: Option<SceneCameraComponent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_index: Option<usize>,  // index dans le tableau entities (pas l'ID moteur)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joint: Option<SceneJoint>,
+    /// Composants enregistrés via `Components::register_serialized`, par clé.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, serde_json::Value>,
 }
 
 /// Structure top-level du fichier JSON de scène.
@@ -124,6 +277,9 @@ pub struct SceneEntityData {
 pub struct SceneData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub directional_light: Option<SceneDirectionalLight>,
+    /// Gravité du monde ; absente = (0, -9.8, 0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gravity: Option<[f32; 3]>,
     #[serde(default)]
     pub entities: Vec<SceneEntityData>,
 }
@@ -0,0 +1,228 @@
+//! Ordonnanceur de systèmes par étapes.
+//!
+//! `WorldCore::update` exécute PreUpdate → FixedUpdate (0..n pas fixes, cf. `FixedTime`)
+//! → Update → PostUpdate ;
+//! le renderer exécute Render au début de `render_frame`. Dans une étape, les
+//! systèmes tournent dans l'ordre d'insertion (`add_system_before` / `add_system_after`
+//! pour se placer par rapport à un label existant).
+
+use crate::world_core::WorldCore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    PreUpdate,
+    FixedUpdate,
+    Update,
+    PostUpdate,
+    Render,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 5] = [Stage::PreUpdate, Stage::FixedUpdate, Stage::Update, Stage::PostUpdate, Stage::Render];
+}
+
+/// Système : fonction sur le monde, `dt` en secondes.
+pub type System = Box<dyn FnMut(&mut WorldCore, f32)>;
+
+struct Entry {
+    label:  &'static str,
+    system: System,
+}
+
+#[derive(Default)]
+pub struct Schedule {
+    stages: [Vec<Entry>; Stage::ALL.len()],
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute un système en fin d'étape. Panique si le label existe déjà.
+    pub fn add_system<F>(&mut self, stage: Stage, label: &'static str, system: F)
+    where
+        F: FnMut(&mut WorldCore, f32) + 'static,
+    {
+        self.assert_unique(label);
+        self.stages[stage as usize].push(Entry { label, system: Box::new(system) });
+    }
+
+    /// Insère un système juste avant `before` (même étape). Retourne false si `before` est inconnu.
+    pub fn add_system_before<F>(&mut self, before: &str, label: &'static str, system: F) -> bool
+    where
+        F: FnMut(&mut WorldCore, f32) + 'static,
+    {
+        self.insert_at(before, 0, label, Box::new(system))
+    }
+
+    /// Insère un système juste après `after` (même étape). Retourne false si `after` est inconnu.
+    pub fn add_system_after<F>(&mut self, after: &str, label: &'static str, system: F) -> bool
+    where
+        F: FnMut(&mut WorldCore, f32) + 'static,
+    {
+        self.insert_at(after, 1, label, Box::new(system))
+    }
+
+    /// Retire un système (intégré ou non). Retourne false si le label est inconnu.
+    pub fn remove_system(&mut self, label: &str) -> bool {
+        let Some((stage, i)) = self.find(label) else { return false };
+        self.stages[stage].remove(i);
+        true
+    }
+
+    /// Labels des systèmes d'une étape, dans l'ordre d'exécution.
+    pub fn labels(&self, stage: Stage) -> impl Iterator<Item = &'static str> + '_ {
+        self.stages[stage as usize].iter().map(|e| e.label)
+    }
+
+    fn find(&self, label: &str) -> Option<(usize, usize)> {
+        self.stages.iter().enumerate().find_map(|(s, entries)| {
+            entries.iter().position(|e| e.label == label).map(|i| (s, i))
+        })
+    }
+
+    fn assert_unique(&self, label: &str) {
+        assert!(self.find(label).is_none(), "system '{label}' already registered");
+    }
+
+    fn insert_at(&mut self, anchor: &str, offset: usize, label: &'static str, system: System) -> bool {
+        self.assert_unique(label);
+        let Some((stage, i)) = self.find(anchor) else { return false };
+        self.stages[stage].insert(i + offset, Entry { label, system });
+        true
+    }
+
+    /// Exécute les systèmes d'une étape sur `world`.
+    pub(crate) fn run(&mut self, stage: Stage, world: &mut WorldCore, dt: f32) {
+        for entry in &mut self.stages[stage as usize] {
+            (entry.system)(world, dt);
+        }
+    }
+
+    /// Déplace les systèmes de `other` (ajoutés pendant une exécution) à la fin de leurs étapes.
+    pub(crate) fn absorb(&mut self, other: Schedule) {
+        for (dst, src) in self.stages.iter_mut().zip(other.stages) {
+            dst.extend(src);
+        }
+    }
+}
+
+/// Accumulateur du pas fixe de FixedUpdate : la physique avance par pas de `1 / hz`
+/// quel que soit le framerate, au plus `max_substeps` fois par frame (le temps en
+/// excès est abandonné plutôt que de partir en spirale).
+pub struct FixedTime {
+    step:         f32,
+    max_substeps: u32,
+    accumulator:  f32,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        FixedTime::new(60.0, 5)
+    }
+}
+
+impl FixedTime {
+    pub fn new(hz: f32, max_substeps: u32) -> Self {
+        assert!(hz > 0.0, "fixed timestep frequency must be positive ({hz})");
+        FixedTime { step: 1.0 / hz, max_substeps: max_substeps.max(1), accumulator: 0.0 }
+    }
+
+    /// Durée d'un pas fixe, en secondes.
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    pub fn max_substeps(&self) -> u32 {
+        self.max_substeps
+    }
+
+    /// Fraction du pas suivant déjà écoulée ∈ [0, 1[ : poids de l'interpolation de rendu.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step).clamp(0.0, 1.0)
+    }
+
+    /// Ajoute `dt` secondes et retourne le nombre de pas fixes à exécuter.
+    pub(crate) fn advance(&mut self, dt: f32) -> u32 {
+        self.accumulator += dt.max(0.0);
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < self.max_substeps {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        if self.accumulator >= self.step {
+            self.accumulator %= self.step;
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn fixed_time_runs_whole_steps() {
+        let mut time = FixedTime::new(60.0, 5);
+        assert_eq!(time.advance(1.0 / 30.0), 2);
+        assert!(time.alpha() < 1e-3);
+    }
+
+    #[test]
+    fn fixed_time_clamps_long_frames() {
+        let mut time = FixedTime::new(60.0, 5);
+        assert_eq!(time.advance(1.0), 5, "une frame d'une seconde : 5 sous-pas, pas 60");
+        assert!((0.0..1.0).contains(&time.alpha()), "le retard est abandonné");
+        assert_eq!(time.advance(0.0), 0);
+    }
+
+    #[test]
+    fn fixed_time_accumulates_fractions() {
+        let mut time = FixedTime::new(60.0, 5);
+        let quarter = time.step() * 0.25;
+        assert_eq!(time.advance(quarter), 0);
+        assert!((time.alpha() - 0.25).abs() < 1e-4);
+        assert_eq!(time.advance(quarter * 2.0), 0);
+        assert!((time.alpha() - 0.75).abs() < 1e-4);
+        assert_eq!(time.advance(quarter * 2.0), 1);
+        assert!((time.alpha() - 0.25).abs() < 1e-3);
+        assert_eq!(time.advance(-1.0), 0, "dt négatif ignoré");
+    }
+
+    #[test]
+    fn systems_run_in_insertion_order_with_anchors() {
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let mut schedule = Schedule::new();
+        let log = |label: &'static str| {
+            let order = order.clone();
+            move |_: &mut WorldCore, _: f32| order.borrow_mut().push(label)
+        };
+        schedule.add_system(Stage::Update, "b", log("b"));
+        schedule.add_system(Stage::Update, "d", log("d"));
+        assert!(schedule.add_system_before("b", "a", log("a")));
+        assert!(schedule.add_system_after("b", "c", log("c")));
+        assert!(!schedule.add_system_after("missing", "e", log("e")));
+        schedule.add_system(Stage::PostUpdate, "post", log("post"));
+
+        assert_eq!(schedule.labels(Stage::Update).collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+        let mut world = WorldCore::new();
+        schedule.run(Stage::Update, &mut world, 0.0);
+        assert_eq!(*order.borrow(), ["a", "b", "c", "d"]);
+
+        assert!(schedule.remove_system("b"));
+        assert!(!schedule.remove_system("b"));
+        assert_eq!(schedule.labels(Stage::Update).collect::<Vec<_>>(), ["a", "c", "d"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "system 'a' already registered")]
+    fn duplicate_label_panics() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::Update, "a", |_, _| {});
+        schedule.add_system_after("a", "a", |_, _| {});
+    }
+}
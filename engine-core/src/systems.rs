@@ -0,0 +1,840 @@
+//! Systèmes intégrés, enregistrés par `WorldCore::new` dans le `Schedule`.
+//!
+//! - PreUpdate   : `camera_look`
+//! - FixedUpdate : `snapshot_previous` → `drive_characters` → `apply_forces` → `integrate` → `step_up`
+//!   → `sweep_fast_bodies` → `resolve_static` → `resolve_dynamic` → `solve_contacts` → `solve_joints`
+//!   → `update_sleep` → `detect_triggers` → `collision_events`
+//! - PostUpdate  : `clear_forces` → `propagate_transforms`
+//! - Render      : `propagate_transforms_render` (rattrape les modifications faites hors `update`, ex. éditeur)
+
+use std::collections::{HashMap, HashSet};
+
+use crate::broadphase::{Aabb, Bvh};
+use crate::collision::{contact, contacts, shape_cast, Contact, ContactPoints, Shape, MAX_CONTACT_POINTS};
+use crate::ecs::{BodyType, CameraComponent, CharacterController, Collider, Entity, Parent, PreviousTransform, Query, RigidBody, Transform};
+use crate::joints;
+use crate::schedule::{Schedule, Stage};
+use crate::world_core::WorldCore;
+
+/// Contact considéré comme un sol si sa normale est à moins de ~45° de la verticale.
+const GROUND_NORMAL_Y: f32 = 0.7;
+/// Marge des requêtes broadphase : couvre les corrections de position faites pendant la passe.
+const BROADPHASE_MARGIN: f32 = 0.05;
+/// Vitesse d'approche (m/s) sous laquelle un contact ne rebondit pas : sinon un corps
+/// posé avec `restitution > 0` tremblerait sous l'effet de la gravité.
+const RESTITUTION_MIN_SPEED: f32 = 1.0;
+/// Un corps CCD s'arrête à cette distance au-delà du premier impact : le léger recouvrement
+/// laisse `resolve_static` appliquer le contact (impulsion, on_ground) comme d'habitude.
+const CCD_SKIN: f32 = 0.005;
+/// Itérations de la dichotomie qui cherche la hauteur d'une marche (précision step_height / 2^n).
+const STEP_BISECT_ITERS: u32 = 10;
+/// Contacts résolus au plus par corps et par collider maillage à chaque pas.
+const MESH_CONTACT_PASSES: u32 = 4;
+/// Passes de `solve_contacts` sur tous les contacts du pas : chacun voit les impulsions
+/// des autres, une pile tient debout.
+const SOLVER_ITERATIONS: u32 = 8;
+/// Seuils de repos : vitesses (m/s, rad/s) sous lesquelles un corps compte comme immobile.
+const SLEEP_LINEAR_SPEED: f32 = 0.1;
+const SLEEP_ANGULAR_SPEED: f32 = 0.1;
+/// Durée de repos (s) de tout un îlot avant qu'il ne s'endorme.
+const SLEEP_DELAY: f32 = 0.5;
+
+/// Enregistre les systèmes intégrés dans leurs étapes.
+pub(crate) fn register_builtin(schedule: &mut Schedule) {
+    schedule.add_system(Stage::PreUpdate,   "camera_look",       camera_look);
+    schedule.add_system(Stage::FixedUpdate, "snapshot_previous", snapshot_previous);
+    schedule.add_system(Stage::FixedUpdate, "drive_characters",  drive_characters);
+    schedule.add_system(Stage::FixedUpdate, "apply_forces",      apply_forces);
+    schedule.add_system(Stage::FixedUpdate, "integrate",         integrate);
+    schedule.add_system(Stage::FixedUpdate, "step_up",           step_up);
+    schedule.add_system(Stage::FixedUpdate, "sweep_fast_bodies", sweep_fast_bodies);
+    schedule.add_system(Stage::FixedUpdate, "resolve_static",    resolve_static);
+    schedule.add_system(Stage::FixedUpdate, "resolve_dynamic",   resolve_dynamic);
+    schedule.add_system(Stage::FixedUpdate, "solve_contacts",    solve_contacts);
+    schedule.add_system(Stage::FixedUpdate, "solve_joints",      joints::solve_joints);
+    schedule.add_system(Stage::FixedUpdate, "update_sleep",      update_sleep);
+    schedule.add_system(Stage::FixedUpdate, "detect_triggers",   detect_triggers);
+    schedule.add_system(Stage::FixedUpdate, "collision_events",  |w, _| w.contacts.end_step());
+    schedule.add_system(Stage::PostUpdate,  "clear_forces",                clear_forces);
+    schedule.add_system(Stage::PostUpdate,  "propagate_transforms",        |w, _| w.propagate_transforms());
+    schedule.add_system(Stage::Render,      "propagate_transforms_render", |w, _| w.propagate_transforms());
+}
+
+/// Contact détecté par `resolve_static` / `resolve_dynamic` (positions déjà corrigées),
+/// résolu en vitesse par `solve_contacts`.
+pub(crate) struct PendingContact {
+    a:           Entity,
+    b:           Option<Entity>,   // None : obstacle de masse infinie (statique, kinematic)
+    obstacle:    glam::Vec3,       // vitesse de l'obstacle si `b` est None
+    contact:     Contact,
+    restitution: f32,
+    friction:    f32,
+}
+
+/// Un corps pendant `solve_contacts` : ses vitesses suivent les impulsions reçues.
+#[derive(Clone, Copy)]
+struct ContactBody {
+    velocity:         glam::Vec3,
+    angular_velocity: glam::Vec3,
+    inv_mass:         f32,
+    inv_inertia:      glam::Mat3,   // monde
+    center:           glam::Vec3,
+}
+
+impl ContactBody {
+    /// Dynamique `rb` posé sur `tr`.
+    fn dynamic(rb: &RigidBody, tr: &Transform, collider: Option<&Collider>, is_character: bool) -> Self {
+        ContactBody {
+            velocity:         rb.velocity,
+            angular_velocity: rb.angular_velocity,
+            inv_mass:         rb.inverse_mass(),
+            inv_inertia:      inverse_inertia(rb, tr, collider, is_character),
+            center:           tr.position,
+        }
+    }
+
+    /// Masse infinie (statique, kinematic) animée de `velocity`.
+    fn fixed(velocity: glam::Vec3) -> Self {
+        ContactBody {
+            velocity,
+            angular_velocity: glam::Vec3::ZERO,
+            inv_mass:         0.0,
+            inv_inertia:      glam::Mat3::ZERO,
+            center:           glam::Vec3::ZERO,
+        }
+    }
+
+    fn point_velocity(&self, point: glam::Vec3) -> glam::Vec3 {
+        self.velocity + self.angular_velocity.cross(point - self.center)
+    }
+
+    /// Masse inverse apparente en `point` le long de `dir` (unitaire).
+    fn inverse_mass_along(&self, point: glam::Vec3, dir: glam::Vec3) -> f32 {
+        let arm = point - self.center;
+        self.inv_mass + (self.inv_inertia * arm.cross(dir)).cross(arm).dot(dir)
+    }
+
+    fn apply_impulse(&mut self, point: glam::Vec3, impulse: glam::Vec3) {
+        self.velocity         += impulse * self.inv_mass;
+        self.angular_velocity += self.inv_inertia * (point - self.center).cross(impulse);
+    }
+}
+
+/// Tenseur d'inertie inverse monde d'un corps ; nul pour un personnage, dont la rotation suit la caméra.
+fn inverse_inertia(rb: &RigidBody, tr: &Transform, collider: Option<&Collider>, is_character: bool) -> glam::Mat3 {
+    if is_character {
+        return glam::Mat3::ZERO;
+    }
+    rb.inverse_inertia(collider.map(|c| &c.shape), tr.rotation, tr.scale)
+}
+
+/// Un contact pendant `solve_contacts` : corps A et B (indices des `ContactBody`, `normal`
+/// de A vers B) et impulsions cumulées par point. Les cumuls restent bornés : normale ≥ 0
+/// (les corps se repoussent sans se coller), frottement de Coulomb ≤ `friction` × normale.
+struct ContactConstraint {
+    a:            usize,
+    b:            usize,
+    normal:       glam::Vec3,
+    points:       ContactPoints,
+    friction:     f32,
+    target:       [f32; MAX_CONTACT_POINTS],          // vitesse normale visée (rebond)
+    normal_sum:   [f32; MAX_CONTACT_POINTS],
+    friction_sum: [glam::Vec3; MAX_CONTACT_POINTS],
+}
+
+impl ContactConstraint {
+    /// Contrainte du contact `pending` entre `bodies[a]` et `bodies[b]`. La vitesse d'approche
+    /// sera annulée, ou renvoyée × restitution si elle dépasse `RESTITUTION_MIN_SPEED`.
+    fn new(a: usize, b: usize, pending: &PendingContact, bodies: &[ContactBody]) -> Self {
+        let (normal, points) = (pending.contact.normal, pending.contact.points);
+        let mut target = [0.0; MAX_CONTACT_POINTS];
+        for (k, p) in points.iter().enumerate() {
+            let vn = (bodies[b].point_velocity(p) - bodies[a].point_velocity(p)).dot(normal);
+            if -vn >= RESTITUTION_MIN_SPEED {
+                target[k] = -vn * pending.restitution;
+            }
+        }
+        ContactConstraint {
+            a, b, normal, points, target,
+            friction:     pending.friction,
+            normal_sum:   [0.0; MAX_CONTACT_POINTS],
+            friction_sum: [glam::Vec3::ZERO; MAX_CONTACT_POINTS],
+        }
+    }
+
+    /// Une passe : correction normale commune au centre des points, répartie à parts égales
+    /// (une face posée à plat reste résolue sans couple parasite), puis point par point
+    /// impulsion normale et frottement (sans jamais inverser le glissement).
+    fn solve(&mut self, bodies: &mut [ContactBody]) {
+        let (mut a, mut b) = (bodies[self.a], bodies[self.b]);
+        let normal = self.normal;
+        let k_normal = |a: &ContactBody, b: &ContactBody, p| a.inverse_mass_along(p, normal) + b.inverse_mass_along(p, normal);
+        let push = |a: &mut ContactBody, b: &mut ContactBody, sum: &mut f32, p, delta: f32| {
+            let total = (*sum + delta).max(0.0);
+            let jn    = normal * (total - *sum);
+            *sum = total;
+            a.apply_impulse(p, -jn);
+            b.apply_impulse(p, jn);
+        };
+
+        let count    = self.points.iter().count() as f32;
+        let centroid = self.points.iter().sum::<glam::Vec3>() / count;
+        let k_center = k_normal(&a, &b, centroid);
+        if k_center > 0.0 {
+            let vn    = (b.point_velocity(centroid) - a.point_velocity(centroid)).dot(normal);
+            let share = (self.target.iter().sum::<f32>() / count - vn) / k_center / count;
+            for (k, p) in self.points.iter().enumerate() {
+                push(&mut a, &mut b, &mut self.normal_sum[k], p, share);
+            }
+        }
+
+        for (k, p) in self.points.iter().enumerate() {
+            let k_point = k_normal(&a, &b, p);
+            if k_point <= 0.0 {
+                continue;
+            }
+            let vn = (b.point_velocity(p) - a.point_velocity(p)).dot(normal);
+            push(&mut a, &mut b, &mut self.normal_sum[k], p, (self.target[k] - vn) / k_point);
+
+            let relative   = b.point_velocity(p) - a.point_velocity(p);
+            let tangential = relative - normal * relative.dot(normal);
+            let slide = tangential.length();
+            if slide <= 1e-6 {
+                continue;
+            }
+            let dir    = tangential / slide;
+            let wanted = self.friction_sum[k] - dir * (slide / (a.inverse_mass_along(p, dir) + b.inverse_mass_along(p, dir)));
+            let total  = wanted.clamp_length_max(self.friction * self.normal_sum[k]);
+            let jt     = total - self.friction_sum[k];
+            self.friction_sum[k] = total;
+            a.apply_impulse(p, -jt);
+            b.apply_impulse(p, jt);
+        }
+        (bodies[self.a], bodies[self.b]) = (a, b);
+    }
+}
+
+/// (restitution, friction) d'un contact entre deux matériaux (restitution, friction) :
+/// rebond du plus élastique, frottement en moyenne géométrique.
+fn mix_material((ea, fa): (f32, f32), (eb, fb): (f32, f32)) -> (f32, f32) {
+    (ea.max(eb), (fa * fb).sqrt())
+}
+
+/// Rotation caméra à la souris + synchro du joueur et de la caméra active.
+fn camera_look(world: &mut WorldCore, _dt: f32) {
+    // Keep mouse-look direction consistent with editor free camera.
+    world.camera_yaw   -= world.input.mouse_dx * world.mouse_sensitivity;
+    world.camera_pitch -= world.input.mouse_dy * world.mouse_sensitivity;
+    world.camera_pitch  = world.camera_pitch
+        .clamp(-89.0_f32.to_radians(), 89.0_f32.to_radians());
+
+    let yaw = world.camera_yaw;
+
+    // Sync yaw back to the player's Transform so its mesh rotates visually
+    // and scripts can read the facing direction via get_rotation().
+    let facing = glam::Quat::from_rotation_y(yaw);
+    let (ccs, trs, prevs) = world.components.storages_mut::<(CharacterController, Transform, PreviousTransform)>();
+    for (id, (cc, tr)) in Query::new((&*ccs, trs)) {
+        if !cc.player { continue; }
+        tr.rotation = facing;
+        // Rotation pilotée par frame, pas par la physique : pas d'interpolation (pas de latence souris).
+        if let Some(prev) = prevs.get_mut(id) {
+            prev.0.rotation = facing;
+        }
+    }
+
+    // Mouse-look sync: only for cameras with follow_entity=true (FPS/TPS mode).
+    // Static cameras (follow_entity=false) keep their manually-set local transform.
+    // - Has parent → parent supplies yaw (via player rotation), camera only gets pitch.
+    // - No parent  → camera gets both yaw and pitch.
+    if world.is_game_mode
+        && let Some(cam_id) = world.active_camera
+    {
+        let follow = world.components.get::<CameraComponent>(cam_id).map(|c| c.follow_entity).unwrap_or(false);
+        if follow {
+            let has_parent = world.components.get::<Parent>(cam_id).is_some();
+            let pitch      = world.camera_pitch;
+            if let Some(ct) = world.components.get_mut::<Transform>(cam_id) {
+                ct.rotation = if has_parent {
+                    glam::Quat::from_rotation_x(pitch)
+                } else {
+                    glam::Quat::from_euler(glam::EulerRot::XYZ, pitch, yaw, 0.0)
+                };
+            }
+        }
+    }
+}
+
+/// Mémorise le Transform des corps mobiles avant le pas, pour l'interpolation de rendu.
+fn snapshot_previous(world: &mut WorldCore, _dt: f32) {
+    let (rbs, trs, prevs) = world.components.storages_mut::<(RigidBody, Transform, PreviousTransform)>();
+    for (id, (rb, tr)) in Query::new((&*rbs, &*trs)) {
+        if rb.body_type != BodyType::Static {
+            prevs.insert(id, PreviousTransform(*tr));
+        }
+    }
+}
+
+/// Input (WASD, saut) ou intention scriptée → velocity des corps dynamiques à CharacterController.
+/// Lit `on_ground` du pas précédent, avant que `apply_forces` ne le remette à false.
+fn drive_characters(world: &mut WorldCore, dt: f32) {
+    let yaw        = world.camera_yaw;
+    let forward_xz = glam::Vec3::new(yaw.sin(), 0.0, -yaw.cos());
+    let right_xz   = glam::Vec3::new(yaw.cos(), 0.0,  yaw.sin());
+    let keys       = world.input.keys;
+    let gravity_y  = -world.gravity.y;
+
+    // WASD → direction XZ
+    let mut move_dir = glam::Vec3::ZERO;
+    if keys & (1 << 0) != 0 { move_dir += forward_xz; }
+    if keys & (1 << 1) != 0 { move_dir -= forward_xz; }
+    if keys & (1 << 2) != 0 { move_dir -= right_xz;   }
+    if keys & (1 << 3) != 0 { move_dir += right_xz;   }
+    let input_dir  = move_dir.normalize_or_zero();
+    let input_jump = keys & (1 << 4) != 0;
+
+    let (ccs, rbs) = world.components.storages_mut::<(CharacterController, RigidBody)>();
+    for (_, (cc, rb)) in Query::new((ccs, rbs)) {
+        if !rb.is_dynamic() { continue; }
+        let (move_dir, jump_down) = if cc.player {
+            (input_dir, input_jump)
+        } else {
+            (cc.intent, std::mem::take(&mut cc.jump_request))
+        };
+        cc.air_time = if rb.on_ground { 0.0 } else { cc.air_time + dt };
+
+        // Vitesse horizontale : tend vers la vitesse visée, moins vite en l'air.
+        let control = if rb.on_ground { 1.0 } else { cc.air_control.clamp(0.0, 1.0) };
+        let current = glam::Vec3::new(rb.velocity.x, 0.0, rb.velocity.z);
+        let target  = move_dir * cc.speed.max(0.0);
+        let delta   = target - current;
+        let max_dv  = cc.acceleration.max(0.0) * control * dt;
+        let next    = if delta.length() <= max_dv { target } else { current + delta.normalize() * max_dv };
+        rb.velocity.x = next.x;
+        rb.velocity.z = next.z;
+
+        // Saut : un appui reste valable `jump_buffer` s (atterrissage imminent), et le sol
+        // `coyote_time` s après l'avoir quitté (bord de plateforme).
+        if jump_down && !cc.jump_held {
+            cc.jump_queued = cc.jump_buffer.max(dt);
+        }
+        cc.jump_held = jump_down;
+        if cc.jump_queued > 0.0 && cc.air_time <= cc.coyote_time {
+            // + g·dt/2 : compense l'intégration discrète, le sommet atteint alors jump_height.
+            let g = (gravity_y * rb.gravity_scale).max(0.0);
+            rb.velocity.y  = (2.0 * g * cc.jump_height.max(0.0)).sqrt() + 0.5 * g * dt;
+            cc.jump_queued = 0.0;
+            cc.air_time    = f32::INFINITY;  // pas de second saut pendant le coyote time
+        } else {
+            cc.jump_queued = (cc.jump_queued - dt).max(0.0);
+        }
+    }
+}
+
+/// Gravité, forces et couples cumulés → vitesses, puis amortissement.
+fn apply_forces(world: &mut WorldCore, dt: f32) {
+    let gravity = world.gravity;
+    let (rbs, trs, cos, ccs) = world.components.storages_mut::<(RigidBody, Transform, Collider, CharacterController)>();
+    for (id, (rb, tr)) in Query::new((rbs, &*trs)) {
+        if !rb.is_dynamic() || rb.sleeping { continue; }
+
+        rb.velocity += (gravity * rb.gravity_scale + rb.force * rb.inverse_mass()) * dt;
+        if rb.torque != glam::Vec3::ZERO {
+            rb.angular_velocity += inverse_inertia(rb, tr, cos.get(id), ccs.contains(id)) * rb.torque * dt;
+        }
+        rb.velocity         /= 1.0 + rb.linear_damping.max(0.0) * dt;
+        rb.angular_velocity /= 1.0 + rb.angular_damping.max(0.0) * dt;
+
+        // Reset on_ground — rétabli par resolve_static si collision sol détectée
+        rb.on_ground = false;
+    }
+}
+
+/// Forces et couples valent pour tous les pas fixes d'un `update`, puis sont remis à zéro.
+fn clear_forces(world: &mut WorldCore, _dt: f32) {
+    for (_, rb) in Query::new(world.components.storage_mut::<RigidBody>()) {
+        rb.force  = glam::Vec3::ZERO;
+        rb.torque = glam::Vec3::ZERO;
+    }
+}
+
+/// Intégration Euler des positions et rotations, puis transport des corps posés sur un kinematic.
+/// Le déplacement d'un kinematic couvre sa vitesse et ce que les scripts lui ont fait
+/// subir depuis le pas précédent (kinematics et passagers : racines de la hiérarchie).
+fn integrate(world: &mut WorldCore, dt: f32) {
+    let (rbs, trs) = world.components.storages_mut::<(RigidBody, Transform)>();
+    let mut carriers: HashMap<Entity, glam::Vec3> = HashMap::new();
+    for (id, (rb, tr)) in Query::new((&mut *rbs, &mut *trs)) {
+        match rb.body_type {
+            BodyType::Static    => {}
+            BodyType::Dynamic   => {
+                if rb.sleeping { continue; }
+                tr.position += rb.velocity * dt;
+                if rb.angular_velocity != glam::Vec3::ZERO {
+                    tr.rotation = (glam::Quat::from_scaled_axis(rb.angular_velocity * dt) * tr.rotation).normalize();
+                }
+            }
+            BodyType::Kinematic => {
+                let start = rb.settled_position.unwrap_or(tr.position);
+                tr.position += rb.velocity * dt;
+                rb.step_motion      = tr.position - start;
+                rb.settled_position = Some(tr.position);
+                if rb.step_motion != glam::Vec3::ZERO {
+                    carriers.insert(id, rb.step_motion);
+                }
+            }
+        }
+    }
+    if carriers.is_empty() {
+        return;
+    }
+    for (_, (rb, tr)) in Query::new((&*rbs, trs)) {
+        if let Some(motion) = rb.ground.filter(|_| rb.is_dynamic()).and_then(|g| carriers.get(&g)) {
+            tr.position += *motion;
+        }
+    }
+}
+
+/// Statique ou kinematic, figé pendant la résolution.
+struct FixedBody {
+    id:       Entity,
+    shape:    Shape,
+    collider: Collider,
+    material: (f32, f32),   // (restitution, friction)
+    velocity: glam::Vec3,   // déplacement du pas / dt pour un kinematic
+}
+
+/// Statiques et kinematics (hors triggers) posés dans le monde. Statiques : GlobalTransform
+/// (propagé en PostUpdate), recalculée à défaut ; kinematics : viennent de bouger, matrice recalculée.
+fn fixed_bodies(world: &WorldCore, dt: f32) -> Vec<FixedBody> {
+    Query::new((world.components.storage::<RigidBody>(), world.components.storage::<Collider>()))
+        .with(world.components.storage::<Transform>())
+        .into_iter()
+        .filter(|(_, (rb, co))| !rb.is_dynamic() && !co.is_trigger)
+        .map(|(id, (rb, co))| {
+            let (matrix, velocity) = match rb.body_type {
+                BodyType::Kinematic => (world.compute_world_matrix(id), rb.step_motion / dt),
+                _                   => (world.world_matrix(id), glam::Vec3::ZERO),
+            };
+            FixedBody {
+                id,
+                shape:    Shape::from_collider(matrix, co),
+                collider: co.clone(),
+                material: (rb.restitution, rb.friction),
+                velocity,
+            }
+        })
+        .collect()
+}
+
+/// Marches : un personnage au sol qui avance contre un obstacle (statique / kinematic) est
+/// soulevé jusqu'au dessus de la marche, au plus `step_height` ; `resolve_static` l'y repose
+/// ensuite. Un obstacle plus haut l'arrête comme un mur.
+fn step_up(world: &mut WorldCore, dt: f32) {
+    // (id, forme, collider, direction horizontale, hauteur de marche, normale Y min d'un sol)
+    let climbers: Vec<(Entity, Shape, Collider, glam::Vec3, f32, f32)> = Query::new((
+        world.components.storage::<CharacterController>(),
+        world.components.storage::<RigidBody>(),
+        world.components.storage::<Collider>(),
+        world.components.storage::<Transform>(),
+    ))
+        .into_iter()
+        .filter(|(_, (cc, rb, co, _))| {
+            rb.is_dynamic() && !co.is_trigger && !co.shape.is_mesh() && cc.step_height > 0.0 && cc.air_time == 0.0
+        })
+        .filter_map(|(id, (cc, rb, co, tr))| {
+            let heading = glam::Vec3::new(rb.velocity.x, 0.0, rb.velocity.z).try_normalize()?;
+            Some((id, Shape::from_collider(tr.matrix(), co), co.clone(), heading, cc.step_height, cc.min_ground_normal_y()))
+        })
+        .collect();
+    if climbers.is_empty() {
+        return;
+    }
+
+    let fixed = fixed_bodies(world, dt);
+    let tree  = Bvh::build(fixed.iter().map(|f| f.shape.aabb()).collect());
+    let mut candidates = Vec::new();
+    for (id, shape, collider, heading, step_height, ground_y) in climbers {
+        let lifted = |lift: f32| {
+            let mut s = shape.clone();
+            s.translate(glam::Vec3::Y * lift);
+            s
+        };
+        // Le dessus de la marche est cherché un rayon plus loin, centre au-dessus de l'arête.
+        let probe = heading * shape.radius_along(heading);
+        let reach = lifted(step_height).aabb();
+        candidates.clear();
+        tree.query(&shape.aabb().union(reach).union(Aabb::new(reach.min + probe, reach.max + probe)), &mut candidates);
+        candidates.retain(|&i| collider.interacts_with(&fixed[i].collider));
+
+        // Bloqué si un contact qui n'est pas un sol s'oppose au déplacement ou à la montée (plafond).
+        let blocked = |s: &Shape| candidates.iter().any(|&i| {
+            contacts(s, &fixed[i].shape).iter()
+                .any(|c| c.normal.y >= -ground_y && (c.normal.dot(heading) > 0.0 || c.normal.y > 0.0))
+        });
+        if !blocked(&shape) || blocked(&lifted(step_height)) {
+            continue;
+        }
+        // Plus basse hauteur libre à cet endroit : une forme arrondie montée seulement jusqu'à
+        // décoller de l'arête resterait posée dessus, hors du sol, et le contact suivant
+        // transformerait sa vitesse horizontale en saut.
+        let supported = |s: &Shape| candidates.iter().any(|&i| {
+            contacts(s, &fixed[i].shape).iter().any(|c| c.normal.y < 0.0)
+        });
+        let (mut low, mut high) = (0.0, step_height);
+        for _ in 0..STEP_BISECT_ITERS {
+            let mid = (low + high) * 0.5;
+            let mut s = lifted(mid);
+            s.translate(probe);
+            if supported(&s) { low = mid; } else { high = mid; }
+        }
+        let (rbs, trs) = world.components.storages_mut::<(RigidBody, Transform)>();
+        if let (Some(rb), Some(tr)) = (rbs.get_mut(id), trs.get_mut(id)) {
+            tr.position.y += high;
+            rb.velocity.y  = rb.velocity.y.max(0.0);
+            rb.on_ground   = true;  // posé sur la marche : la montée continue au pas suivant
+        }
+    }
+}
+
+/// Détection continue des corps `ccd` : balaie leur collider depuis la position de début de pas
+/// (PreviousTransform) jusqu'à la position intégrée, contre les statiques et kinematics, et les
+/// arrête au premier impact. Les corps trop lents pour traverser un obstacle sont laissés au test discret.
+fn sweep_fast_bodies(world: &mut WorldCore, dt: f32) {
+    // (id, collider, forme en début de pas, direction, longueur du déplacement)
+    let sweeps: Vec<(Entity, Collider, Shape, glam::Vec3, f32)> = Query::new((
+        world.components.storage::<RigidBody>(),
+        world.components.storage::<Collider>(),
+        world.components.storage::<Transform>(),
+        world.components.storage::<PreviousTransform>(),
+    ))
+        .into_iter()
+        .filter(|(_, (rb, co, _, _))| rb.ccd && rb.is_dynamic() && !co.is_trigger && !co.shape.is_mesh())
+        .filter_map(|(id, (_, co, tr, prev))| {
+            let motion = tr.position - prev.0.position;
+            let (dir, length) = (motion.try_normalize()?, motion.length());
+            let mut shape = Shape::from_collider(tr.matrix(), co);
+            if length < shape.radius_along(dir) {
+                return None;
+            }
+            shape.translate(-motion);
+            Some((id, co.clone(), shape, dir, length))
+        })
+        .collect();
+    if sweeps.is_empty() {
+        return;
+    }
+
+    let fixed = fixed_bodies(world, dt);
+    let tree  = Bvh::build(fixed.iter().map(|f| f.shape.aabb()).collect());
+    let mut candidates = Vec::new();
+    for (id, collider, start, dir, length) in sweeps {
+        let mut end = start.clone();
+        end.translate(dir * length);
+        candidates.clear();
+        tree.query(&start.aabb().union(end.aabb()), &mut candidates);
+
+        let impact = candidates.iter()
+            .filter(|&&i| collider.interacts_with(&fixed[i].collider))
+            .filter_map(|&i| shape_cast(&start, dir, length, &fixed[i].shape))
+            .map(|hit| hit.distance)
+            .min_by(f32::total_cmp);
+        if let (Some(distance), Some(tr)) = (impact, world.components.get_mut::<Transform>(id)) {
+            tr.position -= dir * (length - (distance + CCD_SKIN).min(length));
+        }
+    }
+}
+
+/// Séparation dynamiques vs statiques et kinematics (masse infinie) + détection on_ground ;
+/// les impulsions suivent dans `solve_contacts`.
+fn resolve_static(world: &mut WorldCore, dt: f32) {
+    // Statiques et kinematics ne bougent plus pendant la résolution : formes figées.
+    let fixed  = fixed_bodies(world, dt);
+    let tree   = Bvh::build(fixed.iter().map(|f| f.shape.aabb()).collect());
+    let linked = joints::linked_pairs(world);
+
+    let mut candidates = Vec::new();
+    let mut woken = Vec::new();
+    let (rbs, trs, cos, ccs) = world.components.storages_mut::<(RigidBody, Transform, Collider, CharacterController)>();
+    for (id, (rb, tr, co)) in Query::new((rbs, trs, &*cos)) {
+        if !rb.is_dynamic() || co.is_trigger || co.shape.is_mesh() { continue; }
+        // Corps dynamiques : racines de la hiérarchie, local = monde.
+        let mut shape = Shape::from_collider(tr.matrix(), co);
+        let ground_y  = ccs.get(id).map_or(GROUND_NORMAL_Y, CharacterController::min_ground_normal_y);
+
+        candidates.clear();
+        tree.query(&shape.aabb().expand(BROADPHASE_MARGIN), &mut candidates);
+        candidates.sort_unstable();  // ordre de résolution déterministe
+
+        // Un corps endormi ne teste que les kinematics en mouvement, qui le réveillent.
+        if rb.sleeping {
+            let pushed = candidates.iter().any(|&i| {
+                let body = &fixed[i];
+                body.velocity != glam::Vec3::ZERO && co.interacts_with(&body.collider) && contact(&shape, &body.shape).is_some()
+            });
+            if !pushed { continue; }
+            rb.sleeping = false;
+            woken.push(id);
+        }
+        rb.ground = None;
+
+        for &i in &candidates {
+            let body = &fixed[i];
+            if !co.interacts_with(&body.collider) || linked.contains(&(id, body.id)) { continue; }
+            // Un maillage peut être touché par plusieurs triangles à la fois (marche d'escalier) :
+            // résoudre le plus profond, puis recommencer.
+            let passes = if body.shape.is_mesh() { MESH_CONTACT_PASSES } else { 1 };
+            for _ in 0..passes {
+                let Some(contact) = contact(&shape, &body.shape) else { break };
+                world.contacts.record(id, body.id, false);
+
+                // Corriger position : reculer le long de la normale
+                let push = contact.normal * contact.depth;
+                shape.translate(-push);
+                tr.position -= push;
+
+                // Impulsion contre un corps de masse infinie + détecter on_ground
+                // (normale vers le bas : l'obstacle est en dessous). Sur le sol, le transport
+                // (integrate) suit déjà un kinematic : la vitesse du corps reste relative à lui.
+                let is_ground = contact.normal.y < -ground_y;
+                let (restitution, friction) = mix_material((rb.restitution, rb.friction), body.material);
+                world.pending_contacts.push(PendingContact {
+                    a: id,
+                    b: None,
+                    obstacle: if is_ground { glam::Vec3::ZERO } else { body.velocity },
+                    contact, restitution, friction,
+                });
+                if is_ground {
+                    rb.on_ground = true;
+                    rb.ground    = Some(body.id);
+                }
+            }
+        }
+    }
+    for id in woken {
+        world.wake_up(id);
+    }
+}
+
+/// Séparation dynamiques vs dynamiques, répartie selon les masses ; les impulsions suivent
+/// dans `solve_contacts`.
+fn resolve_dynamic(world: &mut WorldCore, _dt: f32) {
+    // Paires (i, j) : les deux corps sont mutés, on garde une liste d'ids indexable.
+    let dynamic_ids: Vec<Entity> = Query::new(world.components.storage::<RigidBody>())
+        .with(world.components.storage::<Transform>())
+        .with(world.components.storage::<Collider>())
+        .into_iter()
+        .filter(|(id, rb)| {
+            rb.is_dynamic()
+                && world.components.get::<Collider>(*id).is_some_and(|co| !co.is_trigger && !co.shape.is_mesh())
+        })
+        .map(|(id, _)| id)
+        .collect();
+
+    let shape_of = |world: &WorldCore, id: Entity| -> Option<(Shape, Collider)> {
+        let tr = world.components.get::<Transform>(id)?;
+        let co = world.components.get::<Collider>(id)?;
+        Some((Shape::from_collider(tr.matrix(), co), co.clone()))
+    };
+
+    // Paires candidates d'après les positions en début de passe.
+    let linked = joints::linked_pairs(world);
+    let aabbs: Vec<Aabb> = dynamic_ids.iter()
+        .map(|&id| shape_of(world, id).map_or(Aabb::EMPTY, |(s, _)| s.aabb().expand(BROADPHASE_MARGIN)))
+        .collect();
+    for (i, j) in Bvh::build(aabbs).overlapping_pairs() {
+        let a_id = dynamic_ids[i];
+        let b_id = dynamic_ids[j];
+        // Deux dormeurs : contact figé (cf. ContactTracker::sleep). Un dormeur touché se réveille.
+        let (a_asleep, b_asleep) = (world.is_sleeping(a_id), world.is_sleeping(b_id));
+        if a_asleep && b_asleep { continue; }
+
+        let (Some((a, a_co)), Some((b, b_co))) = (shape_of(world, a_id), shape_of(world, b_id)) else { continue };
+        if !a_co.interacts_with(&b_co) || linked.contains(&(a_id, b_id)) { continue; }
+        let Some(contact) = contact(&a, &b) else { continue };
+        if a_asleep { world.wake_up(a_id); }
+        if b_asleep { world.wake_up(b_id); }
+        world.contacts.record(a_id, b_id, false);
+
+        let rbs = world.components.storage::<RigidBody>();
+        let (Some(rb_a), Some(rb_b)) = (rbs.get(a_id), rbs.get(b_id)) else { continue };
+        let (inv_a, inv_b) = (rb_a.inverse_mass(), rb_b.inverse_mass());
+        let inv_sum = inv_a + inv_b;
+        let (restitution, friction) = mix_material((rb_a.restitution, rb_a.friction), (rb_b.restitution, rb_b.friction));
+
+        // Le plus léger recule le plus.
+        let push = contact.normal * (contact.depth / inv_sum);
+        if let Some(tr) = world.components.get_mut::<Transform>(a_id) {
+            tr.position -= push * inv_a;
+        }
+        if let Some(tr) = world.components.get_mut::<Transform>(b_id) {
+            tr.position += push * inv_b;
+        }
+        world.pending_contacts.push(PendingContact {
+            a: a_id,
+            b: Some(b_id),
+            obstacle: glam::Vec3::ZERO,
+            contact, restitution, friction,
+        });
+    }
+}
+
+/// Impulsions des contacts du pas (`PendingContact`), en `SOLVER_ITERATIONS` passes sur
+/// l'ensemble : chaque contact tient compte de ce que les autres ont déjà transmis.
+fn solve_contacts(world: &mut WorldCore, _dt: f32) {
+    let pending = std::mem::take(&mut world.pending_contacts);
+    if pending.is_empty() {
+        return;
+    }
+
+    // Un `ContactBody` par dynamique touché, un par obstacle.
+    let mut bodies: Vec<ContactBody> = Vec::new();
+    let mut index:  HashMap<Entity, usize> = HashMap::new();
+    let mut constraints = Vec::with_capacity(pending.len());
+    for p in &pending {
+        let Some(a) = body_index(world, &mut bodies, &mut index, p.a) else { continue };
+        let b = match p.b {
+            Some(id) => match body_index(world, &mut bodies, &mut index, id) {
+                Some(b) => b,
+                None    => continue,
+            },
+            None => {
+                bodies.push(ContactBody::fixed(p.obstacle));
+                bodies.len() - 1
+            }
+        };
+        constraints.push(ContactConstraint::new(a, b, p, &bodies));
+    }
+
+    for _ in 0..SOLVER_ITERATIONS {
+        for constraint in &mut constraints {
+            constraint.solve(&mut bodies);
+        }
+    }
+    for (id, i) in index {
+        if let Some(rb) = world.components.get_mut::<RigidBody>(id) {
+            rb.velocity         = bodies[i].velocity;
+            rb.angular_velocity = bodies[i].angular_velocity;
+        }
+    }
+}
+
+/// Endort les îlots au repos : corps dynamiques reliés par des contacts ou des joints, endormis
+/// ensemble quand tous sont restés sous les seuils de vitesse pendant `SLEEP_DELAY`. Les personnages,
+/// et les corps posés contre un kinematic en mouvement, ne dorment pas et gardent leur îlot éveillé.
+fn update_sleep(world: &mut WorldCore, dt: f32) {
+    let rbs = world.components.storage::<RigidBody>();
+    let moving_kinematic = |id: Entity| {
+        rbs.get(id).is_some_and(|rb| rb.body_type == BodyType::Kinematic && rb.step_motion != glam::Vec3::ZERO)
+    };
+
+    // Liens du pas : contacts solides et joints.
+    let links: Vec<(Entity, Entity)> = world.contacts.touching()
+        .chain(joints::linked_pairs(world))
+        .collect();
+    let mut restless: HashSet<Entity> = world.components.storage::<CharacterController>().iter().map(|(id, _)| id).collect();
+    for &(a, b) in &links {
+        if moving_kinematic(a) { restless.insert(b); }
+        if moving_kinematic(b) { restless.insert(a); }
+    }
+
+    let ids: Vec<Entity> = rbs.iter().filter(|(_, rb)| rb.is_dynamic() && !rb.sleeping).map(|(id, _)| id).collect();
+    let index: HashMap<Entity, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    let mut parent: Vec<usize> = (0..ids.len()).collect();
+    for (a, b) in links {
+        if let (Some(&i), Some(&j)) = (index.get(&a), index.get(&b)) {
+            let (ri, rj) = (island_root(&mut parent, i), island_root(&mut parent, j));
+            parent[ri] = rj;
+        }
+    }
+
+    // Temps de repos de chaque corps, puis le plus court de chaque îlot.
+    let rbs = world.components.storage_mut::<RigidBody>();
+    let mut island_rest: HashMap<usize, f32> = HashMap::new();
+    for (i, &id) in ids.iter().enumerate() {
+        let Some(rb) = rbs.get_mut(id) else { continue };
+        let still = rb.velocity.length() < SLEEP_LINEAR_SPEED && rb.angular_velocity.length() < SLEEP_ANGULAR_SPEED;
+        rb.sleep_timer = if still && !restless.contains(&id) { rb.sleep_timer + dt } else { 0.0 };
+        let rest = island_rest.entry(island_root(&mut parent, i)).or_insert(f32::INFINITY);
+        *rest = rest.min(rb.sleep_timer);
+    }
+
+    let mut sleepers: HashSet<Entity> = HashSet::new();
+    for (i, &id) in ids.iter().enumerate() {
+        let root = island_root(&mut parent, i);
+        if island_rest[&root] < SLEEP_DELAY { continue; }
+        if let Some(rb) = rbs.get_mut(id) {
+            rb.sleeping         = true;
+            rb.island           = Some(ids[root]);
+            rb.velocity         = glam::Vec3::ZERO;
+            rb.angular_velocity = glam::Vec3::ZERO;
+        }
+        sleepers.insert(id);
+    }
+    if !sleepers.is_empty() {
+        world.contacts.sleep(&sleepers);
+    }
+}
+
+/// Racine de l'îlot de `i` (union-find), en raccourcissant le chemin parcouru.
+fn island_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Indice du `ContactBody` de la dynamique `id`, créé au premier contact ; None si `id` a perdu son corps.
+fn body_index(world: &WorldCore, bodies: &mut Vec<ContactBody>, index: &mut HashMap<Entity, usize>, id: Entity) -> Option<usize> {
+    if let Some(&i) = index.get(&id) {
+        return Some(i);
+    }
+    let rb = world.components.get::<RigidBody>(id)?;
+    let tr = world.components.get::<Transform>(id)?;
+    let is_character = world.components.get::<CharacterController>(id).is_some();
+    bodies.push(ContactBody::dynamic(rb, tr, world.components.get::<Collider>(id), is_character));
+    index.insert(id, bodies.len() - 1);
+    Some(bodies.len() - 1)
+}
+
+/// Chevauchements impliquant un trigger et au moins un corps mobile (sans résolution).
+fn detect_triggers(world: &mut WorldCore, _dt: f32) {
+    let colliders = world.components.storage::<Collider>();
+    if !colliders.iter().any(|(_, co)| co.is_trigger) {
+        return;
+    }
+    let rbs = world.components.storage::<RigidBody>();
+    let moves = |id: Entity| rbs.get(id).is_some_and(|rb| rb.body_type != BodyType::Static);
+
+    // (id, forme, collider, mobile ?) de tous les colliders posés dans le monde.
+    let bodies: Vec<(Entity, Shape, Collider, bool)> = Query::new(colliders)
+        .with(world.components.storage::<Transform>())
+        .into_iter()
+        .map(|(id, co)| {
+            let moving = moves(id);
+            // Les corps mobiles viennent de bouger : leur GlobalTransform est périmé.
+            let matrix = if moving { world.compute_world_matrix(id) } else { world.world_matrix(id) };
+            (id, Shape::from_collider(matrix, co), co.clone(), moving)
+        })
+        .collect();
+    let tree = Bvh::build(bodies.iter().map(|(_, s, _, _)| s.aabb()).collect());
+
+    let mut overlaps = Vec::new();
+    let mut candidates = Vec::new();
+    for (i, &(a_id, ref a, ref a_co, a_moving)) in bodies.iter().enumerate() {
+        if !a_co.is_trigger { continue; }
+        candidates.clear();
+        tree.query(&a.aabb(), &mut candidates);
+        for &j in &candidates {
+            let (b_id, ref b, ref b_co, b_moving) = bodies[j];
+            if i == j || !(a_moving || b_moving) || !a_co.interacts_with(b_co) { continue; }
+            if contact(a, b).is_some() {
+                overlaps.push((a_id, b_id));
+            }
+        }
+    }
+    for (a, b) in overlaps {
+        world.contacts.record(a, b, true);
+    }
+}
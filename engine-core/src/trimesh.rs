@@ -0,0 +1,108 @@
+//! Maillage de collision : triangles d'un mesh custom en espace local + BVH.
+//!
+//! Construit une fois par mesh (`WorldCore::add_mesh_collider`) puis partagé par
+//! tous les colliders qui l'utilisent. Les requêtes se font en espace local ; la
+//! narrow-phase (`collision.rs`) pose ensuite les triangles candidats en espace monde.
+
+use std::fmt;
+
+use glam::Vec3;
+
+use crate::broadphase::{Aabb, Bvh};
+
+/// Sous cette aire (×2), un triangle est dégénéré et ignoré.
+const DEGENERATE_EPS: f32 = 1e-12;
+
+pub struct TriMesh {
+    triangles: Vec<[Vec3; 3]>,
+    bvh:       Bvh,
+    bounds:    Aabb,
+}
+
+impl TriMesh {
+    /// Triangles `indices` (par trois) sur `positions`. Indices hors limites et triangles
+    /// dégénérés ignorés ; None s'il ne reste aucun triangle.
+    pub(crate) fn new(positions: &[Vec3], indices: &[u32]) -> Option<Self> {
+        let triangles: Vec<[Vec3; 3]> = indices.chunks_exact(3)
+            .filter_map(|t| Some([
+                *positions.get(t[0] as usize)?,
+                *positions.get(t[1] as usize)?,
+                *positions.get(t[2] as usize)?,
+            ]))
+            .filter(|[a, b, c]| (*b - *a).cross(*c - *a).length_squared() > DEGENERATE_EPS)
+            .collect();
+        if triangles.is_empty() {
+            return None;
+        }
+        let aabbs: Vec<Aabb> = triangles.iter()
+            .map(|[a, b, c]| Aabb::new(a.min(*b).min(*c), a.max(*b).max(*c)))
+            .collect();
+        let bounds = aabbs.iter().fold(Aabb::EMPTY, |acc, b| acc.union(*b));
+        Some(TriMesh { triangles, bvh: Bvh::build(aabbs), bounds })
+    }
+
+    /// Boîte englobante locale.
+    pub(crate) fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub(crate) fn triangle(&self, index: usize) -> [Vec3; 3] {
+        self.triangles[index]
+    }
+
+    /// Ajoute à `out` les triangles dont la boîte chevauche `local` (espace local).
+    pub(crate) fn query(&self, local: &Aabb, out: &mut Vec<usize>) {
+        self.bvh.query(local, out);
+    }
+
+    /// Premier triangle touché par le rayon local `origin + t * dir`, 0 ≤ t ≤ `max_t`
+    /// (deux faces) : (t, indice du triangle).
+    pub(crate) fn ray(&self, origin: Vec3, dir: Vec3, max_t: f32) -> Option<(f32, usize)> {
+        let mut candidates = Vec::new();
+        self.bvh.query_ray(origin, dir, max_t, &mut candidates);
+        candidates.into_iter()
+            .filter_map(|i| ray_triangle(origin, dir, self.triangles[i]).map(|t| (t, i)))
+            .filter(|&(t, _)| t <= max_t)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+    }
+}
+
+/// Identité : deux colliders sont égaux s'ils partagent le même maillage.
+impl PartialEq for TriMesh {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for TriMesh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TriMesh").field("triangles", &self.triangles.len()).finish()
+    }
+}
+
+/// Rayon / triangle (Möller–Trumbore, deux faces) : t ≥ 0 de l'impact.
+pub(crate) fn ray_triangle(origin: Vec3, dir: Vec3, [a, b, c]: [Vec3; 3]) -> Option<f32> {
+    let (e1, e2) = (b - a, c - a);
+    let p   = dir.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < DEGENERATE_EPS {
+        return None;
+    }
+    let inv = 1.0 / det;
+    let s = origin - a;
+    let u = s.dot(p) * inv;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = dir.dot(q) * inv;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = e2.dot(q) * inv;
+    (t >= 0.0).then_some(t)
+}
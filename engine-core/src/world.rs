@@ -0,0 +1,2156 @@
+use crate::camera::Camera;
+use crate::ecs::{BodyType, CameraComponent, Entity, Joint, Material, MeshRenderer, MeshType, PointLight, Query, SparseSet, Transform};
+use crate::mesh::{Vertex, CUBE_INDICES, CUBE_VERTICES, PLANE_INDICES, PLANE_VERTICES};
+use crate::raycast::RayHit;
+use crate::schedule::Stage;
+use crate::world_core::WorldCore;
+
+use wasm_bindgen::prelude::*;
+use web_sys::HtmlCanvasElement;
+use wgpu::util::DeviceExt;
+
+struct EntityGpu {
+    uniform_buffer:        wgpu::Buffer,   // EntityUniforms (144 bytes) — Group 0
+    bind_group:            wgpu::BindGroup,
+    shadow_uniform_buffer: wgpu::Buffer,   // ShadowUniforms (64 bytes) — shadow pass
+    shadow_bind_group:     wgpu::BindGroup,
+}
+
+/// Ressources GPU pour une texture chargee.
+struct TextureGpu {
+    #[allow(dead_code)]
+    texture: wgpu::Texture,
+    view:    wgpu::TextureView,
+}
+
+struct CustomMeshGpu {
+    vertex_buffer:      wgpu::Buffer,
+    index_buffer:       wgpu::Buffer,
+    index_count:        u32,
+}
+
+
+
+// ── Types GPU pour l'éclairage ────────────────────────────────────────────
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct EntityUniforms {
+    mvp:       [[f32; 4]; 4], // 64
+    model:     [[f32; 4]; 4], // 64
+    metallic:  f32,           // 4
+    roughness: f32,           // 4
+    _pad1:     [f32; 2],      // 8
+    scale:     [f32; 4],      // 16
+    emissive:  [f32; 3],      // 12 (NOUVEAU)
+    _pad2:     f32,           // 4  (Padding final pour alignement 16 bytes)
+}
+// Total : 176 bytes
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuDirectionalLight {
+    direction:     [f32; 3], _p0: f32,
+    color:         [f32; 3], intensity: f32,
+    position:      [f32; 3], _p1: f32,
+    cone_cos_outer: f32, cone_cos_inner: f32, _p2: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuPointLight {
+    position:  [f32; 3], _p0: f32,
+    color:     [f32; 3], intensity: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct LightUniforms {
+    camera_pos:      [f32; 4],            //  16 bytes — offset   0
+    directional:     GpuDirectionalLight, //  64 bytes — offset  16
+    n_points:        u32,                 //   4 bytes — offset  48
+    _pad:            [u32; 3],            //  12 bytes — offset  52
+    points:          [GpuPointLight; 8],  // 256 bytes — offset  64
+    light_space_mat: [[f32; 4]; 4],       //  64 bytes — offset 320
+    ambient_color:   [f32; 4],            //  16 bytes — offset 384
+}
+// Total : 400 bytes
+
+/// Monde wasm : enveloppe `WorldCore` (ECS, physique, scènes) avec le renderer WebGPU.
+#[wasm_bindgen]
+pub struct World {
+    core: WorldCore,
+
+    device:  wgpu::Device,
+    queue:   wgpu::Queue,
+    surface: wgpu::Surface<'static>,
+    config:  wgpu::SurfaceConfiguration,
+    depth_texture: wgpu::Texture,
+    depth_view:    wgpu::TextureView,
+    render_pipeline:   wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    cube_vertex_buffer:  wgpu::Buffer,
+    cube_index_buffer:   wgpu::Buffer,
+    plane_vertex_buffer: wgpu::Buffer,
+    plane_index_buffer:  wgpu::Buffer,
+    entity_gpus:    SparseSet<EntityGpu>,
+    camera: Camera,
+
+    // Textures
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    sampler:                   wgpu::Sampler,
+    default_tex:               TextureGpu,
+    default_normal_tex:        TextureGpu,
+    textures:                  Vec<TextureGpu>,
+
+    // Éclairage
+    light_bind_group_layout: wgpu::BindGroupLayout,
+    light_buffer:            wgpu::Buffer,
+    light_bind_group:        wgpu::BindGroup,
+
+    // Shadow map
+    shadow_depth_texture:     wgpu::Texture,
+    shadow_depth_view:        wgpu::TextureView,
+    shadow_bind_group_layout: wgpu::BindGroupLayout,
+    shadow_bind_group:        wgpu::BindGroup,
+    shadow_pipeline:          wgpu::RenderPipeline,
+    shadow_entity_layout:     wgpu::BindGroupLayout,
+
+    // Meshes custom (index = WorldCore::custom_meshes)
+    custom_meshes: Vec<CustomMeshGpu>,
+
+    // Sphere / Cylinder built-in meshes
+    sphere_vbuf:       wgpu::Buffer,
+    sphere_ibuf:       wgpu::Buffer,
+    sphere_ilen:       u32,
+    cylinder_vbuf:     wgpu::Buffer,
+    cylinder_ibuf:     wgpu::Buffer,
+    cylinder_ilen:     u32,
+
+    // Ambient light
+    ambient_color:     glam::Vec3,
+    ambient_intensity: f32,
+
+    // Inset preview depth buffer (separate from main depth, sized at ~1/4 canvas)
+    inset_depth_texture: wgpu::Texture,
+    inset_depth_view:    wgpu::TextureView,
+    inset_w: u32,
+    inset_h: u32,
+}
+
+fn create_depth_texture(
+    device: &wgpu::Device,
+    config: &wgpu::SurfaceConfiguration,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    create_depth_texture_wh(device, config.width, config.height)
+}
+
+fn create_depth_texture_wh(
+    device: &wgpu::Device,
+    width:  u32,
+    height: u32,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("depth_texture"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count:    1,
+        dimension:       wgpu::TextureDimension::D2,
+        format:          wgpu::TextureFormat::Depth32Float,
+        usage:           wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats:    &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+/// Cree une TextureGpu depuis des donnees RGBA brutes.
+fn create_texture_from_data(
+    device: &wgpu::Device,
+    queue:  &wgpu::Queue,
+    width:  u32,
+    height: u32,
+    data:   &[u8],
+    generate_mipmaps: bool, // <- NOUVEAU PARAMÈTRE
+) -> TextureGpu {
+    // 1. Calculer le nombre de niveaux de mipmaps requis
+    let mip_level_count = if generate_mipmaps {
+        width.max(height).ilog2() + 1
+    } else {
+        1
+    };
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("tex"),
+        size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        mip_level_count,
+        sample_count:    1,
+        dimension:       wgpu::TextureDimension::D2,
+        format:          wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage:           wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats:    &[],
+    });
+
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture, mip_level: 0,
+            origin:  wgpu::Origin3d::ZERO,
+            aspect:  wgpu::TextureAspect::All,
+        },
+        data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0, bytes_per_row: Some(4 * width), rows_per_image: None,
+        },
+        wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+    );
+
+    // Génération des mipmaps seulement si demandée
+    if generate_mipmaps {
+        let mut current_data = data.to_vec();
+        let mut w = width;
+        let mut h = height;
+
+        for level in 1..mip_level_count {
+            let next_w = w.max(2) / 2;
+            let next_h = h.max(2) / 2;
+            let mut next_data = vec![0u8; (next_w * next_h * 4) as usize];
+
+            for y in 0..next_h {
+                for x in 0..next_w {
+                    let mut r = 0u32;
+                    let mut g = 0u32;
+                    let mut b = 0u32;
+                    let mut a = 0u32;
+
+                    for dy in 0..2 {
+                        for dx in 0..2 {
+                            let sx = (x * 2 + dx).min(w - 1);
+                            let sy = (y * 2 + dy).min(h - 1);
+                            let idx = ((sy * w + sx) * 4) as usize;
+                            r += current_data[idx] as u32;
+                            g += current_data[idx + 1] as u32;
+                            b += current_data[idx + 2] as u32;
+                            a += current_data[idx + 3] as u32;
+                        }
+                    }
+
+                    let dst_idx = ((y * next_w + x) * 4) as usize;
+                    next_data[dst_idx]     = (r / 4) as u8;
+                    next_data[dst_idx + 1] = (g / 4) as u8;
+                    next_data[dst_idx + 2] = (b / 4) as u8;
+                    next_data[dst_idx + 3] = (a / 4) as u8;
+                }
+            }
+
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture, mip_level: level,
+                    origin:  wgpu::Origin3d::ZERO,
+                    aspect:  wgpu::TextureAspect::All,
+                },
+                &next_data,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0, bytes_per_row: Some(4 * next_w), rows_per_image: None,
+                },
+                wgpu::Extent3d { width: next_w, height: next_h, depth_or_array_layers: 1 },
+            );
+
+            current_data = next_data;
+            w = next_w;
+            h = next_h;
+        }
+    }
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    TextureGpu { texture, view }
+}
+
+/// Calcule la matrice light-space pour la shadow map spotlight.
+/// Projection perspective depuis `light_pos` dans `direction` (cône 60°).
+fn compute_light_space_mat(direction: glam::Vec3, light_pos: glam::Vec3) -> glam::Mat4 {
+    let dir = direction.normalize();
+    // Vecteur up : évite le gimbal lock quand la lampe pointe vers le bas
+    let up = if dir.y.abs() > 0.99 { glam::Vec3::X } else { glam::Vec3::Y };
+    let view = glam::Mat4::look_at_rh(light_pos, light_pos + dir, up);
+    let proj = glam::Mat4::perspective_rh(
+        60.0_f32.to_radians(), // 60° FOV = cône 30° demi-angle
+        1.0,                   // shadow map carrée
+        0.1, 200.0,
+    );
+    proj * view
+}
+
+#[wasm_bindgen]
+impl World {
+    pub async fn new(canvas: HtmlCanvasElement) -> Result<World, JsValue> {
+        console_error_panic_hook::set_once();
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::BROWSER_WEBGPU,
+            ..Default::default()
+        });
+
+        let width  = canvas.width();
+        let height = canvas.height();
+        let surface = instance
+            .create_surface(wgpu::SurfaceTarget::Canvas(canvas))
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference:       wgpu::PowerPreference::default(),
+                compatible_surface:     Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await
+            .map_err(|e| JsValue::from_str(&format!("{e:?}")))?;
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default())
+            .await
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let format = surface_caps
+            .formats
+            .first()
+            .copied()
+            .ok_or_else(|| JsValue::from_str("Aucun format de surface supporté"))?;
+
+        let config = wgpu::SurfaceConfiguration {
+            usage:                         wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width,
+            height,
+            present_mode:                  wgpu::PresentMode::Fifo,
+            alpha_mode:                    wgpu::CompositeAlphaMode::Opaque,
+            view_formats:                  vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&device, &config);
+
+        let (depth_texture, depth_view) = create_depth_texture(&device, &config);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label:  Some("shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding:    0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty:                 wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size:   None,
+                },
+                count: None,
+            }],
+        });
+
+        // Texture bind group layout (Group 1) : albedo + sampler + normal + sampler
+        let texture_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("texture_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding:    0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type:    wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled:   false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding:    1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Normal map texture
+                wgpu::BindGroupLayoutEntry {
+                    binding:    2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type:    wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled:   false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding:    3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        // Sampler partage (linear, repeat)
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label:          Some("texture_sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat, // Important pour la répétition
+            address_mode_v: wgpu::AddressMode::Repeat, // Important pour la répétition
+            address_mode_w: wgpu::AddressMode::Repeat,
+            mag_filter:     wgpu::FilterMode::Linear,
+            min_filter:     wgpu::FilterMode::Linear,
+            mipmap_filter:  wgpu::MipmapFilterMode::Linear, // Correction du type ici
+            ..Default::default()
+        });
+
+        // Texture blanche 1x1 par defaut
+        let default_tex = create_texture_from_data(&device, &queue, 1, 1, &[255u8, 255, 255, 255], false);
+        // Flat normal : (128, 128, 255, 255) = vecteur (0,0,1) en tangent space
+        let default_normal_tex = create_texture_from_data(&device, &queue, 1, 1, &[128u8, 128, 255, 255], false);
+
+        // ── Light bind group layout (Group 2) ────────────────────────────────────
+        let light_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("light_bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding:    0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty:                 wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size:   None,
+                },
+                count: None,
+            }],
+        });
+
+        let light_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label:              Some("light_buffer"),
+            size:               std::mem::size_of::<LightUniforms>() as u64,
+            // Round up to next multiple of 16 for WebGPU uniform buffer alignment.
+            // LightUniforms is 432 bytes which is already a multiple of 16.
+            usage:              wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let light_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label:   Some("light_bind_group"),
+            layout:  &light_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding:  0,
+                resource: light_buffer.as_entire_binding(),
+            }],
+        });
+
+        // ── Shadow map infrastructure ─────────────────────────────────────────
+        let shadow_size = wgpu::Extent3d { width: 2048, height: 2048, depth_or_array_layers: 1 };
+        let shadow_depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("shadow_depth"),
+            size: shadow_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let shadow_depth_view = shadow_depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Bind group layout Group 3 : shadow_map (depth) + comparison sampler
+        let shadow_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("shadow_bgl"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type:    wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled:   false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                    count: None,
+                },
+            ],
+        });
+
+        let shadow_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label:          Some("shadow_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            compare:        Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        });
+
+        let shadow_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label:   Some("shadow_bg"),
+            layout:  &shadow_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding:  0,
+                    resource: wgpu::BindingResource::TextureView(&shadow_depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding:  1,
+                    resource: wgpu::BindingResource::Sampler(&shadow_sampler),
+                },
+            ],
+        });
+
+        // Shadow pipeline — bind group 0 : light_mvp uniform per entity
+        let shadow_entity_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("shadow_entity_bgl"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding:    0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty:                 wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size:   None,
+                },
+                count: None,
+            }],
+        });
+
+        let shadow_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label:  Some("shadow_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shadow.wgsl").into()),
+        });
+
+        let shadow_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label:              Some("shadow_pipeline_layout"),
+            bind_group_layouts: &[&shadow_entity_layout],
+            ..Default::default()
+        });
+
+        let shadow_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label:  Some("shadow_pipeline"),
+            layout: Some(&shadow_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module:      &shadow_shader,
+                entry_point: Some("vs_shadow"),
+                buffers:     &[Vertex::desc()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: None,
+            primitive: wgpu::PrimitiveState {
+                topology:  wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format:              wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare:       wgpu::CompareFunction::LessEqual,
+                stencil:             wgpu::StencilState::default(),
+                bias:                wgpu::DepthBiasState {
+                    constant:    2,
+                    slope_scale: 2.0,
+                    clamp:       0.0,
+                },
+            }),
+            multisample:    wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache:          None,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label:              Some("pipeline_layout"),
+            bind_group_layouts: &[
+                &bind_group_layout,
+                &texture_bind_group_layout,
+                &light_bind_group_layout,
+                &shadow_bind_group_layout,
+            ],
+            ..Default::default()
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label:  Some("render_pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module:      &shader,
+                entry_point: Some("vs_main"),
+                buffers:     &[Vertex::desc()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module:      &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend:      Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology:           wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face:         wgpu::FrontFace::Ccw,
+                cull_mode:          Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format:              wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare:       wgpu::CompareFunction::Less,
+                stencil:             wgpu::StencilState::default(),
+                bias:                wgpu::DepthBiasState::default(),
+            }),
+            multisample:    wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache:          None,
+        });
+
+        let cube_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label:    Some("cube_vertex_buffer"),
+            contents: bytemuck::cast_slice(CUBE_VERTICES),
+            usage:    wgpu::BufferUsages::VERTEX,
+        });
+
+        let cube_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label:    Some("cube_index_buffer"),
+            contents: bytemuck::cast_slice(CUBE_INDICES),
+            usage:    wgpu::BufferUsages::INDEX,
+        });
+
+        let plane_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label:    Some("plane_vertex_buffer"),
+            contents: bytemuck::cast_slice(PLANE_VERTICES),
+            usage:    wgpu::BufferUsages::VERTEX,
+        });
+
+        let plane_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label:    Some("plane_index_buffer"),
+            contents: bytemuck::cast_slice(PLANE_INDICES),
+            usage:    wgpu::BufferUsages::INDEX,
+        });
+
+        use crate::mesh::{generate_sphere, generate_cylinder};
+        let (sv, si) = generate_sphere(16);
+        let sphere_vbuf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("sphere_vbuf"), contents: bytemuck::cast_slice(&sv), usage: wgpu::BufferUsages::VERTEX,
+        });
+        let sphere_ibuf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("sphere_ibuf"), contents: bytemuck::cast_slice(&si), usage: wgpu::BufferUsages::INDEX,
+        });
+        let sphere_ilen = si.len() as u32;
+        let (cv, ci) = generate_cylinder(16);
+        let cylinder_vbuf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("cyl_vbuf"), contents: bytemuck::cast_slice(&cv), usage: wgpu::BufferUsages::VERTEX,
+        });
+        let cylinder_ibuf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("cyl_ibuf"), contents: bytemuck::cast_slice(&ci), usage: wgpu::BufferUsages::INDEX,
+        });
+        let cylinder_ilen = ci.len() as u32;
+
+        web_sys::console::log_1(&"[World] Pipeline 3D initialisée".into());
+
+        // Pre-compute inset preview depth texture before config/device are moved into World.
+        let inset_w = (config.width / 4).max(1);
+        let inset_h = ((inset_w as f32 * 9.0 / 16.0) as u32).max(1);
+        let (inset_depth_texture, inset_depth_view) = create_depth_texture_wh(&device, inset_w, inset_h);
+
+        Ok(World {
+            core: WorldCore::new(),
+            device,
+            queue,
+            surface,
+            config,
+            depth_texture,
+            depth_view,
+            render_pipeline,
+            bind_group_layout,
+            cube_vertex_buffer,
+            cube_index_buffer,
+            plane_vertex_buffer,
+            plane_index_buffer,
+            entity_gpus:    SparseSet::new(),
+            camera:         Camera::default(),
+            texture_bind_group_layout,
+            sampler,
+            default_tex,
+            default_normal_tex,
+            textures:  Vec::new(),
+            light_bind_group_layout,
+            light_buffer,
+            light_bind_group,
+            shadow_depth_texture,
+            shadow_depth_view,
+            shadow_bind_group_layout,
+            shadow_bind_group,
+            shadow_pipeline,
+            shadow_entity_layout,
+            custom_meshes:       Vec::new(),
+            sphere_vbuf, sphere_ibuf, sphere_ilen,
+            cylinder_vbuf, cylinder_ibuf, cylinder_ilen,
+            ambient_color: glam::Vec3::new(0.1, 0.1, 0.1),
+            ambient_intensity: 0.5,
+            inset_w,
+            inset_h,
+            inset_depth_texture,
+            inset_depth_view,
+        })
+    }
+}
+#[wasm_bindgen]
+impl World {
+    // ── Entités ──────────────────────────────────────────────────────────────
+
+    /// Crée une entité vide. Retourne son handle packé (index + génération, cf. `Entity::to_bits`).
+    pub fn create_entity(&mut self) -> u32 {
+        self.core.create_entity().to_bits()
+    }
+
+    /// Nombre d'entités vivantes.
+    pub fn live_entity_count(&self) -> u32 {
+        self.core.live_entity_count() as u32
+    }
+
+    /// Nombre de slots d'entités morts (en attente de recyclage ou retirés).
+    pub fn dead_entity_count(&self) -> u32 {
+        self.core.dead_entity_count() as u32
+    }
+
+    // ── API Éditeur ───────────────────────────────────────────────────────────
+
+    /// Retourne le nom de l'entité (défaut: "Entity {id}").
+    pub fn get_entity_name(&self, id: u32) -> Result<String, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.get_entity_name(id))
+    }
+
+    /// Définit le nom d'une entité.
+    pub fn set_entity_name(&mut self, id: u32, name: String) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_entity_name(id, name);
+        Ok(())
+    }
+
+    // ── Tags ──────────────────────────────────────────────────────────────────
+
+    /// Assigne un tag string à une entité. Remplace le tag précédent s'il en avait un.
+    pub fn set_tag(&mut self, id: u32, tag: &str) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_tag(id, tag);
+        Ok(())
+    }
+
+    /// Retourne le premier ID d'entité ayant ce tag, ou u32::MAX si aucun.
+    pub fn get_entity_by_tag(&self, tag: &str) -> u32 {
+        self.core.get_entity_by_tag(tag)
+            .map(Entity::to_bits)
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Retourne le tag d'une entité ("" si aucun tag assigné).
+    pub fn get_tag(&self, id: u32) -> Result<String, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.get_tag(id))
+    }
+
+    /// Supprime une entité et tous ses composants.
+    pub fn remove_entity(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.remove_entity(id);
+        self.sync_entity_gpus();
+        Ok(())
+    }
+
+    /// Retourne true si l'entité a un MeshRenderer.
+    pub fn has_mesh_renderer(&self, id: u32) -> Result<bool, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.has_mesh_renderer(id))
+    }
+
+    /// Liste les IDs de toutes les entités qui ont un Transform.
+    pub fn get_entity_ids(&self) -> js_sys::Uint32Array {
+        let ids: Vec<u32> = self.core.get_entity_ids()
+            .into_iter()
+            .map(Entity::to_bits)
+            .collect();
+        js_sys::Uint32Array::from(ids.as_slice())
+    }
+
+    /// Retourne [px, py, pz, rx, ry, rz, sx, sy, sz] pour l'entité.
+    /// Retourne 9 zéros si l'entité n'a pas de Transform.
+    pub fn get_transform_array(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(self.core.get_transform_array(id).as_slice()))
+    }
+
+    /// Tick de la dernière modification d'un Transform (f64 : exact jusqu'à 2^53).
+    /// À mémoriser puis passer à `get_transforms_changed_since` pour une synchro incrémentale.
+    pub fn get_transform_change_tick(&self) -> f64 {
+        self.core.transform_change_tick() as f64
+    }
+
+    /// IDs des entités dont le Transform a été ajouté ou modifié après `tick`.
+    pub fn get_transforms_changed_since(&self, tick: f64) -> Vec<u32> {
+        self.core.transforms_changed_since(tick as u64).into_iter().map(Entity::to_bits).collect()
+    }
+
+    // ── Hiérarchie parent-enfant ─────────────────────────────────────────────
+
+    /// Définit parent_id comme parent de child_id.
+    /// Convertit le world transform actuel de child en local relatif à parent.
+    /// Ignoré si la relation créerait un cycle (ou auto-parent).
+    pub fn set_parent(&mut self, child_id: u32, parent_id: u32) -> Result<(), JsValue> {
+        let child_id = self.entity(child_id)?;
+        let parent_id = self.entity(parent_id)?;
+        self.core.set_parent(child_id, parent_id);
+        Ok(())
+    }
+
+    /// Retire le parent de child_id.
+    /// Convertit le local transform en world transform.
+    pub fn remove_parent(&mut self, child_id: u32) -> Result<(), JsValue> {
+        let child_id = self.entity(child_id)?;
+        self.core.remove_parent(child_id);
+        Ok(())
+    }
+
+    /// Retourne l'ID du parent, ou u32::MAX si pas de parent.
+    pub fn get_parent(&self, child_id: u32) -> Result<u32, JsValue> {
+        let child_id = self.entity(child_id)?;
+        Ok(self.core.get_parent(child_id)
+            .map(Entity::to_bits)
+            .unwrap_or(u32::MAX))
+    }
+
+    /// Retourne les IDs des enfants directs de parent_id.
+    pub fn get_children(&self, parent_id: u32) -> Result<js_sys::Uint32Array, JsValue> {
+        let parent_id = self.entity(parent_id)?;
+        let children: Vec<u32> = self.core.get_children(parent_id)
+            .into_iter()
+            .map(Entity::to_bits)
+            .collect();
+        Ok(js_sys::Uint32Array::from(children.as_slice()))
+    }
+
+    /// Retourne [px, py, pz, rx, ry, rz, sx, sy, sz] en espace monde.
+    pub fn get_world_transform_array(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(self.core.get_world_transform_array(id).as_slice()))
+    }
+
+    /// Retourne la matrice view*proj [16 f32, column-major] pour les gizmos.
+    /// Utilise toujours la caméra orbitale/active (sans preview), alignée avec le viewport principal.
+    pub fn get_view_proj(&self) -> js_sys::Float32Array {
+        let aspect = self.config.width as f32 / self.config.height as f32;
+        let vp = self.main_camera_matrix(aspect);
+        js_sys::Float32Array::from(vp.to_cols_array().as_slice())
+    }
+
+    /// Retourne la matrice monde [16 f32, column-major] d'une entité (résolution hiérarchie).
+    pub fn get_world_matrix(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        let m = self.core.world_matrix(id);
+        Ok(js_sys::Float32Array::from(m.to_cols_array().as_slice()))
+    }
+
+    // ── Transform ────────────────────────────────────────────────────────────
+
+    /// Ajoute un composant Transform à l'entité (position initiale xyz).
+    pub fn add_transform(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_transform(id, x, y, z);
+        Ok(())
+    }
+
+    pub fn set_position(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_position(id, x, y, z);
+        Ok(())
+    }
+
+    pub fn set_rotation(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_rotation(id, x, y, z);
+        Ok(())
+    }
+
+    pub fn set_scale(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_scale(id, x, y, z);
+        Ok(())
+    }
+
+    /// Déplace l'entité vers une position en espace MONDE.
+    /// Convertit automatiquement en espace local si l'entité a un parent.
+    pub fn set_world_position(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_world_position(id, x, y, z);
+        Ok(())
+    }
+
+    // ── MeshRenderer ─────────────────────────────────────────────────────────
+
+    /// Ajoute un MeshRenderer Cube + crée les ressources GPU associées.
+    pub fn add_mesh_renderer(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_mesh_renderer(id);
+        let gpu = self.create_entity_gpu();
+        self.entity_gpus.insert(id, gpu);
+        Ok(())
+    }
+
+    /// Upload custom mesh. vertices: flat f32 array (15 per vertex), indices: u32 array.
+    /// Returns custom mesh index for use with set_mesh_type("custom:N").
+    pub fn upload_custom_mesh(&mut self, vertices: &[f32], indices: &[u32]) -> usize {
+        let vbuf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label:    Some("custom_vbuf"),
+            contents: bytemuck::cast_slice(vertices),
+            usage:    wgpu::BufferUsages::VERTEX,
+        });
+        let ibuf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label:    Some("custom_ibuf"),
+            contents: bytemuck::cast_slice(indices),
+            usage:    wgpu::BufferUsages::INDEX,
+        });
+        let idx = self.core.add_custom_mesh(vertices, indices);
+        debug_assert_eq!(idx, self.custom_meshes.len(), "custom meshes CPU/GPU désynchronisés");
+        self.custom_meshes.push(CustomMeshGpu {
+            vertex_buffer: vbuf,
+            index_buffer: ibuf,
+            index_count: indices.len() as u32,
+        });
+        idx
+    }
+
+    /// Change le type de mesh d'une entité existante.
+    pub fn set_mesh_type(&mut self, id: u32, mesh_type: &str) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_mesh_type(id, mesh_type);
+        Ok(())
+    }
+
+    /// Retourne le type de mesh d'une entité ("cube" | "plane" | "sphere" | "cylinder" | "custom:N").
+    pub fn get_mesh_type(&self, id: u32) -> Result<String, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.get_mesh_type(id))
+    }
+
+    // ── Caméra ───────────────────────────────────────────────────────────────
+
+    pub fn set_camera(&mut self, ex: f32, ey: f32, ez: f32, tx: f32, ty: f32, tz: f32) {
+        self.camera.eye    = glam::Vec3::new(ex, ey, ez);
+        self.camera.target = glam::Vec3::new(tx, ty, tz);
+    }
+
+    pub fn add_camera(&mut self, id: u32, fov: f32, near: f32, far: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_camera(id, fov, near, far);
+        Ok(())
+    }
+
+    pub fn set_camera_follow_entity(&mut self, id: u32, follow_entity: bool) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_camera_follow_entity(id, follow_entity);
+        Ok(())
+    }
+
+    pub fn set_active_camera(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_active_camera(id);
+        Ok(())
+    }
+
+    pub fn remove_active_camera(&mut self) {
+        self.core.remove_active_camera();
+    }
+
+    /// Supprime le composant Camera d'une entité (sans supprimer l'entité elle-même).
+    pub fn remove_camera(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.remove_camera(id);
+        Ok(())
+    }
+
+    /// Prévisualise cette caméra dans le viewport de l'éditeur (sans activer le game mode).
+    pub fn set_preview_camera(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_preview_camera(id);
+        Ok(())
+    }
+
+    pub fn clear_preview_camera(&mut self) {
+        self.core.clear_preview_camera();
+    }
+
+    /// Switch between game mode (Play) and editor mode.
+    /// In editor mode, the active_camera entity is ignored — orbital camera is always used.
+    pub fn set_game_mode(&mut self, enabled: bool) {
+        self.core.set_game_mode(enabled);
+    }
+
+    // ── Textures ──────────────────────────────────────────────────────────────
+
+    /// Charge des pixels RGBA bruts en GPU. Retourne un TextureId (u32).
+    /// Cote TS : passer un Uint8Array de taille width * height * 4.
+    pub fn upload_texture(&mut self, width: u32, height: u32, data: &[u8], generate_mipmaps: bool) -> u32 {
+        assert_eq!(
+            data.len() as u64,
+            4 * width as u64 * height as u64,
+            "upload_texture: data length ({}) != width * height * 4 ({})",
+            data.len(),
+            4 * width as u64 * height as u64,
+        );
+        let tex = create_texture_from_data(&self.device, &self.queue, width, height, data, generate_mipmaps);
+        let id = self.textures.len() as u32;
+        self.textures.push(tex);
+        id
+    }
+
+    /// Rétrocompatibilité Phase 1-5. Utilise add_pbr_material pour le PBR.
+    pub fn add_material(&mut self, entity_id: u32, texture_id: u32) -> Result<(), JsValue> {
+        let entity_id = self.entity(entity_id)?;
+        self.core.add_material(entity_id, texture_id);
+        Ok(())
+    }
+
+    /// Associe un matériau PBR complet à l'entité.
+    pub fn add_pbr_material(
+        &mut self,
+        entity_id: u32,
+        albedo_tex: u32,
+        metallic:   f32,
+        roughness:  f32,
+    ) -> Result<(), JsValue> {
+        let entity_id = self.entity(entity_id)?;
+        self.core.add_pbr_material(entity_id, albedo_tex, metallic, roughness);
+        Ok(())
+    }
+
+    /// Rend un objet émissif (ex: ampoule, néon).
+    pub fn set_emissive(&mut self, entity_id: u32, r: f32, g: f32, b: f32) -> Result<(), JsValue> {
+        let entity_id = self.entity(entity_id)?;
+        self.core.set_emissive(entity_id, r, g, b);
+        Ok(())
+    }
+
+    /// Applique une normal map à l'entité (doit avoir un Material).
+    pub fn set_normal_map(&mut self, entity_id: u32, normal_tex_id: u32) -> Result<(), JsValue> {
+        let entity_id = self.entity(entity_id)?;
+        self.core.set_normal_map(entity_id, normal_tex_id);
+        Ok(())
+    }
+
+
+    // ── Resize surface + depth texture ────────────────────────────────────────
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 { return; }
+        self.config.width  = width;
+        self.config.height = height;
+        self.surface.configure(&self.device, &self.config);
+        let (dt, dv) = create_depth_texture(&self.device, &self.config);
+        self.depth_texture = dt;
+        self.depth_view    = dv;
+        // Recreate inset depth texture at new proportional size
+        self.inset_w = (width / 4).max(1);
+        self.inset_h = ((self.inset_w as f32 * 9.0 / 16.0) as u32).max(1);
+        let (idt, idv) = create_depth_texture_wh(&self.device, self.inset_w, self.inset_h);
+        self.inset_depth_texture = idt;
+        self.inset_depth_view    = idv;
+    }
+
+    // ── Rendu ─────────────────────────────────────────────────────────────────
+
+    pub fn render_frame(&mut self, delta_ms: f32) {
+        self.core.run_stage(Stage::Render, delta_ms / 1000.0);
+
+        let output = match self.surface.get_current_texture() {
+            Ok(t) => t,
+            Err(wgpu::SurfaceError::OutOfMemory) => {
+                web_sys::console::error_1(&"[World] GPU hors mémoire".into());
+                return;
+            }
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                web_sys::console::warn_1(&"[World] Surface lost/outdated — reconfiguring".into());
+                self.surface.configure(&self.device, &self.config);
+                return;
+            }
+            Err(e) => {
+                web_sys::console::warn_1(&format!("[World] render_frame surface error: {:?}", e).into());
+                return;
+            }
+        };
+
+        let view   = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let aspect = self.config.width as f32 / self.config.height.max(1) as f32;
+        // Main pass always uses the editor/game camera — preview_camera is rendered in a separate inset.
+        let view_proj = self.main_camera_matrix(aspect);
+
+        // ── Light space matrix ────────────────────────────────────────────────
+        // Si une entité pilote la direction, on lit son vecteur -Z monde.
+        let light_dir = if let Some(dl_id) = self.core.directional_light_entity {
+            let wm = self.core.render_matrix(dl_id);
+            let fwd = -(wm.col(2).truncate());
+            if fwd.length_squared() > 1e-6 { fwd.normalize() } else { glam::Vec3::new(0.0, -1.0, 0.0) }
+        } else {
+            self.core.directional_light.as_ref()
+                .map(|dl| dl.direction.normalize())
+                .unwrap_or(glam::Vec3::new(0.0, -1.0, 0.0))
+        };
+        // Centre de la shadow map = position de l'entité lumière directionnelle.
+        // Déplacer l'entité déplace la zone de couverture des ombres.
+        // Fallback sur la caméra si pas d'entité.
+        let scene_center = if let Some(dl_id) = self.core.directional_light_entity {
+            self.core.render_matrix(dl_id).col(3).truncate()
+        } else if let Some(cid) = self.core.active_camera {
+            self.core.render_matrix(cid).col(3).truncate()
+        } else {
+            self.camera.eye
+        };
+        let lsm = compute_light_space_mat(light_dir, scene_center);
+
+        let mut encoder = self.device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor { label: Some("render_encoder") }
+        );
+
+        // ── Upload EntityUniforms (MVP + model + metallic + roughness) ────────
+        for (id, (transform, mesh_renderer, gpu)) in
+            Query::new((
+                self.core.components.storage::<Transform>(),
+                self.core.components.storage::<MeshRenderer>(),
+                &self.entity_gpus,
+            ))
+        {
+
+            let model = self.core.render_matrix(id);
+            let mvp = view_proj * model;
+
+            let (metallic, roughness, emissive) = self.core.components.get::<Material>(id)
+                .map(|m| (m.metallic, m.roughness, m.emissive))
+                .unwrap_or((0.0, 0.5, glam::Vec3::ZERO));
+
+            // UV tiling by transform scale is useful for primitives,
+            // but breaks authored UVs on imported custom meshes / spherical/cylindrical UVs.
+            let uv_scale = match mesh_renderer.mesh_type {
+                MeshType::Custom(_) | MeshType::Sphere | MeshType::Cylinder => [1.0, 1.0, 1.0, 0.0],
+                _ => [transform.scale.x, transform.scale.y, transform.scale.z, 0.0],
+            };
+
+            let uniforms = EntityUniforms {
+                mvp:   mvp.to_cols_array_2d(),
+                model: model.to_cols_array_2d(),
+                metallic,
+                roughness,
+                _pad1:    [0.0; 2],
+                scale:    uv_scale,
+                emissive: emissive.to_array(),
+                _pad2:    0.0,
+            };
+            self.queue.write_buffer(&gpu.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+            // Upload shadow uniform : light_mvp = lsm * model
+            let light_mvp = lsm * model;
+            self.queue.write_buffer(
+                &gpu.shadow_uniform_buffer, 0,
+                bytemuck::cast_slice(light_mvp.as_ref()),
+            );
+        }
+
+        // ── Upload LightUniforms ──────────────────────────────────────────────
+        {
+            let mut lu = <LightUniforms as bytemuck::Zeroable>::zeroed();
+            let cam_pos = if let Some(cid) = self.core.active_camera {
+                // Use world position (camera may be a child of another entity).
+                let wm = self.core.render_matrix(cid);
+                wm.col(3).truncate()
+            } else {
+                self.camera.eye
+            };
+            lu.camera_pos = [cam_pos.x, cam_pos.y, cam_pos.z, 0.0];
+            lu.light_space_mat = lsm.to_cols_array_2d();
+
+            if let Some(dl) = &self.core.directional_light {
+                // light_dir déjà calculé ci-dessus (depuis entité ou champ direction)
+                let dir = light_dir;
+                let outer_rad = dl.cone_angle_deg.to_radians();
+                let inner_rad = (dl.cone_angle_deg * 0.75).to_radians();
+                lu.directional = GpuDirectionalLight {
+                    direction: dir.to_array(), _p0: 0.0,
+                    color: dl.color.to_array(), intensity: dl.intensity,
+                    position: scene_center.to_array(), _p1: 0.0,
+                    cone_cos_outer: outer_rad.cos(),
+                    cone_cos_inner: inner_rad.cos(),
+                    _p2: [0.0; 2],
+                };
+            }
+
+            let mut n = 0usize;
+            let lights = Query::new(self.core.components.storage::<PointLight>())
+                .with(self.core.components.storage::<Transform>());
+            for (id, pl) in lights {
+                if n >= 8 { break; }
+                let wm = self.core.render_matrix(id);
+                let wp = wm.col(3).truncate();
+                lu.points[n] = GpuPointLight {
+                    position: wp.to_array(), _p0: 0.0,
+                    color: pl.color.to_array(), intensity: pl.intensity,
+                };
+                n += 1;
+            }
+            lu.n_points = n as u32;
+            lu.ambient_color = [
+                self.ambient_color.x,
+                self.ambient_color.y,
+                self.ambient_color.z,
+                self.ambient_intensity,
+            ];
+            self.queue.write_buffer(&self.light_buffer, 0, bytemuck::bytes_of(&lu));
+        }
+
+        // ── 1. Shadow pass (depth-only) ───────────────────────────────────────
+        {
+            let mut shadow_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("shadow_pass"),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.shadow_depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load:  wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes:    None,
+                occlusion_query_set: None,
+                multiview_mask:      None,
+            });
+
+            shadow_pass.set_pipeline(&self.shadow_pipeline);
+
+            for (_, (mr, gpu)) in Query::new((self.core.components.storage::<MeshRenderer>(), &self.entity_gpus)) {
+                match &mr.mesh_type {
+                    MeshType::Cube => {
+                        shadow_pass.set_vertex_buffer(0, self.cube_vertex_buffer.slice(..));
+                        shadow_pass.set_index_buffer(self.cube_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                        shadow_pass.set_bind_group(0, &gpu.shadow_bind_group, &[]);
+                        shadow_pass.draw_indexed(0..CUBE_INDICES.len() as u32, 0, 0..1);
+                    },
+                    MeshType::Plane => {
+                        shadow_pass.set_vertex_buffer(0, self.plane_vertex_buffer.slice(..));
+                        shadow_pass.set_index_buffer(self.plane_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                        shadow_pass.set_bind_group(0, &gpu.shadow_bind_group, &[]);
+                        shadow_pass.draw_indexed(0..PLANE_INDICES.len() as u32, 0, 0..1);
+                    },
+                    MeshType::Sphere => {
+                        shadow_pass.set_vertex_buffer(0, self.sphere_vbuf.slice(..));
+                        shadow_pass.set_index_buffer(self.sphere_ibuf.slice(..), wgpu::IndexFormat::Uint32);
+                        shadow_pass.set_bind_group(0, &gpu.shadow_bind_group, &[]);
+                        shadow_pass.draw_indexed(0..self.sphere_ilen, 0, 0..1);
+                    },
+                    MeshType::Cylinder => {
+                        shadow_pass.set_vertex_buffer(0, self.cylinder_vbuf.slice(..));
+                        shadow_pass.set_index_buffer(self.cylinder_ibuf.slice(..), wgpu::IndexFormat::Uint32);
+                        shadow_pass.set_bind_group(0, &gpu.shadow_bind_group, &[]);
+                        shadow_pass.draw_indexed(0..self.cylinder_ilen, 0, 0..1);
+                    },
+                    MeshType::Custom(n) => {
+                        if let Some(cm) = self.custom_meshes.get(*n) {
+                            shadow_pass.set_vertex_buffer(0, cm.vertex_buffer.slice(..));
+                            shadow_pass.set_index_buffer(cm.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                            shadow_pass.set_bind_group(0, &gpu.shadow_bind_group, &[]);
+                            shadow_pass.draw_indexed(0..cm.index_count, 0, 0..1);
+                        }
+                    },
+                };
+            }
+        }
+
+        // ── 2. Main pass (PBR) ────────────────────────────────────────────────
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("main_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view:           &view,
+                    resolve_target: None,
+                    depth_slice:    None,
+                    ops: wgpu::Operations {
+                        load:  wgpu::LoadOp::Clear(wgpu::Color { r: 0.05, g: 0.05, b: 0.08, a: 1.0 }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load:  wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes:    None,
+                occlusion_query_set: None,
+                multiview_mask:      None,
+            });
+
+            pass.set_pipeline(&self.render_pipeline);
+
+            for (id, (mr, gpu)) in Query::new((self.core.components.storage::<MeshRenderer>(), &self.entity_gpus)) {
+
+                // Group 1 : albedo + normal bind group (créé à la volée)
+                let (albedo_view, normal_view) = if let Some(mat) = self.core.components.get::<Material>(id) {
+                    let av = if (mat.albedo_tex as usize) < self.textures.len() {
+                        &self.textures[mat.albedo_tex as usize].view
+                    } else {
+                        &self.default_tex.view
+                    };
+                    let nv = if (mat.normal_tex as usize) < self.textures.len() {
+                        &self.textures[mat.normal_tex as usize].view
+                    } else {
+                        &self.default_normal_tex.view
+                    };
+                    (av, nv)
+                } else {
+                    (&self.default_tex.view, &self.default_normal_tex.view)
+                };
+
+                let tex_bg = self.make_tex_bind_group(albedo_view, normal_view);
+
+                pass.set_bind_group(0, &gpu.bind_group, &[]);
+                pass.set_bind_group(1, &tex_bg, &[]);
+                pass.set_bind_group(2, &self.light_bind_group, &[]);
+                pass.set_bind_group(3, &self.shadow_bind_group, &[]);
+                match &mr.mesh_type {
+                    MeshType::Cube => {
+                        pass.set_vertex_buffer(0, self.cube_vertex_buffer.slice(..));
+                        pass.set_index_buffer(self.cube_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                        pass.draw_indexed(0..CUBE_INDICES.len() as u32, 0, 0..1);
+                    },
+                    MeshType::Plane => {
+                        pass.set_vertex_buffer(0, self.plane_vertex_buffer.slice(..));
+                        pass.set_index_buffer(self.plane_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                        pass.draw_indexed(0..PLANE_INDICES.len() as u32, 0, 0..1);
+                    },
+                    MeshType::Sphere => {
+                        pass.set_vertex_buffer(0, self.sphere_vbuf.slice(..));
+                        pass.set_index_buffer(self.sphere_ibuf.slice(..), wgpu::IndexFormat::Uint32);
+                        pass.draw_indexed(0..self.sphere_ilen, 0, 0..1);
+                    },
+                    MeshType::Cylinder => {
+                        pass.set_vertex_buffer(0, self.cylinder_vbuf.slice(..));
+                        pass.set_index_buffer(self.cylinder_ibuf.slice(..), wgpu::IndexFormat::Uint32);
+                        pass.draw_indexed(0..self.cylinder_ilen, 0, 0..1);
+                    },
+                    MeshType::Custom(n) => {
+                        if let Some(cm) = self.custom_meshes.get(*n) {
+                            pass.set_vertex_buffer(0, cm.vertex_buffer.slice(..));
+                            pass.set_index_buffer(cm.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                            pass.draw_indexed(0..cm.index_count, 0, 0..1);
+                        }
+                    },
+                };
+            }
+        }
+
+        // ── Submit shadow + main passes ───────────────────────────────────────
+        // MUST happen before the inset pass re-uploads uniforms with a different VP.
+        // queue.write_buffer calls are consumed at the next submit, so if both the
+        // main VP and inset VP writes land in the same submit, the inset VP wins and
+        // the main pass renders incorrectly (black viewport).
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        // ── Preview camera inset (separate submit, preserves main pass color) ─
+        if let Some(prev_id) = self.core.preview_camera {
+            if self.core.components.get::<Transform>(prev_id).is_some() {
+                let iw = self.inset_w;
+                let ih = self.inset_h;
+                let inset_aspect = iw as f32 / ih.max(1) as f32;
+
+                if let Some(inset_vp) = self.entity_cam_matrix(prev_id, inset_aspect) {
+                    // Re-upload entity MVPs with the inset camera's view_proj
+                    for (id, (mr, gpu)) in Query::new((self.core.components.storage::<MeshRenderer>(), &self.entity_gpus)) {
+                        let model = self.core.render_matrix(id);
+                        let mvp = inset_vp * model;
+                        let (metallic, roughness, emissive) = self.core.components.get::<Material>(id)
+                            .map(|m| (m.metallic, m.roughness, m.emissive))
+                            .unwrap_or((0.0, 0.5, glam::Vec3::ZERO));
+                        let uv_scale = match &mr.mesh_type {
+                            MeshType::Custom(_) | MeshType::Sphere | MeshType::Cylinder => [1.0, 1.0, 1.0, 0.0],
+                            _ => match self.core.components.get::<Transform>(id) {
+                                Some(t) => [t.scale.x, t.scale.y, t.scale.z, 0.0],
+                                None    => [1.0, 1.0, 1.0, 0.0],
+                            },
+                        };
+                        let uniforms = EntityUniforms {
+                            mvp:   mvp.to_cols_array_2d(),
+                            model: model.to_cols_array_2d(),
+                            metallic, roughness, _pad1: [0.0; 2],
+                            scale: uv_scale,
+                            emissive: emissive.to_array(), _pad2: 0.0,
+                        };
+                        self.queue.write_buffer(&gpu.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+                    }
+
+                    let ix = self.config.width.saturating_sub(iw + 10);
+                    let iy = self.config.height.saturating_sub(ih + 10);
+
+                    let mut encoder2 = self.device.create_command_encoder(
+                        &wgpu::CommandEncoderDescriptor { label: Some("inset_encoder") }
+                    );
+                    {
+                        let mut inset_pass = encoder2.begin_render_pass(&wgpu::RenderPassDescriptor {
+                            label: Some("inset_preview_pass"),
+                            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                                view:           &view,
+                                resolve_target: None,
+                                depth_slice:    None,
+                                ops: wgpu::Operations {
+                                    load:  wgpu::LoadOp::Load, // preserve main pass color
+                                    store: wgpu::StoreOp::Store,
+                                },
+                            })],
+                            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                                view: &self.inset_depth_view,
+                                depth_ops: Some(wgpu::Operations {
+                                    load:  wgpu::LoadOp::Clear(1.0),
+                                    store: wgpu::StoreOp::Store,
+                                }),
+                                stencil_ops: None,
+                            }),
+                            timestamp_writes:    None,
+                            occlusion_query_set: None,
+                            multiview_mask:      None,
+                        });
+
+                        inset_pass.set_viewport(ix as f32, iy as f32, iw as f32, ih as f32, 0.0, 1.0);
+                        inset_pass.set_scissor_rect(ix, iy, iw, ih);
+                        inset_pass.set_pipeline(&self.render_pipeline);
+
+                        for (id, (mr, gpu)) in Query::new((self.core.components.storage::<MeshRenderer>(), &self.entity_gpus)) {
+                            let (albedo_view, normal_view) = if let Some(mat) = self.core.components.get::<Material>(id) {
+                                let av = if (mat.albedo_tex as usize) < self.textures.len() { &self.textures[mat.albedo_tex as usize].view } else { &self.default_tex.view };
+                                let nv = if (mat.normal_tex as usize) < self.textures.len() { &self.textures[mat.normal_tex as usize].view } else { &self.default_normal_tex.view };
+                                (av, nv)
+                            } else {
+                                (&self.default_tex.view, &self.default_normal_tex.view)
+                            };
+                            let tex_bg = self.make_tex_bind_group(albedo_view, normal_view);
+                            inset_pass.set_bind_group(0, &gpu.bind_group, &[]);
+                            inset_pass.set_bind_group(1, &tex_bg, &[]);
+                            inset_pass.set_bind_group(2, &self.light_bind_group, &[]);
+                            inset_pass.set_bind_group(3, &self.shadow_bind_group, &[]);
+                            match &mr.mesh_type {
+                                MeshType::Cube     => { inset_pass.set_vertex_buffer(0, self.cube_vertex_buffer.slice(..)); inset_pass.set_index_buffer(self.cube_index_buffer.slice(..), wgpu::IndexFormat::Uint16); inset_pass.draw_indexed(0..CUBE_INDICES.len() as u32, 0, 0..1); },
+                                MeshType::Plane    => { inset_pass.set_vertex_buffer(0, self.plane_vertex_buffer.slice(..)); inset_pass.set_index_buffer(self.plane_index_buffer.slice(..), wgpu::IndexFormat::Uint16); inset_pass.draw_indexed(0..PLANE_INDICES.len() as u32, 0, 0..1); },
+                                MeshType::Sphere   => { inset_pass.set_vertex_buffer(0, self.sphere_vbuf.slice(..)); inset_pass.set_index_buffer(self.sphere_ibuf.slice(..), wgpu::IndexFormat::Uint32); inset_pass.draw_indexed(0..self.sphere_ilen, 0, 0..1); },
+                                MeshType::Cylinder => { inset_pass.set_vertex_buffer(0, self.cylinder_vbuf.slice(..)); inset_pass.set_index_buffer(self.cylinder_ibuf.slice(..), wgpu::IndexFormat::Uint32); inset_pass.draw_indexed(0..self.cylinder_ilen, 0, 0..1); },
+                                MeshType::Custom(n) => { if let Some(cm) = self.custom_meshes.get(*n) { inset_pass.set_vertex_buffer(0, cm.vertex_buffer.slice(..)); inset_pass.set_index_buffer(cm.index_buffer.slice(..), wgpu::IndexFormat::Uint32); inset_pass.draw_indexed(0..cm.index_count, 0, 0..1); } },
+                            };
+                        }
+                    }
+                    self.queue.submit(std::iter::once(encoder2.finish()));
+                }
+            }
+        }
+
+        output.present();
+    }
+}
+
+#[wasm_bindgen]
+impl World {
+    // ── Physique ─────────────────────────────────────────────────────────────
+
+    /// Désigne une entité joueur : lui ajoute un CharacterController par défaut s'il n'en a pas,
+    /// piloté par l'input et orienté par le yaw de la caméra.
+    pub fn set_player(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_player(id);
+        Ok(())
+    }
+
+    /// Intention d'un CharacterController non joueur : direction (x, z) en monde, longueur ≤ 1,
+    /// et demande de saut.
+    pub fn set_character_intent(&mut self, id: u32, x: f32, z: f32, jump: bool) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_character_intent(id, x, z, jump);
+        Ok(())
+    }
+
+    /// Règle le CharacterController (ajouté s'il manque) : vitesse (m/s), accélération au sol (m/s²),
+    /// contrôle en l'air (0..1), hauteur de saut et de marche (m), pente max (degrés),
+    /// coyote time et tampon de saut (s).
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_character_controller(
+        &mut self,
+        id: u32,
+        speed: f32,
+        acceleration: f32,
+        air_control: f32,
+        jump_height: f32,
+        step_height: f32,
+        slope_limit: f32,
+        coyote_time: f32,
+        jump_buffer: f32,
+    ) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_character_controller(id, speed, acceleration, air_control, jump_height,
+                                           step_height, slope_limit, coyote_time, jump_buffer);
+        Ok(())
+    }
+
+    /// Retourne [speed, acceleration, air_control, jump_height, step_height, slope_limit,
+    /// coyote_time, jump_buffer], ou des zéros si absent.
+    pub fn get_character_controller(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(&self.core.get_character_controller(id)[..]))
+    }
+
+    pub fn remove_character_controller(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.remove_character_controller(id);
+        Ok(())
+    }
+
+    /// Ajoute un RigidBody. `is_static = true` pour les entités fixes (sol, murs).
+    pub fn add_rigid_body(&mut self, id: u32, is_static: bool) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_rigid_body(id, is_static);
+        Ok(())
+    }
+
+    /// Type d'un RigidBody existant : "dynamic" | "static" | "kinematic".
+    /// Un kinematic suit sa velocity (`set_velocity`) ou les scripts (`set_position`) et transporte
+    /// les corps posés dessus.
+    pub fn set_body_type(&mut self, id: u32, body_type: &str) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        let body_type = match body_type {
+            "dynamic"   => BodyType::Dynamic,
+            "static"    => BodyType::Static,
+            "kinematic" => BodyType::Kinematic,
+            other       => return Err(JsValue::from_str(&format!("unknown body type: {other}"))),
+        };
+        self.core.set_body_type(id, body_type);
+        Ok(())
+    }
+
+    /// Détection continue (CCD) d'un RigidBody dynamique : balaie le collider le long du
+    /// déplacement de chaque pas (projectiles, chutes rapides).
+    pub fn set_ccd(&mut self, id: u32, enabled: bool) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_ccd(id, enabled);
+        Ok(())
+    }
+
+    /// Masse (kg), restitution (0..1) et friction d'un RigidBody.
+    pub fn set_rigid_body_material(&mut self, id: u32, mass: f32, restitution: f32, friction: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_rigid_body_material(id, mass, restitution, friction);
+        Ok(())
+    }
+
+    /// Retourne [mass, restitution, friction] d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_rigid_body_material(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(&self.core.get_rigid_body_material(id)[..]))
+    }
+
+    /// Amortissements linéaire et angulaire (1/s) d'un RigidBody.
+    pub fn set_damping(&mut self, id: u32, linear: f32, angular: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_damping(id, linear, angular);
+        Ok(())
+    }
+
+    /// Multiplie la gravité du monde pour ce corps (0 = flotte).
+    pub fn set_gravity_scale(&mut self, id: u32, scale: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_gravity_scale(id, scale);
+        Ok(())
+    }
+
+    /// Retourne [linear_damping, angular_damping, gravity_scale] d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_body_dynamics(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(&self.core.get_body_dynamics(id)[..]))
+    }
+
+    /// Ajoute un Collider boîte (demi-extents locaux en mètres). Centre = Transform.position, orienté par la rotation.
+    pub fn add_collider_aabb(&mut self, id: u32, hx: f32, hy: f32, hz: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_collider_aabb(id, hx, hy, hz);
+        Ok(())
+    }
+
+    /// Ajoute un Collider sphère (rayon en mètres). Centre = Transform.position.
+    pub fn add_collider_sphere(&mut self, id: u32, radius: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_collider_sphere(id, radius);
+        Ok(())
+    }
+
+    /// Ajoute un Collider capsule d'axe Y local. Hauteur totale = 2 * (half_height + radius).
+    pub fn add_collider_capsule(&mut self, id: u32, radius: f32, half_height: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_collider_capsule(id, radius, half_height);
+        Ok(())
+    }
+
+    /// Collider maillage : les triangles du mesh custom de l'entité (statiques / kinematics :
+    /// sols, rampes, escaliers). Retourne false si l'entité n'a pas de mesh custom avec des triangles.
+    pub fn add_mesh_collider(&mut self, id: u32) -> Result<bool, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.add_mesh_collider(id))
+    }
+
+    /// Marque le collider comme trigger : chevauchements signalés par `drain_collision_events`, jamais résolus.
+    pub fn set_collider_trigger(&mut self, id: u32, is_trigger: bool) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_collider_trigger(id, is_trigger);
+        Ok(())
+    }
+
+    /// Couches du collider : `layer` (bits d'appartenance) et `mask` (couches avec lesquelles
+    /// il interagit). Deux colliders n'entrent en contact que si chacun accepte l'autre.
+    pub fn set_collider_layers(&mut self, id: u32, layer: u32, mask: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_collider_layers(id, layer, mask);
+        Ok(())
+    }
+
+    /// Retourne [layer, mask] du collider, ou [0, 0] si absent.
+    pub fn get_collider_layers(&self, id: u32) -> Result<js_sys::Uint32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Uint32Array::from(&self.core.get_collider_layers(id)[..]))
+    }
+
+    /// Vide la file des événements de collision / trigger accumulés depuis le dernier appel.
+    /// 4 valeurs par événement : [a, b, phase (0 = enter, 1 = stay, 2 = exit), trigger (0 / 1)].
+    pub fn drain_collision_events(&mut self) -> js_sys::Uint32Array {
+        let flat: Vec<u32> = self.core.drain_collision_events()
+            .into_iter()
+            .flat_map(|e| [e.a.to_bits(), e.b.to_bits(), e.phase as u32, e.trigger as u32])
+            .collect();
+        js_sys::Uint32Array::from(flat.as_slice())
+    }
+
+    /// Retourne [hx, hy, hz] de la boîte englobante locale du collider, ou [0,0,0] si absent.
+    pub fn get_collider_array(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(&self.core.get_collider_array(id)[..]))
+    }
+
+    /// Ajuste automatiquement le Box Collider à la taille du mesh visuel.
+    /// `min_half_y` évite un collider trop fin (utile pour les planes).
+    pub fn fit_collider_to_mesh(&mut self, id: u32, min_half_y: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.fit_collider_to_mesh(id, min_half_y);
+        Ok(())
+    }
+
+    // ── Joints ───────────────────────────────────────────────────────────────
+    // Un joint par entité, vers `target` ou, avec target = u32::MAX, vers un point fixe du monde.
+
+    /// Tige entre l'ancre `a` (locale à l'entité) et `b` (locale à target, monde sans target).
+    /// `length` < 0 : distance actuelle entre les ancres.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_distance_joint(
+        &mut self, id: u32, target: u32,
+        ax: f32, ay: f32, az: f32,
+        bx: f32, by: f32, bz: f32,
+        length: f32,
+    ) -> Result<(), JsValue> {
+        let id     = self.entity(id)?;
+        let target = self.joint_target(id, target)?;
+        let length = (length >= 0.0).then_some(length);
+        self.core.add_distance_joint(id, target, glam::Vec3::new(ax, ay, az), glam::Vec3::new(bx, by, bz), length);
+        Ok(())
+    }
+
+    /// Rotule autour du point monde `p`.
+    pub fn add_ball_joint(&mut self, id: u32, target: u32, px: f32, py: f32, pz: f32) -> Result<(), JsValue> {
+        let id     = self.entity(id)?;
+        let target = self.joint_target(id, target)?;
+        self.core.add_ball_joint(id, target, glam::Vec3::new(px, py, pz));
+        Ok(())
+    }
+
+    /// Charnière d'axe monde `a` passant par le point monde `p`. Avec `use_limits`, l'angle
+    /// reste dans [min_deg, max_deg], 0 = pose actuelle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_hinge_joint(
+        &mut self, id: u32, target: u32,
+        px: f32, py: f32, pz: f32,
+        ax: f32, ay: f32, az: f32,
+        use_limits: bool, min_deg: f32, max_deg: f32,
+    ) -> Result<(), JsValue> {
+        let id     = self.entity(id)?;
+        let target = self.joint_target(id, target)?;
+        let limits = use_limits.then_some((min_deg, max_deg));
+        if self.core.add_hinge_joint(id, target, glam::Vec3::new(px, py, pz), glam::Vec3::new(ax, ay, az), limits) {
+            Ok(())
+        } else {
+            Err(JsValue::from_str("hinge axis must be non-zero"))
+        }
+    }
+
+    /// Soude l'entité à target dans leur pose actuelle.
+    pub fn add_fixed_joint(&mut self, id: u32, target: u32) -> Result<(), JsValue> {
+        let id     = self.entity(id)?;
+        let target = self.joint_target(id, target)?;
+        self.core.add_fixed_joint(id, target);
+        Ok(())
+    }
+
+    pub fn remove_joint(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.remove_joint(id);
+        Ok(())
+    }
+
+    /// Retourne l'ID de la cible du joint, u32::MAX si le joint vise le monde ou s'il n'y en a pas.
+    pub fn get_joint_target(&self, id: u32) -> Result<u32, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.components().get::<Joint>(id)
+            .and_then(|j| j.target)
+            .map(Entity::to_bits)
+            .unwrap_or(u32::MAX))
+    }
+
+    // ── Requêtes spatiales ───────────────────────────────────────────────────
+
+    /// Premier collider (hors triggers) touché par le rayon origine `o`, direction `d`.
+    /// Retourne [id, px, py, pz, nx, ny, nz, distance], ou un tableau vide si rien n'est touché.
+    /// `layer_mask` : couches à tester (0xFFFFFFFF = toutes).
+    #[allow(clippy::too_many_arguments)]
+    pub fn raycast(&self, ox: f32, oy: f32, oz: f32, dx: f32, dy: f32, dz: f32, max_dist: f32, layer_mask: u32)
+        -> js_sys::Float64Array
+    {
+        let hit = self.core.raycast(glam::Vec3::new(ox, oy, oz), glam::Vec3::new(dx, dy, dz), max_dist, layer_mask);
+        hit_array(hit)
+    }
+
+    /// Comme `raycast`, pour une sphère de rayon `radius` lancée depuis `o`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sphere_cast(&self, ox: f32, oy: f32, oz: f32, radius: f32, dx: f32, dy: f32, dz: f32, max_dist: f32, layer_mask: u32)
+        -> js_sys::Float64Array
+    {
+        let hit = self.core.sphere_cast(glam::Vec3::new(ox, oy, oz), radius, glam::Vec3::new(dx, dy, dz), max_dist, layer_mask);
+        hit_array(hit)
+    }
+
+    /// Comme `raycast`, pour une boîte centrée en `c`, demi-extents `h`, rotation Euler `r` (degrés).
+    #[allow(clippy::too_many_arguments)]
+    pub fn box_cast(
+        &self,
+        cx: f32, cy: f32, cz: f32,
+        hx: f32, hy: f32, hz: f32,
+        rx: f32, ry: f32, rz: f32,
+        dx: f32, dy: f32, dz: f32,
+        max_dist: f32,
+        layer_mask: u32,
+    ) -> js_sys::Float64Array {
+        let rotation = Transform::quat_from_euler_deg(glam::Vec3::new(rx, ry, rz));
+        let hit = self.core.box_cast(
+            glam::Vec3::new(cx, cy, cz), glam::Vec3::new(hx, hy, hz), rotation, glam::Vec3::new(dx, dy, dz), max_dist, layer_mask,
+        );
+        hit_array(hit)
+    }
+
+    /// Rayon monde sous un point du viewport principal, en coordonnées NDC ([-1, 1], Y vers le haut).
+    /// Retourne [ox, oy, oz, dx, dy, dz] (origine sur le plan proche, direction unitaire).
+    pub fn screen_ray(&self, ndc_x: f32, ndc_y: f32) -> js_sys::Float32Array {
+        let aspect = self.config.width as f32 / self.config.height as f32;
+        let inv    = self.main_camera_matrix(aspect).inverse();
+        let near   = inv.project_point3(glam::Vec3::new(ndc_x, ndc_y, 0.0));
+        let far    = inv.project_point3(glam::Vec3::new(ndc_x, ndc_y, 1.0));
+        let dir    = (far - near).normalize_or_zero();
+        js_sys::Float32Array::from([near.x, near.y, near.z, dir.x, dir.y, dir.z].as_slice())
+    }
+
+    // ── Input ────────────────────────────────────────────────────────────────
+
+    /// Transmet l'état input du frame courant.
+    /// `keys` bitmask : bit0=W, bit1=S, bit2=A, bit3=D, bit4=SPACE.
+    /// `mouse_dx/dy` : delta pixels depuis le dernier frame (Pointer Lock).
+    pub fn set_input(&mut self, keys: u32, mouse_dx: f32, mouse_dy: f32) {
+        self.core.set_input(keys, mouse_dx, mouse_dy);
+    }
+
+    /// Rotation caméra par pixel de souris, en radians (défaut 0.002).
+    pub fn set_mouse_sensitivity(&mut self, radians_per_pixel: f32) {
+        self.core.set_mouse_sensitivity(radians_per_pixel);
+    }
+
+    /// Met à jour la physique et la caméra FPS. Appeler avant render_frame().
+    pub fn update(&mut self, delta_ms: f32) {
+        self.core.update(delta_ms);
+    }
+
+    /// Pas fixe de la physique : `hz` pas par seconde (défaut 60), au plus `max_substeps` par frame (défaut 5).
+    pub fn set_fixed_timestep(&mut self, hz: f32, max_substeps: u32) -> Result<(), JsValue> {
+        if !(hz > 0.0 && hz.is_finite()) {
+            return Err(JsValue::from_str(&format!("invalid fixed timestep frequency {hz}")));
+        }
+        self.core.set_fixed_timestep(hz, max_substeps);
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl World {
+    // ── Éclairage ────────────────────────────────────────────────────────────
+
+    /// Ajoute une point light attachée à l'entité (doit avoir un Transform).
+    /// Couleur (r, g, b) entre 0.0 et 1.0, intensity en lux (ex: 5.0–20.0).
+    pub fn add_point_light(&mut self, id: u32, r: f32, g: f32, b: f32, intensity: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_point_light(id, r, g, b, intensity);
+        Ok(())
+    }
+
+    /// Supprime la point light de l'entité (sans supprimer l'entité elle-même).
+    pub fn remove_point_light(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.remove_point_light(id);
+        Ok(())
+    }
+
+    /// Définit la lumière directionnelle (soleil). Un seul appel suffit.
+    /// direction (dx, dy, dz) : vecteur vers lequel la lumière pointe (normalisé automatiquement).
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_directional_light(
+        &mut self,
+        dx: f32, dy: f32, dz: f32,
+        r: f32, g: f32, b: f32,
+        intensity: f32,
+    ) {
+        self.core.add_directional_light(dx, dy, dz, r, g, b, intensity);
+    }
+
+    /// Ajoute une lumière directionnelle (spotlight) pilotée par l'entité `id`.
+    /// `cone_angle_deg` : demi-angle extérieur du cône en degrés (ex: 30.0).
+    pub fn add_directional_light_entity(&mut self, id: u32, r: f32, g: f32, b: f32, intensity: f32, cone_angle_deg: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.add_directional_light_entity(id, r, g, b, intensity, cone_angle_deg);
+        Ok(())
+    }
+
+    /// Lie une entité existante à la lumière directionnelle (sa rotation pilote la direction).
+    pub fn set_directional_light_entity(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_directional_light_entity(id);
+        Ok(())
+    }
+
+    /// Supprime la lumière directionnelle globale (la scène n'en aura plus).
+    pub fn remove_directional_light(&mut self) {
+        self.core.remove_directional_light();
+    }
+
+    /// Définit la lumière ambiante globale.
+    pub fn set_ambient_light(&mut self, r: f32, g: f32, b: f32, intensity: f32) {
+        self.ambient_color     = glam::Vec3::new(r, g, b);
+        self.ambient_intensity = intensity;
+    }
+
+    /// Retourne la velocity [vx, vy, vz] d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_velocity(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(self.core.get_velocity(id).as_slice()))
+    }
+
+    /// Définit la velocity d'un RigidBody.
+    pub fn set_velocity(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_velocity(id, x, y, z);
+        Ok(())
+    }
+
+    /// Retourne la vitesse angulaire [wx, wy, wz] (rad/s) d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_angular_velocity(&self, id: u32) -> Result<js_sys::Float32Array, JsValue> {
+        let id = self.entity(id)?;
+        Ok(js_sys::Float32Array::from(self.core.get_angular_velocity(id).as_slice()))
+    }
+
+    pub fn set_angular_velocity(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_angular_velocity(id, x, y, z);
+        Ok(())
+    }
+
+    /// Force (N, monde) appliquée pendant les pas fixes du prochain `update` ; à renouveler
+    /// à chaque frame pour une poussée continue.
+    pub fn apply_force(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.apply_force(id, glam::Vec3::new(x, y, z));
+        Ok(())
+    }
+
+    /// Couple (N·m, axe monde), appliqué comme `apply_force`.
+    pub fn apply_torque(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.apply_torque(id, glam::Vec3::new(x, y, z));
+        Ok(())
+    }
+
+    /// Impulsion (N·s, monde) : change immédiatement la velocity.
+    pub fn apply_impulse(&mut self, id: u32, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.apply_impulse(id, glam::Vec3::new(x, y, z));
+        Ok(())
+    }
+
+    /// True si le RigidBody dort (immobile depuis un moment, réveillé par un contact,
+    /// une impulsion ou un déplacement).
+    pub fn is_sleeping(&self, id: u32) -> Result<bool, JsValue> {
+        let id = self.entity(id)?;
+        Ok(self.core.is_sleeping(id))
+    }
+
+    /// Réveille le corps et tout son îlot endormi.
+    pub fn wake_up(&mut self, id: u32) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.wake_up(id);
+        Ok(())
+    }
+
+    /// Gravité du monde (m/s²), (0, -9.8, 0) par défaut.
+    pub fn set_gravity(&mut self, x: f32, y: f32, z: f32) {
+        self.core.set_gravity(glam::Vec3::new(x, y, z));
+    }
+
+    pub fn get_gravity(&self) -> js_sys::Float32Array {
+        js_sys::Float32Array::from(self.core.gravity().to_array().as_slice())
+    }
+}
+
+#[wasm_bindgen]
+impl World {
+    // ── Scènes ───────────────────────────────────────────────────────────────
+
+    /// Enregistre un TextureId GPU sous un nom string.
+    /// Appeler avant load_scene() pour que les textures nommées soient résolvables.
+    pub fn register_texture(&mut self, name: String, texture_id: u32) {
+        self.core.register_texture(name, texture_id);
+    }
+
+    /// Marque (ou démarque) une entité comme persistante.
+    /// Les entités persistantes survivent aux appels à load_scene().
+    pub fn set_persistent(&mut self, id: u32, persistent: bool) -> Result<(), JsValue> {
+        let id = self.entity(id)?;
+        self.core.set_persistent(id, persistent);
+        Ok(())
+    }
+
+    /// Charge une scène depuis un JSON string.
+    /// Supprime les entités non-persistantes, puis crée les entités du JSON.
+    /// Retourne un Uint32Array des IDs des nouvelles entités créées.
+    pub fn load_scene(&mut self, json: &str) -> js_sys::Uint32Array {
+        let new_ids = match self.core.load_scene(json) {
+            Ok(ids) => ids,
+            Err(e)  => {
+                web_sys::console::error_1(&format!("[load_scene] JSON invalide: {e}").into());
+                return js_sys::Uint32Array::new_with_length(0);
+            }
+        };
+        self.sync_entity_gpus();
+
+        let new_ids: Vec<u32> = new_ids.into_iter().map(Entity::to_bits).collect();
+        js_sys::Uint32Array::from(new_ids.as_slice())
+    }
+
+    /// Sérialise la scène courante (toutes les entités) en JSON string.
+    pub fn save_scene(&self) -> String {
+        self.core.save_scene()
+    }
+}
+
+impl World {
+    /// Monde de simulation, pour enregistrer composants et systèmes depuis un jeu Rust.
+    pub fn core_mut(&mut self) -> &mut WorldCore {
+        &mut self.core
+    }
+
+    /// Valide un handle venu de JS : exception JS si l'entité est morte ou le handle invalide.
+    fn entity(&self, bits: u32) -> Result<Entity, JsValue> {
+        self.core.resolve(bits).ok_or_else(|| {
+            JsValue::from_str(&format!("dead or invalid entity handle {bits:#010x}"))
+        })
+    }
+
+    /// Cible d'un joint de `id` : None pour u32::MAX (monde), erreur si c'est `id` lui-même.
+    fn joint_target(&self, id: Entity, bits: u32) -> Result<Option<Entity>, JsValue> {
+        if bits == u32::MAX {
+            return Ok(None);
+        }
+        let target = self.entity(bits)?;
+        if target == id {
+            return Err(JsValue::from_str("an entity cannot be jointed to itself"));
+        }
+        Ok(Some(target))
+    }
+
+    /// Crée les buffers uniformes + bind groups d'une entité rendue.
+    fn create_entity_gpu(&self) -> EntityGpu {
+        let uniform_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label:              Some("entity_uniform"),
+            size:               std::mem::size_of::<EntityUniforms>() as u64,
+            usage:              wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label:   Some("entity_bind_group"),
+            layout:  &self.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding:  0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shadow_uniform_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label:              Some("shadow_entity_uniform"),
+            size:               64, // mat4x4<f32>
+            usage:              wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let shadow_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label:   Some("shadow_entity_bg"),
+            layout:  &self.shadow_entity_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding:  0,
+                resource: shadow_uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        EntityGpu {
+            uniform_buffer, bind_group,
+            shadow_uniform_buffer, shadow_bind_group,
+        }
+    }
+
+    /// Aligne `entity_gpus` sur les MeshRenderer du core :
+    /// libère les ressources des entités supprimées, crée celles des nouvelles.
+    fn sync_entity_gpus(&mut self) {
+        let stale: Vec<Entity> = self.entity_gpus.iter_ids()
+            .filter(|&id| !(self.core.is_alive(id) && self.core.has_mesh_renderer(id)))
+            .collect();
+        for id in stale {
+            self.entity_gpus.remove(id);
+        }
+
+        let missing: Vec<Entity> = self.core.components.storage::<MeshRenderer>().iter_ids()
+            .filter(|&id| self.entity_gpus.get(id).is_none())
+            .collect();
+        for id in missing {
+            let gpu = self.create_entity_gpu();
+            self.entity_gpus.insert(id, gpu);
+        }
+    }
+
+    fn build_view_from_transform(t: &Transform) -> glam::Mat4 {
+        use glam::{Mat4, Vec3, Vec4};
+
+        let rot = Mat4::from_quat(t.rotation);
+
+        // Forward in engine convention is -Z in local space.
+        let forward = (rot * Vec4::new(0.0, 0.0, -1.0, 0.0)).truncate().normalize();
+
+        // Build a stable orthonormal basis, even when forward is close to world up.
+        let world_up = Vec3::Y;
+        let up_ref = if forward.dot(world_up).abs() > 0.999 {
+            Vec3::Z
+        } else {
+            world_up
+        };
+        let right = forward.cross(up_ref).normalize();
+        let up = right.cross(forward).normalize();
+
+        Mat4::look_at_rh(t.position, t.position + forward, up)
+    }
+
+    /// Build a view matrix directly from a world-space Mat4 (no euler decomposition).
+    /// Forward = -Z column, position = column 3.
+    fn view_from_world_mat(world: &glam::Mat4) -> glam::Mat4 {
+        use glam::Vec3;
+        let pos     = world.col(3).truncate();
+        let forward = -(world.col(2).truncate().normalize_or_zero());
+        let up_ref  = if forward.dot(Vec3::Y).abs() > 0.999 { Vec3::Z } else { Vec3::Y };
+        let right   = forward.cross(up_ref).normalize();
+        let up      = right.cross(forward).normalize();
+        glam::Mat4::look_at_rh(pos, pos + forward, up)
+    }
+
+    /// Builds proj*view for a specific camera entity (using its world transform).
+    fn entity_cam_matrix(&self, cam_id: Entity, aspect: f32) -> Option<glam::Mat4> {
+        self.core.components.get::<Transform>(cam_id)?;
+        let cam  = self.core.components.get::<CameraComponent>(cam_id);
+        let fov  = cam.map(|c| c.fov).unwrap_or(60.0);
+        let near = cam.map(|c| c.near).unwrap_or(0.1);
+        let far  = cam.map(|c| c.far).unwrap_or(1000.0);
+        let proj = glam::Mat4::perspective_rh(fov.to_radians(), aspect, near, far);
+        let world = self.core.render_matrix(cam_id);
+        let (_scale, rot, pos) = world.to_scale_rotation_translation();
+        // Camera convention: local -Z is forward.
+        let forward = (rot * glam::Vec3::new(0.0, 0.0, -1.0)).normalize_or_zero();
+        let up = (rot * glam::Vec3::Y).normalize_or_zero();
+        if forward.length_squared() < 1e-6 || up.length_squared() < 1e-6 {
+            return None;
+        }
+        let view = glam::Mat4::look_at_rh(pos, pos + forward, up);
+        Some(proj * view)
+    }
+
+    /// Main viewport camera: active entity camera (game mode) or orbital editor camera.
+    /// Does NOT include preview_camera — used for the main render pass and get_view_proj().
+    fn main_camera_matrix(&self, aspect: f32) -> glam::Mat4 {
+        if self.core.is_game_mode {
+            if let Some(cam_id) = self.core.active_camera {
+                if let Some(m) = self.entity_cam_matrix(cam_id, aspect) { return m; }
+            }
+        }
+        self.camera.proj_matrix(aspect) * self.camera.view_matrix()
+    }
+
+    /// Full-priority camera: preview > active (game mode) > orbital.
+    /// Used only for get_view_proj() when TS side needs to know the current "rendered from" camera.
+    fn camera_matrix(&self, aspect: f32) -> glam::Mat4 {
+        if let Some(prev_id) = self.core.preview_camera {
+            if let Some(m) = self.entity_cam_matrix(prev_id, aspect) { return m; }
+        }
+        self.main_camera_matrix(aspect)
+    }
+
+    fn make_tex_bind_group(
+        &self,
+        albedo_view: &wgpu::TextureView,
+        normal_view: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label:   Some("tex_bg"),
+            layout:  &self.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(albedo_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(normal_view) },
+                wgpu::BindGroupEntry { binding: 3, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+            ],
+        })
+    }
+}
+
+/// [id, px, py, pz, nx, ny, nz, distance] (f64 : id u32 exact), vide si aucun impact.
+fn hit_array(hit: Option<RayHit>) -> js_sys::Float64Array {
+    let flat: Vec<f64> = hit
+        .map(|h| {
+            let mut v = vec![h.entity.to_bits() as f64];
+            v.extend(h.point.to_array().iter().chain(&h.normal.to_array()).map(|&x| x as f64));
+            v.push(h.distance as f64);
+            v
+        })
+        .unwrap_or_default();
+    js_sys::Float64Array::from(flat.as_slice())
+}
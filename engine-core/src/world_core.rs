@@ -0,0 +1,1499 @@
+//! État de simulation sans GPU : ECS, physique, hiérarchie, scènes, noms et tags.
+//!
+//! `WorldCore` compile en natif (pas de wgpu / web-sys requis) pour pouvoir
+//! tester le gameplay avec `cargo test`. Le `World` wasm l'enveloppe et ajoute
+//! le renderer WebGPU.
+
+use crate::ecs::{BodyType, CameraComponent, CharacterController, Collider, ColliderShape, Components, Entity, GlobalTransform, EntityAllocator, Joint, Material, MeshRenderer, MeshType, Parent, PointLight, PreviousTransform, RigidBody,
+                 Transform};
+use crate::events::{CollisionEvent, ContactTracker};
+use crate::hierarchy::TransformSync;
+use crate::log;
+use crate::raycast::QueryTree;
+use crate::schedule::{FixedTime, Schedule, Stage};
+use crate::systems;
+use crate::trimesh::TriMesh;
+use crate::scene::{SceneCameraComponent, SceneCharacterController, SceneData, SceneDirectionalLight, SceneEntityData, SceneJoint, SceneMaterial,
+                   ScenePointLight, SceneRigidBody, SceneRotation, SceneTransform};
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use glam::Mat4;
+
+/// Rotation caméra par pixel de souris (radians), cf. `set_mouse_sensitivity`.
+const DEFAULT_MOUSE_SENSITIVITY: f32 = 0.002;
+/// Gravité du monde (m/s²), cf. `set_gravity`.
+const DEFAULT_GRAVITY: glam::Vec3 = glam::Vec3::new(0.0, -9.8, 0.0);
+
+#[derive(Default)]
+pub(crate) struct InputState {
+    pub(crate) keys:     u32,
+    pub(crate) mouse_dx: f32,
+    pub(crate) mouse_dy: f32,
+}
+
+/// Données CPU pour la lumière directionnelle unique.
+pub(crate) struct DirectionalLightData {
+    pub(crate) direction:      glam::Vec3,
+    pub(crate) color:          glam::Vec3,
+    pub(crate) intensity:      f32,
+    pub(crate) cone_angle_deg: f32,  // demi-angle extérieur du cône en degrés
+}
+
+/// Données CPU d'un mesh custom (les buffers GPU restent côté renderer).
+pub(crate) struct CustomMeshData {
+    pub(crate) local_half_extents: glam::Vec3,
+    // Triangles gardés pour `add_mesh_collider` ; maillage de collision construit au premier usage.
+    pub(crate) positions: Vec<glam::Vec3>,
+    pub(crate) indices:   Vec<u32>,
+    pub(crate) collision: Option<Arc<TriMesh>>,
+}
+
+/// Monde de simulation sans GPU.
+pub struct WorldCore {
+    pub(crate) entities:   EntityAllocator,
+
+    // ECS : un SparseSet par type de composant enregistré
+    pub(crate) components: Components,
+
+    // Input + caméra FPS
+    pub(crate) input:             InputState,
+    pub(crate) mouse_sensitivity: f32,   // radians/pixel
+    pub(crate) camera_yaw:     f32,   // radians — rotation horizontale
+    pub(crate) camera_pitch:   f32,   // radians — rotation verticale, clampé ±89°
+
+    // Éclairage
+    pub(crate) directional_light:        Option<DirectionalLightData>,
+    pub(crate) directional_light_entity: Option<Entity>,  // entité dont la rotation pilote la direction
+
+    // Scènes
+    pub(crate) persistent_entities: HashSet<Entity>,
+    pub(crate) texture_registry:    HashMap<String, u32>,
+    pub(crate) entity_names:        HashMap<Entity, String>,
+    pub(crate) tags:                HashMap<Entity, String>,
+    pub(crate) custom_meshes:       Vec<CustomMeshData>,
+
+    // Camera entities
+    pub(crate) active_camera:  Option<Entity>,
+    pub(crate) preview_camera: Option<Entity>,  // shows inset preview in editor (no game mode needed)
+    pub(crate) is_game_mode:   bool,   // true = Play mode; false = Editor mode (orbital camera)
+
+    // Systèmes par étape (cf. schedule.rs)
+    pub(crate) schedule:   Schedule,
+    pub(crate) fixed_time: FixedTime,
+
+    // Cache des matrices monde (cf. hierarchy.rs)
+    pub(crate) transform_sync: TransformSync,
+
+    // Colliders rangés pour raycast / shape casts, reconstruits après un changement (cf. raycast.rs)
+    pub(crate) query_tree: RefCell<Option<QueryTree>>,
+
+    // Contacts du pas fixe + file d'événements (cf. events.rs)
+    pub(crate) contacts: ContactTracker,
+
+    // Contacts du pas en attente d'impulsions (cf. systems::solve_contacts)
+    pub(crate) pending_contacts: Vec<systems::PendingContact>,
+
+    // Accélération appliquée aux corps dynamiques, × `RigidBody::gravity_scale`
+    pub(crate) gravity: glam::Vec3,
+}
+
+impl Default for WorldCore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorldCore {
+    pub fn new() -> Self {
+        WorldCore {
+            entities:       EntityAllocator::new(),
+            components:     Self::builtin_components(),
+            input:          InputState::default(),
+            mouse_sensitivity: DEFAULT_MOUSE_SENSITIVITY,
+            camera_yaw:     0.0,
+            camera_pitch:   0.0,
+            directional_light:        None,
+            directional_light_entity: None,
+            persistent_entities: HashSet::new(),
+            texture_registry:    HashMap::new(),
+            entity_names:        HashMap::new(),
+            tags:                HashMap::new(),
+            custom_meshes:       Vec::new(),
+            active_camera:  None,
+            preview_camera: None,
+            is_game_mode:   false,
+            schedule:       Self::builtin_schedule(),
+            fixed_time:     FixedTime::default(),
+            transform_sync: TransformSync::default(),
+            query_tree:     RefCell::new(None),
+            contacts:       ContactTracker::default(),
+            pending_contacts: Vec::new(),
+            gravity:        DEFAULT_GRAVITY,
+        }
+    }
+
+    /// Registre initial : composants intégrés. Leur format de scène reste explicite
+    /// (`SceneEntityData`), d'où `register` et non `register_serialized`.
+    fn builtin_components() -> Components {
+        let mut c = Components::new();
+        c.register::<Transform>();
+        c.register::<PreviousTransform>();
+        c.register::<GlobalTransform>();
+        c.register::<MeshRenderer>();
+        c.register::<Material>();
+        c.register::<RigidBody>();
+        c.register::<Collider>();
+        c.register::<CharacterController>();
+        c.register::<Joint>();
+        c.register::<PointLight>();
+        c.register::<Parent>();
+        c.register::<CameraComponent>();
+        c
+    }
+
+    fn builtin_schedule() -> Schedule {
+        let mut schedule = Schedule::new();
+        systems::register_builtin(&mut schedule);
+        schedule
+    }
+
+    /// Registre des composants, pour enregistrer et manipuler des types définis par le jeu.
+    pub fn components(&self) -> &Components {
+        &self.components
+    }
+
+    pub fn components_mut(&mut self) -> &mut Components {
+        &mut self.components
+    }
+
+    // ── Entités ──────────────────────────────────────────────────────────────
+
+    /// Crée une entité vide. Retourne son handle générationnel.
+    pub fn create_entity(&mut self) -> Entity {
+        self.entities.create()
+    }
+
+    /// true si le handle désigne une entité vivante (génération à jour).
+    pub fn is_alive(&self, id: Entity) -> bool {
+        self.entities.is_alive(id)
+    }
+
+    /// Convertit un handle packé (`Entity::to_bits`) en entité vivante, None si mort/invalide.
+    pub fn resolve(&self, bits: u32) -> Option<Entity> {
+        self.entities.resolve(bits)
+    }
+
+    /// Nombre d'entités vivantes.
+    pub fn live_entity_count(&self) -> usize {
+        self.entities.live_count()
+    }
+
+    /// Nombre de slots d'entités morts (recyclables ou retirés).
+    pub fn dead_entity_count(&self) -> usize {
+        self.entities.dead_count()
+    }
+
+    // ── API Éditeur ───────────────────────────────────────────────────────────
+
+    /// Retourne le nom de l'entité (défaut: "Entity {id}").
+    pub fn get_entity_name(&self, id: Entity) -> String {
+        self.entity_names
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| format!("Entity {}", id.index()))
+    }
+
+    /// Définit le nom d'une entité.
+    pub fn set_entity_name(&mut self, id: Entity, name: String) {
+        self.entity_names.insert(id, name);
+    }
+
+    // ── Tags ──────────────────────────────────────────────────────────────────
+
+    /// Assigne un tag string à une entité. Remplace le tag précédent s'il en avait un.
+    pub fn set_tag(&mut self, id: Entity, tag: &str) {
+        self.tags.insert(id, tag.to_string());
+    }
+
+    /// Retourne le premier ID d'entité ayant ce tag, ou None si aucun.
+    pub fn get_entity_by_tag(&self, tag: &str) -> Option<Entity> {
+        self.tags
+            .iter()
+            .find(|(_, t)| t.as_str() == tag)
+            .map(|(&id, _)| id)
+    }
+
+    /// Retourne le tag d'une entité ("" si aucun tag assigné).
+    pub fn get_tag(&self, id: Entity) -> String {
+        self.tags.get(&id).cloned().unwrap_or_default()
+    }
+
+    /// Supprime une entité, ses descendants et tous leurs composants.
+    /// Panique si le handle est mort (les appels wasm sont validés en amont).
+    pub fn remove_entity(&mut self, id: Entity) {
+        assert!(self.entities.is_alive(id), "remove_entity: dead entity handle {id:?}");
+
+        // Collecter l'entité + tous ses descendants (BFS) avant toute suppression.
+        let mut to_delete: Vec<Entity> = vec![id];
+        let mut head = 0;
+        while head < to_delete.len() {
+            let current = to_delete[head];
+            let children: Vec<Entity> = self.components.storage::<Parent>().iter()
+                .filter(|(_, p)| p.parent_id == current)
+                .map(|(cid, _)| cid)
+                .collect();
+            to_delete.extend(children);
+            head += 1;
+        }
+
+        // Retirer également l'entrée parent de l'entité racine supprimée
+        // (si elle-même était enfant d'un autre parent).
+        self.components.remove::<Parent>(id);
+
+        // Les joints qui visent une entité supprimée disparaissent avec elle.
+        self.drop_joints_to(|target| to_delete.contains(&target));
+
+        // Ce qui reposait contre elles retombe ; leurs contacts sortent au prochain pas.
+        for &eid in &to_delete {
+            self.wake_up(eid);
+            self.contacts.forget(eid);
+        }
+
+        // Supprimer tous les composants pour chaque entité collectée.
+        for eid in to_delete {
+            self.components.remove_entity(eid);
+            self.entity_names.remove(&eid);
+            self.tags.remove(&eid);
+            self.persistent_entities.remove(&eid);
+            if self.active_camera == Some(eid) { self.active_camera = None; }
+            if self.preview_camera == Some(eid) { self.preview_camera = None; }
+            if self.directional_light_entity == Some(eid) {
+                self.directional_light = None;
+                self.directional_light_entity = None;
+            }
+            self.entities.kill(eid);
+        }
+    }
+
+    /// Retourne true si l'entité a un MeshRenderer.
+    pub fn has_mesh_renderer(&self, id: Entity) -> bool {
+        self.components.get::<MeshRenderer>(id).is_some()
+    }
+
+    /// Liste les IDs de toutes les entités qui ont un Transform.
+    pub fn get_entity_ids(&self) -> Vec<Entity> {
+        self.components.storage::<Transform>().iter_ids().collect()
+    }
+
+    /// Tick de la dernière modification d'un Transform (ajout, mutation ou retrait).
+    pub fn transform_change_tick(&self) -> u64 {
+        self.components.storage::<Transform>().change_tick()
+    }
+
+    /// Entités dont le Transform a été ajouté ou modifié après `tick`.
+    pub fn transforms_changed_since(&self, tick: u64) -> Vec<Entity> {
+        self.components.storage::<Transform>().changed_since(tick).collect()
+    }
+
+    /// Vide la file des événements de collision / trigger, dans l'ordre d'émission (cf. events.rs).
+    pub fn drain_collision_events(&mut self) -> Vec<CollisionEvent> {
+        self.contacts.drain().collect()
+    }
+
+    /// Retourne [px, py, pz, rx, ry, rz, sx, sy, sz] pour l'entité.
+    /// Retourne 9 zéros si l'entité n'a pas de Transform.
+    pub fn get_transform_array(&self, id: Entity) -> [f32; 9] {
+        if let Some(t) = self.components.get::<Transform>(id) {
+            let euler = t.euler_deg();
+            [
+                t.position.x, t.position.y, t.position.z,
+                euler.x,      euler.y,      euler.z,
+                t.scale.x,    t.scale.y,    t.scale.z,
+            ]
+        } else {
+            [0f32; 9]
+        }
+    }
+
+    // ── Hiérarchie parent-enfant ─────────────────────────────────────────────
+
+    /// Définit parent_id comme parent de child_id.
+    /// Convertit le world transform actuel de child en local relatif à parent.
+    /// Ignoré si la relation créerait un cycle (ou auto-parent).
+    pub fn set_parent(&mut self, child_id: Entity, parent_id: Entity) {
+        // Prevent self-parenting.
+        if child_id == parent_id { return; }
+        // Prevent cycles: walk up from parent_id; if we ever reach child_id it's a cycle.
+        {
+            let mut cur = parent_id;
+            loop {
+                if cur == child_id { return; } // cycle detected
+                match self.components.get::<Parent>(cur) {
+                    Some(p) => cur = p.parent_id,
+                    None    => break,
+                }
+            }
+        }
+        let child_world  = self.world_matrix(child_id);
+        let parent_world = self.world_matrix(parent_id);
+        let local_mat    = parent_world.inverse() * child_world;
+
+        let (scale, rotation, translation) = local_mat.to_scale_rotation_translation();
+        if let Some(t) = self.components.get_mut::<Transform>(child_id) {
+            t.position = translation;
+            t.rotation = rotation;
+            t.scale    = scale;
+        }
+        self.components.insert(child_id, Parent { parent_id });
+    }
+
+    /// Retire le parent de child_id.
+    /// Convertit le local transform en world transform.
+    pub fn remove_parent(&mut self, child_id: Entity) {
+        let world_mat = self.world_matrix(child_id);
+        let (scale, rotation, translation) = world_mat.to_scale_rotation_translation();
+        if let Some(t) = self.components.get_mut::<Transform>(child_id) {
+            t.position = translation;
+            t.rotation = rotation;
+            t.scale    = scale;
+        }
+        self.components.remove::<Parent>(child_id);
+    }
+
+    /// Retourne l'ID du parent, ou None si pas de parent.
+    pub fn get_parent(&self, child_id: Entity) -> Option<Entity> {
+        self.components.get::<Parent>(child_id).map(|p| p.parent_id)
+    }
+
+    /// Retourne les IDs des enfants directs de parent_id.
+    pub fn get_children(&self, parent_id: Entity) -> Vec<Entity> {
+        self.components.storage::<Parent>().iter()
+            .filter(|(_, p)| p.parent_id == parent_id)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Retourne [px, py, pz, rx, ry, rz, sx, sy, sz] en espace monde.
+    pub fn get_world_transform_array(&self, id: Entity) -> [f32; 9] {
+        let mat = self.world_matrix(id);
+        let (scale, rotation, translation) = mat.to_scale_rotation_translation();
+        let euler = Transform::euler_deg_from_quat(rotation);
+        [
+            translation.x, translation.y, translation.z,
+            euler.x, euler.y, euler.z,
+            scale.x, scale.y, scale.z,
+        ]
+    }
+
+    /// Interne: définit le parent SANS convertir les transforms.
+    /// Utilisé par load_scene — les transforms locaux sont déjà dans le JSON.
+    fn set_parent_raw(&mut self, child_id: Entity, parent_id: Entity) {
+        self.components.insert(child_id, Parent { parent_id });
+    }
+
+    /// Calcule la matrice world de l'entité en remontant la chaîne de parents (sans cache).
+    /// Les entités racines (sans parent) retournent directement leur matrix locale.
+    /// Préférer `world_matrix`, qui lit le `GlobalTransform` en cache.
+    pub fn compute_world_matrix(&self, id: Entity) -> Mat4 {
+        let local = self.components.get::<Transform>(id)
+            .map(Transform::matrix)
+            .unwrap_or(Mat4::IDENTITY);
+
+        if let Some(parent) = self.components.get::<Parent>(id) {
+            self.compute_world_matrix(parent.parent_id) * local
+        } else {
+            local
+        }
+    }
+
+    /// Comme `compute_world_matrix`, mais interpole les corps physiques entre les deux
+    /// derniers pas fixes (`PreviousTransform` → `Transform`, poids `FixedTime::alpha`).
+    /// À utiliser pour le rendu uniquement.
+    pub fn compute_render_matrix(&self, id: Entity) -> Mat4 {
+        let local = match (
+            self.components.get::<PreviousTransform>(id),
+            self.components.get::<Transform>(id),
+        ) {
+            (Some(prev), Some(t)) => prev.0.lerp_matrix(t, self.fixed_time.alpha()),
+            (None, Some(t))       => t.matrix(),
+            _                     => Mat4::IDENTITY,
+        };
+
+        if let Some(parent) = self.components.get::<Parent>(id) {
+            self.compute_render_matrix(parent.parent_id) * local
+        } else {
+            local
+        }
+    }
+
+    // ── Transform ────────────────────────────────────────────────────────────
+
+    /// Ajoute un composant Transform à l'entité (position initiale xyz).
+    pub fn add_transform(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        self.components.insert(id, Transform {
+            position: glam::Vec3::new(x, y, z),
+            ..Transform::default()
+        });
+        self.components.remove::<PreviousTransform>(id);
+    }
+
+    pub fn set_position(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        let v = glam::Vec3::new(x, y, z);
+        self.wake_up(id);
+        if let Some(t) = self.components.get_mut::<Transform>(id) {
+            t.position = v;
+        }
+        // Déplacement explicite (téléportation) : le rendu ne doit pas interpoler depuis l'ancien état.
+        if let Some(prev) = self.components.get_mut::<PreviousTransform>(id) {
+            prev.0.position = v;
+        }
+    }
+
+    /// Rotation en angles d'Euler XYZ (degrés), stockée en quaternion.
+    pub fn set_rotation(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        let v = Transform::quat_from_euler_deg(glam::Vec3::new(x, y, z));
+        self.wake_up(id);
+        if let Some(t) = self.components.get_mut::<Transform>(id) {
+            t.rotation = v;
+        }
+        if let Some(prev) = self.components.get_mut::<PreviousTransform>(id) {
+            prev.0.rotation = v;
+        }
+    }
+
+    pub fn set_scale(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        let v = glam::Vec3::new(x, y, z);
+        self.wake_up(id);
+        if let Some(t) = self.components.get_mut::<Transform>(id) {
+            t.scale = v;
+        }
+        if let Some(prev) = self.components.get_mut::<PreviousTransform>(id) {
+            prev.0.scale = v;
+        }
+    }
+
+    /// Déplace l'entité vers une position en espace MONDE.
+    /// Convertit automatiquement en espace local si l'entité a un parent.
+    pub fn set_world_position(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        let target = glam::Vec3::new(x, y, z);
+        self.wake_up(id);
+        // Extraire le parent_id sans garder d'emprunt sur self
+        let parent_id = self.components.get::<Parent>(id).map(|p| p.parent_id);
+        let local = if let Some(pid) = parent_id {
+            let parent_world = self.world_matrix(pid);
+            parent_world.inverse().transform_point3(target)
+        } else {
+            target
+        };
+        if let Some(t) = self.components.get_mut::<Transform>(id) {
+            t.position = local;
+        }
+        if let Some(prev) = self.components.get_mut::<PreviousTransform>(id) {
+            prev.0.position = local;
+        }
+    }
+
+    // ── MeshRenderer ─────────────────────────────────────────────────────────
+
+    /// Ajoute un MeshRenderer Cube (les ressources GPU sont gérées par le renderer).
+    pub fn add_mesh_renderer(&mut self, id: Entity) {
+        self.components.insert(id, MeshRenderer { mesh_type: MeshType::Cube });
+    }
+
+    /// Enregistre la partie CPU d'un mesh custom. vertices: flat f32 array (15 per vertex), indices: u32 array.
+    /// Returns custom mesh index for use with set_mesh_type("custom:N").
+    pub fn add_custom_mesh(&mut self, vertices: &[f32], indices: &[u32]) -> usize {
+        let local_half_extents = if vertices.len() >= 15 {
+            let mut min = glam::Vec3::splat(f32::INFINITY);
+            let mut max = glam::Vec3::splat(f32::NEG_INFINITY);
+            for i in (0..vertices.len()).step_by(15) {
+                let p = glam::Vec3::new(vertices[i], vertices[i + 1], vertices[i + 2]);
+                min = min.min(p);
+                max = max.max(p);
+            }
+            (max - min) * 0.5
+        } else {
+            glam::Vec3::splat(0.5)
+        };
+
+        let positions = vertices.chunks_exact(15).map(|v| glam::Vec3::new(v[0], v[1], v[2])).collect();
+
+        let idx = self.custom_meshes.len();
+        self.custom_meshes.push(CustomMeshData {
+            local_half_extents,
+            positions,
+            indices: indices.to_vec(),
+            collision: None,
+        });
+        idx
+    }
+
+    /// Change le type de mesh d'une entité existante.
+    pub fn set_mesh_type(&mut self, id: Entity, mesh_type: &str) {
+        let mt = if let Some(n) = mesh_type.strip_prefix("custom:") {
+            MeshType::Custom(n.parse().unwrap_or(0))
+        } else {
+            match mesh_type {
+                "plane"    => MeshType::Plane,
+                "sphere"   => MeshType::Sphere,
+                "cylinder" => MeshType::Cylinder,
+                _          => MeshType::Cube,
+            }
+        };
+        if let Some(mr) = self.components.get_mut::<MeshRenderer>(id) {
+            mr.mesh_type = mt;
+        }
+    }
+
+    /// Retourne le type de mesh d'une entité ("cube" | "plane" | "sphere" | "cylinder" | "custom:N").
+    pub fn get_mesh_type(&self, id: Entity) -> String {
+        match self.components.get::<MeshRenderer>(id) {
+            Some(mr) => match &mr.mesh_type {
+                MeshType::Cube       => "cube".to_string(),
+                MeshType::Plane      => "plane".to_string(),
+                MeshType::Custom(n)  => format!("custom:{}", n),
+                MeshType::Sphere     => "sphere".to_string(),
+                MeshType::Cylinder   => "cylinder".to_string(),
+            },
+            None => "cube".to_string(),
+        }
+    }
+
+    // ── Caméra ───────────────────────────────────────────────────────────────
+
+    pub fn add_camera(&mut self, id: Entity, fov: f32, near: f32, far: f32) {
+        // Keep camera projection stable even when UI sends temporary invalid values.
+        let fov  = fov.clamp(1.0, 179.0);
+        let near = near.max(0.001);
+        let far  = far.max(near + 0.001);
+        // Preserve follow mode across camera updates (fov/near/far edits).
+        let follow_entity = self.components.get::<CameraComponent>(id).map(|c| c.follow_entity).unwrap_or(false);
+        self.components.insert(id, CameraComponent { fov, near, far, follow_entity });
+    }
+
+    pub fn set_camera_follow_entity(&mut self, id: Entity, follow_entity: bool) {
+        if let Some(cam) = self.components.get_mut::<CameraComponent>(id) {
+            cam.follow_entity = follow_entity;
+        }
+    }
+
+    pub fn set_active_camera(&mut self, id: Entity) {
+        if self.components.get::<CameraComponent>(id).is_some() && self.components.get::<Transform>(id).is_some() {
+            self.active_camera = Some(id);
+        }
+    }
+
+    pub fn remove_active_camera(&mut self) {
+        self.active_camera = None;
+    }
+
+    /// Supprime le composant Camera d'une entité (sans supprimer l'entité elle-même).
+    pub fn remove_camera(&mut self, id: Entity) {
+        self.components.remove::<CameraComponent>(id);
+        if self.active_camera  == Some(id) { self.active_camera  = None; }
+        if self.preview_camera == Some(id) { self.preview_camera = None; }
+    }
+
+    /// Prévisualise cette caméra dans le viewport de l'éditeur (sans activer le game mode).
+    pub fn set_preview_camera(&mut self, id: Entity) {
+        // Keep preview resilient to transient editor/engine desync:
+        // a valid transform is enough (projection falls back to default camera params).
+        if self.components.get::<Transform>(id).is_some() {
+            self.preview_camera = Some(id);
+        }
+    }
+
+    pub fn clear_preview_camera(&mut self) {
+        self.preview_camera = None;
+    }
+
+    /// Switch between game mode (Play) and editor mode.
+    /// In editor mode, the active_camera entity is ignored — orbital camera is always used.
+    pub fn set_game_mode(&mut self, enabled: bool) {
+        self.is_game_mode = enabled;
+        if enabled {
+            self.sync_look_from_active_camera();
+        }
+    }
+
+    /// Align internal yaw/pitch state to the current active camera world orientation.
+    /// This prevents "snap" on Play start when follow_entity camera control is enabled.
+    fn sync_look_from_active_camera(&mut self) {
+        let Some(cam_id) = self.active_camera else { return };
+        let follow = self.components.get::<CameraComponent>(cam_id).map(|c| c.follow_entity).unwrap_or(false);
+        if !follow {
+            return;
+        }
+
+        let world = self.world_matrix(cam_id);
+        let forward = -(world.col(2).truncate().normalize_or_zero());
+        if forward.length_squared() < 1e-6 {
+            return;
+        }
+
+        self.camera_yaw = forward.x.atan2(-forward.z);
+        self.camera_pitch = forward
+            .y
+            .asin()
+            .clamp(-89.0_f32.to_radians(), 89.0_f32.to_radians());
+    }
+
+    // ── Matériaux ─────────────────────────────────────────────────────────────
+
+    /// Rétrocompatibilité Phase 1-5. Utilise add_pbr_material pour le PBR.
+    pub fn add_material(&mut self, entity_id: Entity, texture_id: u32) {
+        self.components.insert(entity_id, Material {
+            albedo_tex: texture_id,
+            normal_tex: u32::MAX,
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: glam::Vec3::ZERO,
+        });
+    }
+
+    /// Associe un matériau PBR complet à l'entité.
+    pub fn add_pbr_material(
+        &mut self,
+        entity_id: Entity,
+        albedo_tex: u32,
+        metallic:   f32,
+        roughness:  f32,
+    ) {
+        self.components.insert(entity_id, Material {
+            albedo_tex,
+            normal_tex: u32::MAX,
+            metallic,
+            roughness,
+            emissive: glam::Vec3::ZERO, // Par défaut, n'émet pas de lumière
+        });
+    }
+
+    /// Rend un objet émissif (ex: ampoule, néon).
+    /// r,g,b > 1.0 permet de faire du "bloom" si on avait du post-process,
+    /// ici cela garantit juste une couleur très vive.
+    pub fn set_emissive(&mut self, entity_id: Entity, r: f32, g: f32, b: f32) {
+        if let Some(mat) = self.components.get_mut::<Material>(entity_id) {
+            mat.emissive = glam::Vec3::new(r, g, b);
+        }
+    }
+
+    /// Applique une normal map à l'entité (doit avoir un Material).
+    pub fn set_normal_map(&mut self, entity_id: Entity, normal_tex_id: u32) {
+        if let Some(mat) = self.components.get_mut::<Material>(entity_id) {
+            mat.normal_tex = normal_tex_id;
+        }
+    }
+
+    // ── Éclairage ────────────────────────────────────────────────────────────
+
+    /// Ajoute une point light attachée à l'entité (doit avoir un Transform).
+    /// Couleur (r, g, b) entre 0.0 et 1.0, intensity en lux (ex: 5.0–20.0).
+    pub fn add_point_light(&mut self, id: Entity, r: f32, g: f32, b: f32, intensity: f32) {
+        self.components.insert(id, PointLight {
+            color:     glam::Vec3::new(r, g, b),
+            intensity,
+        });
+    }
+
+    /// Supprime la point light de l'entité (sans supprimer l'entité elle-même).
+    pub fn remove_point_light(&mut self, id: Entity) {
+        self.components.remove::<PointLight>(id);
+    }
+
+    /// Définit la lumière directionnelle (soleil). Un seul appel suffit.
+    /// direction (dx, dy, dz) : vecteur vers lequel la lumière pointe (normalisé automatiquement).
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_directional_light(
+        &mut self,
+        dx: f32, dy: f32, dz: f32,
+        r: f32, g: f32, b: f32,
+        intensity: f32,
+    ) {
+        self.directional_light = Some(DirectionalLightData {
+            direction:      glam::Vec3::new(dx, dy, dz),
+            color:          glam::Vec3::new(r, g, b),
+            intensity,
+            cone_angle_deg: 30.0,
+        });
+    }
+
+    /// Ajoute une lumière directionnelle (spotlight) pilotée par l'entité `id`.
+    /// `cone_angle_deg` : demi-angle extérieur du cône en degrés (ex: 30.0).
+    pub fn add_directional_light_entity(&mut self, id: Entity, r: f32, g: f32, b: f32, intensity: f32, cone_angle_deg: f32) {
+        self.directional_light = Some(DirectionalLightData {
+            direction:      glam::Vec3::new(0.0, -1.0, 0.0),
+            color:          glam::Vec3::new(r, g, b),
+            intensity,
+            cone_angle_deg: cone_angle_deg.clamp(1.0, 89.0),
+        });
+        self.directional_light_entity = Some(id);
+    }
+
+    /// Lie une entité existante à la lumière directionnelle (sa rotation pilote la direction).
+    pub fn set_directional_light_entity(&mut self, id: Entity) {
+        self.directional_light_entity = Some(id);
+    }
+
+    /// Supprime la lumière directionnelle globale (la scène n'en aura plus).
+    pub fn remove_directional_light(&mut self) {
+        self.directional_light = None;
+        self.directional_light_entity = None;
+    }
+
+    // ── Physique ─────────────────────────────────────────────────────────────
+
+    /// Désigne une entité joueur : lui ajoute un CharacterController par défaut s'il n'en a pas,
+    /// piloté par l'input et orienté par le yaw de la caméra.
+    pub fn set_player(&mut self, id: Entity) {
+        let current = self.components.get::<CharacterController>(id).copied().unwrap_or_default();
+        self.components.insert(id, CharacterController { player: true, ..current });
+    }
+
+    /// Intention d'un CharacterController non joueur : direction de marche (x, z) en monde,
+    /// ramenée à une longueur ≤ 1 (fraction de `speed`), gardée jusqu'au prochain appel ;
+    /// `jump` demande un saut au pas suivant. Sans effet sans CharacterController.
+    pub fn set_character_intent(&mut self, id: Entity, x: f32, z: f32, jump: bool) {
+        if let Some(cc) = self.components.get_mut::<CharacterController>(id) {
+            cc.intent = glam::Vec3::new(x, 0.0, z).clamp_length_max(1.0);
+            cc.jump_request |= jump;
+        }
+    }
+
+    /// Règle le CharacterController de l'entité (ajouté s'il manque). Vitesses et durées
+    /// négatives ramenées à 0, `air_control` borné à [0, 1], `slope_limit` (degrés) à [0, 89].
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_character_controller(
+        &mut self,
+        id: Entity,
+        speed: f32,
+        acceleration: f32,
+        air_control: f32,
+        jump_height: f32,
+        step_height: f32,
+        slope_limit: f32,
+        coyote_time: f32,
+        jump_buffer: f32,
+    ) {
+        let current = self.components.get::<CharacterController>(id).copied().unwrap_or_default();
+        self.components.insert(id, CharacterController {
+            speed:        speed.max(0.0),
+            acceleration: acceleration.max(0.0),
+            air_control:  air_control.clamp(0.0, 1.0),
+            jump_height:  jump_height.max(0.0),
+            step_height:  step_height.max(0.0),
+            slope_limit:  slope_limit.clamp(0.0, 89.0),
+            coyote_time:  coyote_time.max(0.0),
+            jump_buffer:  jump_buffer.max(0.0),
+            ..current
+        });
+    }
+
+    /// Retourne [speed, acceleration, air_control, jump_height, step_height, slope_limit,
+    /// coyote_time, jump_buffer], ou des zéros si l'entité n'a pas de CharacterController.
+    pub fn get_character_controller(&self, id: Entity) -> [f32; 8] {
+        self.components.get::<CharacterController>(id).map_or([0.0; 8], |c| [
+            c.speed, c.acceleration, c.air_control, c.jump_height,
+            c.step_height, c.slope_limit, c.coyote_time, c.jump_buffer,
+        ])
+    }
+
+    pub fn remove_character_controller(&mut self, id: Entity) {
+        self.components.remove::<CharacterController>(id);
+    }
+
+    /// Sensibilité de la souris (radians par pixel) pour la rotation caméra.
+    pub fn set_mouse_sensitivity(&mut self, radians_per_pixel: f32) {
+        self.mouse_sensitivity = radians_per_pixel;
+    }
+
+    /// Ajoute un RigidBody. `is_static = true` pour les entités fixes (sol, murs).
+    /// Remplace un RigidBody existant en conservant sa masse, son matériau, le réglage CCD,
+    /// ses amortissements et son échelle de gravité.
+    pub fn add_rigid_body(&mut self, id: Entity, is_static: bool) {
+        let body_type = if is_static { BodyType::Static } else { BodyType::Dynamic };
+        let mut rb = RigidBody { body_type, ..RigidBody::default() };
+        self.wake_up(id);
+        if let Some(old) = self.components.get::<RigidBody>(id) {
+            (rb.mass, rb.restitution, rb.friction, rb.ccd) = (old.mass, old.restitution, old.friction, old.ccd);
+            (rb.linear_damping, rb.angular_damping, rb.gravity_scale) = (old.linear_damping, old.angular_damping, old.gravity_scale);
+        }
+        self.components.insert(id, rb);
+        if is_static {
+            self.components.remove::<PreviousTransform>(id);
+        }
+    }
+
+    /// Change le type d'un RigidBody existant (cf. `BodyType`).
+    pub fn set_body_type(&mut self, id: Entity, body_type: BodyType) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.body_type        = body_type;
+            rb.settled_position = None;
+            rb.step_motion      = glam::Vec3::ZERO;
+        }
+        // Un statique n'est plus capturé par `snapshot_previous` : le rendu interpolerait
+        // indéfiniment depuis son dernier état dynamique.
+        if body_type == BodyType::Static {
+            self.components.remove::<PreviousTransform>(id);
+        }
+    }
+
+    /// Active la détection continue (CCD) d'un RigidBody dynamique : pas de traversée
+    /// des statiques / kinematics, quelle que soit la vitesse.
+    pub fn set_ccd(&mut self, id: Entity, enabled: bool) {
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.ccd = enabled;
+        }
+    }
+
+    /// Masse (kg, bornée à `RigidBody::MIN_MASS`), restitution (0..1) et friction (≥ 0) d'un RigidBody.
+    pub fn set_rigid_body_material(&mut self, id: Entity, mass: f32, restitution: f32, friction: f32) {
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.mass        = mass.max(RigidBody::MIN_MASS);
+            rb.restitution = restitution.clamp(0.0, 1.0);
+            rb.friction    = friction.max(0.0);
+        }
+    }
+
+    /// Retourne [mass, restitution, friction] d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_rigid_body_material(&self, id: Entity) -> [f32; 3] {
+        self.components.get::<RigidBody>(id)
+            .map(|rb| [rb.mass, rb.restitution, rb.friction])
+            .unwrap_or([0.0; 3])
+    }
+
+    /// Ajoute un Collider boîte (demi-extents locaux en mètres). Centre = Transform.position, orienté par la rotation.
+    pub fn add_collider_aabb(&mut self, id: Entity, hx: f32, hy: f32, hz: f32) {
+        self.set_collider_shape(id, ColliderShape::Box {
+            half_extents: glam::Vec3::new(hx, hy, hz),
+        });
+    }
+
+    /// Ajoute un Collider sphère (rayon en mètres). Centre = Transform.position.
+    pub fn add_collider_sphere(&mut self, id: Entity, radius: f32) {
+        self.set_collider_shape(id, ColliderShape::Sphere { radius });
+    }
+
+    /// Ajoute un Collider capsule d'axe Y local. Hauteur totale = 2 * (half_height + radius).
+    pub fn add_collider_capsule(&mut self, id: Entity, radius: f32, half_height: f32) {
+        self.set_collider_shape(id, ColliderShape::Capsule { radius, half_height });
+    }
+
+    /// Remplace la forme du collider ; un collider existant garde son flag `is_trigger` et ses couches.
+    fn set_collider_shape(&mut self, id: Entity, shape: ColliderShape) {
+        let collider = match self.components.get::<Collider>(id) {
+            Some(old) => Collider { shape, is_trigger: old.is_trigger, layer: old.layer, mask: old.mask },
+            None      => Collider::new(shape),
+        };
+        self.components.insert(id, collider);
+    }
+
+    /// Marque le collider comme trigger (chevauchement détecté, jamais résolu). Sans effet sans Collider.
+    pub fn set_collider_trigger(&mut self, id: Entity, is_trigger: bool) {
+        if let Some(c) = self.components.get_mut::<Collider>(id) {
+            c.is_trigger = is_trigger;
+        }
+    }
+
+    /// Couches du collider (`layer`, un bit par couche) et couches avec lesquelles il entre en
+    /// contact ou déclenche un trigger (`mask`). Sans effet sans Collider.
+    pub fn set_collider_layers(&mut self, id: Entity, layer: u32, mask: u32) {
+        if let Some(c) = self.components.get_mut::<Collider>(id) {
+            c.layer = layer;
+            c.mask  = mask;
+        }
+    }
+
+    /// Retourne [layer, mask] du collider, ou [0, 0] si absent.
+    pub fn get_collider_layers(&self, id: Entity) -> [u32; 2] {
+        self.components.get::<Collider>(id).map_or([0; 2], |c| [c.layer, c.mask])
+    }
+
+    /// Retourne [hx, hy, hz] de la boîte englobante locale du collider, ou [0,0,0] si absent.
+    pub fn get_collider_array(&self, id: Entity) -> [f32; 3] {
+        self.components.get::<Collider>(id)
+            .map(|c| c.shape.half_extents().to_array())
+            .unwrap_or([0.0; 3])
+    }
+
+    /// Ajuste automatiquement le Box Collider à la taille du mesh visuel.
+    /// `min_half_y` évite un collider trop fin (utile pour les planes).
+    pub fn fit_collider_to_mesh(&mut self, id: Entity, min_half_y: f32) {
+        let Some(mr) = self.components.get::<MeshRenderer>(id) else { return };
+        let min_y = min_half_y.max(0.001);
+        let he = match &mr.mesh_type {
+            MeshType::Cube     => glam::Vec3::new(0.5, 0.5, 0.5),
+            MeshType::Plane    => glam::Vec3::new(0.5, min_y, 0.5),
+            MeshType::Sphere   => glam::Vec3::new(0.5, 0.5, 0.5),
+            MeshType::Cylinder => glam::Vec3::new(0.5, 0.5, 0.5),
+            MeshType::Custom(n) => self.custom_meshes
+                .get(*n)
+                .map(|cm| glam::Vec3::new(
+                    cm.local_half_extents.x.max(0.001),
+                    cm.local_half_extents.y.max(min_y),
+                    cm.local_half_extents.z.max(0.001),
+                ))
+                .unwrap_or(glam::Vec3::new(0.5, 0.5, 0.5)),
+        };
+
+        self.set_collider_shape(id, ColliderShape::Box { half_extents: he });
+    }
+
+    /// Collider maillage : les triangles du mesh custom de l'entité, pour les statiques et
+    /// kinematics (sols, rampes, escaliers). Retourne false si l'entité n'a pas de mesh
+    /// custom ou si celui-ci n'a aucun triangle.
+    pub fn add_mesh_collider(&mut self, id: Entity) -> bool {
+        let Some(shape) = self.mesh_collider_shape(id) else { return false };
+        self.set_collider_shape(id, shape);
+        true
+    }
+
+    /// Forme maillage du mesh custom de l'entité (BVH construit au premier appel pour ce mesh).
+    fn mesh_collider_shape(&mut self, id: Entity) -> Option<ColliderShape> {
+        let MeshType::Custom(n) = self.components.get::<MeshRenderer>(id)?.mesh_type else { return None };
+        let cm = self.custom_meshes.get_mut(n)?;
+        if cm.collision.is_none() {
+            cm.collision = TriMesh::new(&cm.positions, &cm.indices).map(Arc::new);
+        }
+        cm.collision.clone().map(|mesh| ColliderShape::Mesh { mesh })
+    }
+
+    /// Retourne la velocity [vx, vy, vz] d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_velocity(&self, id: Entity) -> [f32; 3] {
+        self.components.get::<RigidBody>(id)
+            .map(|rb| rb.velocity.to_array())
+            .unwrap_or([0.0; 3])
+    }
+
+    /// Définit la velocity d'un RigidBody.
+    pub fn set_velocity(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.velocity = glam::Vec3::new(x, y, z);
+        }
+    }
+
+    /// Retourne la vitesse angulaire (rad/s, axe monde) d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_angular_velocity(&self, id: Entity) -> [f32; 3] {
+        self.components.get::<RigidBody>(id)
+            .map(|rb| rb.angular_velocity.to_array())
+            .unwrap_or([0.0; 3])
+    }
+
+    pub fn set_angular_velocity(&mut self, id: Entity, x: f32, y: f32, z: f32) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.angular_velocity = glam::Vec3::new(x, y, z);
+        }
+    }
+
+    /// Force (N, monde) appliquée au centre du corps pendant les pas fixes du prochain `update` :
+    /// à renouveler à chaque frame pour une poussée continue. Sans effet hors Dynamic.
+    pub fn apply_force(&mut self, id: Entity, force: glam::Vec3) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.force += force;
+        }
+    }
+
+    /// Couple (N·m, axe monde), appliqué comme `apply_force`.
+    pub fn apply_torque(&mut self, id: Entity, torque: glam::Vec3) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.torque += torque;
+        }
+    }
+
+    /// Impulsion (N·s, monde) : change immédiatement la velocity de `impulse / masse`.
+    pub fn apply_impulse(&mut self, id: Entity, impulse: glam::Vec3) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.velocity += impulse * rb.inverse_mass();
+        }
+    }
+
+    /// True si le RigidBody dort (cf. `systems::update_sleep`).
+    pub fn is_sleeping(&self, id: Entity) -> bool {
+        self.components.get::<RigidBody>(id).is_some_and(RigidBody::is_sleeping)
+    }
+
+    /// Réveille l'îlot endormi de `id`, et par contact ceux qui reposaient contre lui (statique
+    /// déplacé ou supprimé). Remet à zéro le temps de repos de `id` s'il était éveillé.
+    pub fn wake_up(&mut self, id: Entity) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let island = self.components.get::<RigidBody>(id).and_then(|rb| rb.island);
+            let members: Vec<Entity> = match island {
+                Some(key) => self.components.storage::<RigidBody>().iter()
+                    .filter(|(_, rb)| rb.island == Some(key))
+                    .map(|(id, _)| id)
+                    .collect(),
+                None => vec![id],
+            };
+            for member in members {
+                if let Some(rb) = self.components.get_mut::<RigidBody>(member) {
+                    rb.sleeping    = false;
+                    rb.sleep_timer = 0.0;
+                    rb.island      = None;
+                }
+                stack.extend(self.contacts.wake(member).into_iter().filter(|&other| self.is_sleeping(other)));
+            }
+        }
+    }
+
+    /// Amortissements linéaire et angulaire (1/s, ≥ 0) d'un RigidBody.
+    pub fn set_damping(&mut self, id: Entity, linear: f32, angular: f32) {
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.linear_damping  = linear.max(0.0);
+            rb.angular_damping = angular.max(0.0);
+        }
+    }
+
+    /// Retourne [linear_damping, angular_damping, gravity_scale] d'un RigidBody, ou [0,0,0] si absent.
+    pub fn get_body_dynamics(&self, id: Entity) -> [f32; 3] {
+        self.components.get::<RigidBody>(id)
+            .map(|rb| [rb.linear_damping, rb.angular_damping, rb.gravity_scale])
+            .unwrap_or([0.0; 3])
+    }
+
+    /// Multiplie la gravité du monde pour ce corps.
+    pub fn set_gravity_scale(&mut self, id: Entity, scale: f32) {
+        self.wake_up(id);
+        if let Some(rb) = self.components.get_mut::<RigidBody>(id) {
+            rb.gravity_scale = scale;
+        }
+    }
+
+    /// Gravité du monde (m/s²), (0, -9.8, 0) par défaut. `on_ground`, les marches et le saut
+    /// des CharacterController restent mesurés selon l'axe Y.
+    pub fn set_gravity(&mut self, gravity: glam::Vec3) {
+        self.gravity = gravity;
+        let sleepers: Vec<Entity> = self.components.storage::<RigidBody>().iter()
+            .filter(|(_, rb)| rb.sleeping)
+            .map(|(id, _)| id)
+            .collect();
+        for id in sleepers {
+            self.wake_up(id);
+        }
+    }
+
+    pub fn gravity(&self) -> glam::Vec3 {
+        self.gravity
+    }
+
+    // ── Input ────────────────────────────────────────────────────────────────
+
+    /// Transmet l'état input du frame courant.
+    /// `keys` bitmask : bit0=W, bit1=S, bit2=A, bit3=D, bit4=SPACE.
+    /// `mouse_dx/dy` : delta pixels depuis le dernier frame (Pointer Lock).
+    pub fn set_input(&mut self, keys: u32, mouse_dx: f32, mouse_dy: f32) {
+        self.input.keys     = keys;
+        self.input.mouse_dx = mouse_dx;
+        self.input.mouse_dy = mouse_dy;
+    }
+
+    /// Met à jour la physique et la caméra FPS. Appeler avant render_frame().
+    /// Exécute PreUpdate → FixedUpdate (autant de pas fixes que l'accumulateur en contient)
+    /// → Update → PostUpdate.
+    pub fn update(&mut self, delta_ms: f32) {
+        let dt = delta_ms.max(0.0) / 1000.0;
+
+        self.run_stage(Stage::PreUpdate, dt);
+        let steps = self.fixed_time.advance(dt);
+        let step  = self.fixed_time.step();
+        for _ in 0..steps {
+            self.run_stage(Stage::FixedUpdate, step);
+        }
+        self.run_stage(Stage::Update, dt);
+        self.run_stage(Stage::PostUpdate, dt);
+    }
+
+    /// Configure le pas fixe de la physique (`hz` pas par seconde, au plus `max_substeps` par frame).
+    pub fn set_fixed_timestep(&mut self, hz: f32, max_substeps: u32) {
+        self.fixed_time = FixedTime::new(hz, max_substeps);
+    }
+
+    pub fn fixed_time(&self) -> &FixedTime {
+        &self.fixed_time
+    }
+
+    // ── Systèmes ─────────────────────────────────────────────────────────────
+
+    /// Ordonnanceur, pour insérer des systèmes de jeu entre les étapes intégrées.
+    pub fn schedule_mut(&mut self) -> &mut Schedule {
+        &mut self.schedule
+    }
+
+    /// Exécute les systèmes d'une étape.
+    ///
+    /// Le Schedule est sorti du monde le temps de l'exécution : un système ajouté
+    /// depuis un système est conservé (en fin d'étape) mais ne tourne qu'au passage suivant.
+    pub fn run_stage(&mut self, stage: Stage, dt: f32) {
+        let mut schedule = std::mem::take(&mut self.schedule);
+        schedule.run(stage, self, dt);
+        let added = std::mem::replace(&mut self.schedule, schedule);
+        self.schedule.absorb(added);
+    }
+
+    // ── Scènes ───────────────────────────────────────────────────────────────
+
+    /// Enregistre un TextureId GPU sous un nom string.
+    /// Appeler avant load_scene() pour que les textures nommées soient résolvables.
+    pub fn register_texture(&mut self, name: String, texture_id: u32) {
+        self.texture_registry.insert(name, texture_id);
+    }
+
+    /// Marque (ou démarque) une entité comme persistante.
+    /// Les entités persistantes survivent aux appels à load_scene().
+    pub fn set_persistent(&mut self, id: Entity, persistent: bool) {
+        if persistent {
+            self.persistent_entities.insert(id);
+        } else {
+            self.persistent_entities.remove(&id);
+        }
+    }
+
+    /// Charge une scène depuis un JSON string.
+    /// Supprime les entités non-persistantes, puis crée les entités du JSON.
+    /// Retourne les IDs des nouvelles entités créées.
+    pub fn load_scene(&mut self, json: &str) -> Result<Vec<Entity>, serde_json::Error> {
+        let mut scene: SceneData = serde_json::from_str(json)?;
+
+        self.clear_scene();
+
+        // Lumière directionnelle
+        if let Some(dl) = scene.directional_light {
+            self.directional_light = Some(DirectionalLightData {
+                direction:      glam::Vec3::from(dl.direction),
+                color:          glam::Vec3::from(dl.color),
+                intensity:      dl.intensity,
+                cone_angle_deg: dl.cone_angle_deg,
+            });
+        }
+        self.gravity = scene.gravity.map_or(DEFAULT_GRAVITY, glam::Vec3::from);
+
+        // Créer les entités
+        let mut new_ids: Vec<Entity> = Vec::new();
+
+        // Collecter les relations parent AVANT la boucle consommatrice
+        let parent_requests: Vec<(usize, usize)> = scene.entities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| e.parent_index.map(|pidx| (i, pidx)))
+            .collect();
+        // Idem pour les joints : leur cible peut être créée après eux
+        let joint_requests: Vec<(usize, SceneJoint)> = scene.entities
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, e)| e.joint.take().map(|j| (i, j)))
+            .collect();
+
+        for entity_data in scene.entities {
+            let id = self.create_entity();
+            new_ids.push(id);
+
+            if let Some(t) = entity_data.transform {
+                self.components.insert(id, Transform {
+                    position: glam::Vec3::from(t.position),
+                    rotation: t.rotation.to_quat(),
+                    scale:    glam::Vec3::from(t.scale),
+                });
+            }
+
+            if entity_data.mesh_renderer == Some(true) {
+                self.add_mesh_renderer(id);
+            }
+
+            if let Some(mat) = entity_data.material {
+                let tex_id = self.texture_registry
+                    .get(&mat.texture)
+                    .copied()
+                    .unwrap_or_else(|| {
+                        log::warn(&format!("[load_scene] texture '{}' non enregistrée", mat.texture));
+                        u32::MAX
+                    });
+                let normal_id = if mat.normal_texture.is_empty() {
+                    u32::MAX
+                } else {
+                    self.texture_registry.get(&mat.normal_texture).copied().unwrap_or(u32::MAX)
+                };
+                self.components.insert(id, Material {
+                    albedo_tex: tex_id,
+                    normal_tex: normal_id,
+                    metallic:   mat.metallic,
+                    roughness:  mat.roughness,
+                    emissive:   glam::Vec3::from(mat.emissive.unwrap_or([0.0, 0.0, 0.0])),
+                });
+            }
+
+            if let Some(rb) = entity_data.rigid_body {
+                self.add_rigid_body(id, rb.is_static);
+                if rb.kinematic == Some(true) {
+                    self.set_body_type(id, BodyType::Kinematic);
+                }
+                self.set_ccd(id, rb.ccd == Some(true));
+                self.set_rigid_body_material(id, rb.mass, rb.restitution, rb.friction);
+                self.set_damping(id, rb.linear_damping, rb.angular_damping);
+                self.set_gravity_scale(id, rb.gravity_scale);
+            }
+
+            // Avant le collider : un collider maillage lit le mesh custom.
+            if let Some(mt) = &entity_data.mesh_type {
+                self.set_mesh_type(id, mt);
+            }
+
+            let collider_shape = match (entity_data.collider_aabb, entity_data.collider_sphere, entity_data.collider_capsule) {
+                (Some(he), _, _)                     => Some(ColliderShape::Box { half_extents: glam::Vec3::from(he) }),
+                (_, Some(radius), _)                 => Some(ColliderShape::Sphere { radius }),
+                (_, _, Some([radius, half_height]))  => Some(ColliderShape::Capsule { radius, half_height }),
+                _ if entity_data.collider_mesh == Some(true) => self.mesh_collider_shape(id),
+                _ => None,
+            };
+            if let Some(shape) = collider_shape {
+                self.components.insert(id, Collider {
+                    shape,
+                    is_trigger: entity_data.collider_trigger.unwrap_or(false),
+                    layer:      entity_data.collider_layer.unwrap_or(Collider::DEFAULT_LAYER),
+                    mask:       entity_data.collider_mask.unwrap_or(Collider::DEFAULT_MASK),
+                });
+            }
+
+            if let Some(cc) = entity_data.character_controller {
+                self.set_character_controller(id, cc.speed, cc.acceleration, cc.air_control, cc.jump_height,
+                                              cc.step_height, cc.slope_limit, cc.coyote_time, cc.jump_buffer);
+                if cc.player {
+                    self.set_player(id);
+                }
+            }
+
+            if let Some(pl) = entity_data.point_light {
+                self.components.insert(id, PointLight {
+                    color:     glam::Vec3::from(pl.color),
+                    intensity: pl.intensity,
+                });
+            }
+
+            if let Some(name) = entity_data.name {
+                self.set_entity_name(id, name);
+            }
+            if let Some(tag) = &entity_data.tag {
+                self.set_tag(id, tag);
+            }
+            self.components.load_entity(id, entity_data.components);
+            if let Some(cam) = entity_data.camera {
+                self.add_camera(id, cam.fov, cam.near, cam.far);
+                self.set_camera_follow_entity(id, cam.follow_entity);
+                if cam.is_active {
+                    self.set_active_camera(id);
+                }
+            }
+        }
+
+        // Restaurer les relations parent-enfant
+        for (child_idx, parent_idx) in parent_requests {
+            if let (Some(&child_id), Some(&parent_id)) =
+                (new_ids.get(child_idx), new_ids.get(parent_idx))
+            {
+                self.set_parent_raw(child_id, parent_id);
+            }
+        }
+
+        // Restaurer les joints (cible par index ; absente = point fixe du monde)
+        for (idx, data) in joint_requests {
+            let target = match data.target_index {
+                Some(t) => match new_ids.get(t) {
+                    Some(&target) if t != idx => Some(target),
+                    _ => {
+                        log::warn(&format!("[load_scene] joint de l'entité {idx} : cible {t} invalide"));
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            match data.to_joint(target) {
+                Some(joint) => self.components.insert(new_ids[idx], joint),
+                None => log::warn(&format!("[load_scene] joint de type '{}' inconnu", data.kind)),
+            }
+        }
+
+        Ok(new_ids)
+    }
+
+    /// Sérialise la scène courante (toutes les entités) en JSON string.
+    pub fn save_scene(&self) -> String {
+        let directional_light = self.directional_light.as_ref().map(|dl| SceneDirectionalLight {
+            direction:      dl.direction.to_array(),
+            color:          dl.color.to_array(),
+            intensity:      dl.intensity,
+            cone_angle_deg: dl.cone_angle_deg,
+        });
+        let gravity = (self.gravity != DEFAULT_GRAVITY).then(|| self.gravity.to_array());
+
+        // Collecter tous les IDs d'entités ayant au moins un composant
+        let all_ids: HashSet<Entity> = self.components.ids().collect();
+
+        // Trouver le nom de texture inverse (TextureId → nom)
+        let id_to_name: HashMap<u32, String> = self.texture_registry
+            .iter()
+            .map(|(name, &id)| (id, name.clone()))
+            .collect();
+
+        let mut entities: Vec<SceneEntityData> = Vec::new();
+        let mut sorted_ids: Vec<Entity> = all_ids.into_iter().collect();
+        sorted_ids.sort();
+
+        // Map entity_id → index dans sorted_ids (pour sérialiser les relations parent)
+        let id_to_index: HashMap<Entity, usize> = sorted_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+
+        for id in sorted_ids {
+            let transform = self.components.get::<Transform>(id).map(|t| SceneTransform {
+                position: t.position.to_array(),
+                rotation: SceneRotation::Quat(t.rotation.to_array()),
+                scale:    t.scale.to_array(),
+            });
+            let mesh_renderer = if self.components.get::<MeshRenderer>(id).is_some() { Some(true) } else { None };
+            let material = self.components.get::<Material>(id).map(|m| SceneMaterial {
+                texture:        id_to_name.get(&m.albedo_tex).cloned().unwrap_or_default(),
+                normal_texture: id_to_name.get(&m.normal_tex).cloned().unwrap_or_default(),
+                metallic:       m.metallic,
+                roughness:      m.roughness,
+                emissive:       Some(m.emissive.to_array()),
+            });
+            let rigid_body = self.components.get::<RigidBody>(id).map(|rb| SceneRigidBody {
+                is_static:   rb.body_type == BodyType::Static,
+                kinematic:   (rb.body_type == BodyType::Kinematic).then_some(true),
+                ccd:         rb.ccd.then_some(true),
+                mass:        rb.mass,
+                restitution: rb.restitution,
+                friction:    rb.friction,
+                linear_damping:  rb.linear_damping,
+                angular_damping: rb.angular_damping,
+                gravity_scale:   rb.gravity_scale,
+            });
+            let (mut collider_aabb, mut collider_sphere, mut collider_capsule) = (None, None, None);
+            let collider = self.components.get::<Collider>(id);
+            let mut collider_mesh = None;
+            match collider.map(|c| &c.shape) {
+                Some(&ColliderShape::Box { half_extents })            => collider_aabb    = Some(half_extents.to_array()),
+                Some(&ColliderShape::Sphere { radius })               => collider_sphere  = Some(radius),
+                Some(&ColliderShape::Capsule { radius, half_height }) => collider_capsule = Some([radius, half_height]),
+                Some(ColliderShape::Mesh { .. })                      => collider_mesh    = Some(true),
+                None => {}
+            }
+            let collider_trigger = collider.filter(|c| c.is_trigger).map(|_| true);
+            let collider_layer   = collider.map(|c| c.layer).filter(|&l| l != Collider::DEFAULT_LAYER);
+            let collider_mask    = collider.map(|c| c.mask).filter(|&m| m != Collider::DEFAULT_MASK);
+            let character_controller = self.components.get::<CharacterController>(id).map(|c| SceneCharacterController {
+                speed:        c.speed,
+                acceleration: c.acceleration,
+                air_control:  c.air_control,
+                jump_height:  c.jump_height,
+                step_height:  c.step_height,
+                slope_limit:  c.slope_limit,
+                coyote_time:  c.coyote_time,
+                jump_buffer:  c.jump_buffer,
+                player:       c.player,
+            });
+            let point_light = self.components.get::<PointLight>(id).map(|pl| ScenePointLight {
+                color:     pl.color.to_array(),
+                intensity: pl.intensity,
+            });
+
+            let parent_index = self.components.get::<Parent>(id)
+                .and_then(|p| id_to_index.get(&p.parent_id))
+                .copied();
+            let joint = self.components.get::<Joint>(id).and_then(|j| match j.target {
+                Some(target) => id_to_index.get(&target).map(|&t| SceneJoint::new(j, Some(t))),
+                None         => Some(SceneJoint::new(j, None)),
+            });
+
+            entities.push(SceneEntityData {
+                transform, mesh_renderer, material, rigid_body,
+                collider_aabb, collider_sphere, collider_capsule, collider_mesh,
+                collider_trigger, collider_layer, collider_mask, character_controller, point_light,
+                mesh_type: self.components.get::<MeshRenderer>(id).map(|mr| match &mr.mesh_type {
+                    MeshType::Cube       => "cube".to_string(),
+                    MeshType::Plane      => "plane".to_string(),
+                    MeshType::Custom(n)  => format!("custom:{}", n),
+                    MeshType::Sphere     => "sphere".to_string(),
+                    MeshType::Cylinder   => "cylinder".to_string(),
+                }),
+                name: self.entity_names.get(&id).cloned(),
+                tag:  self.tags.get(&id).cloned(),
+                camera: self.components.get::<CameraComponent>(id).map(|c| SceneCameraComponent {
+                    fov: c.fov, near: c.near, far: c.far,
+                    follow_entity: c.follow_entity,
+                    is_active: self.active_camera == Some(id),
+                }),
+                parent_index,
+                joint,
+                components: self.components.save_entity(id),
+            });
+        }
+
+        let scene = SceneData { directional_light, gravity, entities };
+        match serde_json::to_string_pretty(&scene) {
+            Ok(s) => s,
+            Err(e) => {
+                log::error(&format!("[World] save_scene serde error: {}", e));
+                "{\"entities\":[]}".to_string()
+            }
+        }
+    }
+
+    /// Supprime tous les composants des entités non-persistantes.
+    /// Les entités persistantes et la texture_registry sont conservées.
+    /// La directional_light est réinitialisée.
+    fn clear_scene(&mut self) {
+        // 0. Promouvoir les entités persistantes dont le parent est non-persistant.
+        //    Doit se faire AVANT la suppression pour que world_matrix fonctionne.
+        let orphans: Vec<Entity> = self.persistent_entities.iter()
+            .copied()
+            .filter(|&pid| {
+                self.components.get::<Parent>(pid)
+                    .map(|p| !self.persistent_entities.contains(&p.parent_id))
+                    .unwrap_or(false)
+            })
+            .collect();
+        for pid in orphans {
+            let world_mat = self.world_matrix(pid);
+            let (scale, rotation, translation) = world_mat.to_scale_rotation_translation();
+            if let Some(t) = self.components.get_mut::<Transform>(pid) {
+                t.position = translation;
+                t.rotation = rotation;
+                t.scale    = scale;
+            }
+            self.components.remove::<Parent>(pid);
+        }
+
+        // Toutes les entités vivantes non-persistantes (y compris celles sans composant)
+        let all_ids: Vec<Entity> = self.entities.iter()
+            .filter(|id| !self.persistent_entities.contains(id))
+            .collect();
+
+        // Joints des entités persistantes vers la scène déchargée.
+        self.drop_joints_to(|target| all_ids.contains(&target));
+
+        for id in all_ids {
+            self.components.remove_entity(id);
+            self.entities.kill(id);
+        }
+        self.active_camera  = None;
+        self.preview_camera = None;
+        // Clear entity_names for removed entities
+        self.entity_names.retain(|id, _| self.persistent_entities.contains(id));
+        // Retain tags only for persistent entities
+        self.tags.retain(|id, _| self.persistent_entities.contains(id));
+
+        self.directional_light = None;
+        self.directional_light_entity = None;
+        self.contacts.clear();
+    }
+}
@@ -0,0 +1,41 @@
+//! Détection continue : un projectile rapide ne traverse pas un mur fin.
+
+mod common;
+
+use common::{ball, fixed_step, position, run, velocity};
+use engine_core::ecs::Entity;
+use engine_core::WorldCore;
+use glam::Vec3;
+
+/// Mur statique de 5 cm d'épaisseur en x = 0, et une balle de 5 cm de rayon tirée vers lui
+/// à 200 m/s : elle franchit 3.3 m en un pas fixe.
+fn shoot(ccd: bool) -> (WorldCore, Entity) {
+    let mut world = WorldCore::new();
+    world.set_gravity(Vec3::ZERO);
+    let wall = world.create_entity();
+    world.add_transform(wall, 0.0, 0.0, 0.0);
+    world.add_rigid_body(wall, true);
+    world.add_collider_aabb(wall, 0.025, 2.0, 2.0);
+
+    let bullet = ball(&mut world, Vec3::new(-1.0, 0.0, 0.0), 0.05);
+    world.set_ccd(bullet, ccd);
+    world.set_velocity(bullet, 200.0, 0.0, 0.0);
+    fixed_step(&mut world);
+    (world, bullet)
+}
+
+#[test]
+fn ccd_bullet_stops_at_thin_wall_in_one_step() {
+    let (world, bullet) = shoot(true);
+    let x = position(&world, bullet).x;
+    assert!((-0.08..-0.07).contains(&x), "arrêtée contre la face du mur, x = {x}");
+    assert!(velocity(&world, bullet).x <= 0.0, "plus de vitesse vers le mur");
+}
+
+#[test]
+fn discrete_bullet_tunnels_through_thin_wall() {
+    let (mut world, bullet) = shoot(false);
+    assert!(position(&world, bullet).x > 2.0, "sans CCD, la balle saute par-dessus le mur");
+    run(&mut world, 5);
+    assert!(velocity(&world, bullet).x > 199.0, "et poursuit sa course");
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::*;
+use engine_core::WorldCore;
+use engine_core::ecs::{CharacterController, Entity, Transform};
+use glam::{Quat, Vec3};
+
+const KEY_W: u32 = 1 << 0;
+
+/// Sol, un joueur et un personnage non joueur (réglages par défaut), posés au sol.
+fn scene() -> (WorldCore, Entity, Entity) {
+    let mut world = WorldCore::new();
+    floor(&mut world);
+    let player = cube(&mut world, Vec3::new(0.0, 0.5, 0.0));
+    world.set_player(player);
+    let npc = cube(&mut world, Vec3::new(5.0, 0.5, 0.0));
+    world.set_character_controller(npc, 5.0, 60.0, 0.3, 1.25, 0.3, 45.0, 0.1, 0.1);
+    run(&mut world, 10);
+    (world, player, npc)
+}
+
+fn horizontal_speed(world: &WorldCore, id: Entity) -> f32 {
+    let v = velocity(world, id);
+    Vec3::new(v.x, 0.0, v.z).length()
+}
+
+fn rotation(world: &WorldCore, id: Entity) -> Quat {
+    world.components().get::<Transform>(id).expect("transform").rotation
+}
+
+#[test]
+fn keyboard_and_camera_drive_only_the_player() {
+    let (mut world, player, npc) = scene();
+    world.set_input(KEY_W, 200.0, 0.0);
+    world.update(FRAME_MS);
+    world.set_input(KEY_W, 0.0, 0.0);
+    run(&mut world, 30);
+
+    assert!((horizontal_speed(&world, player) - 5.0).abs() < 0.1, "le joueur avance à `speed`");
+    assert!(horizontal_speed(&world, npc) < 1e-3, "le non-joueur ignore le clavier");
+    assert!(!rotation(&world, player).abs_diff_eq(Quat::IDENTITY, 1e-3), "le joueur suit le yaw caméra");
+    assert!(rotation(&world, npc).abs_diff_eq(Quat::IDENTITY, 1e-6), "le non-joueur garde sa rotation");
+}
+
+#[test]
+fn non_player_follows_its_intent() {
+    let (mut world, player, npc) = scene();
+    world.set_character_intent(npc, 0.0, -2.0, false);
+    run(&mut world, 30);
+
+    let v = velocity(&world, npc);
+    assert!(v.x.abs() < 1e-3 && (v.z + 5.0).abs() < 0.1, "intention ramenée à une longueur 1 : {v:?}");
+    assert!(horizontal_speed(&world, player) < 1e-3, "l'intention ne touche pas le joueur");
+
+    world.set_character_intent(npc, 0.0, 0.0, false);
+    run(&mut world, 30);
+    assert!(horizontal_speed(&world, npc) < 1e-3, "intention nulle : arrêt");
+}
+
+#[test]
+fn jump_request_is_consumed_once() {
+    let (mut world, _, npc) = scene();
+    world.set_character_intent(npc, 0.0, 0.0, true);
+    fixed_step(&mut world);
+    assert!(velocity(&world, npc).y > 4.0, "saut au pas suivant la demande");
+
+    let mut peak = 0.0_f32;
+    for _ in 0..180 {
+        fixed_step(&mut world);
+        peak = peak.max(position(&world, npc).y);
+    }
+    assert!((peak - 1.75).abs() < 0.1, "un seul saut de `jump_height` : sommet {peak}");
+    assert!((position(&world, npc).y - 0.5).abs() < 0.05, "retombé au sol, pas de second saut");
+}
+
+fn player_flags(world: &WorldCore) -> Vec<bool> {
+    world.components().storage::<CharacterController>().iter().map(|(_, cc)| cc.player).collect()
+}
+
+#[test]
+fn scene_keeps_player_flag() {
+    let (world, ..) = scene();
+    let json = world.save_scene();
+    let mut loaded = WorldCore::new();
+    loaded.load_scene(&json).expect("scène valide");
+    let mut flags = player_flags(&loaded);
+    flags.sort();
+    assert_eq!(flags, [false, true], "un seul joueur après rechargement");
+
+    // Champ `player` absent : aucun joueur.
+    let mut legacy: serde_json::Value = serde_json::from_str(&json).expect("json");
+    for entity in legacy["entities"].as_array_mut().expect("entities") {
+        if let Some(cc) = entity["character_controller"].as_object_mut() {
+            cc.remove("player");
+        }
+    }
+    let legacy = legacy.to_string();
+    loaded.load_scene(&legacy).expect("scène valide");
+    assert_eq!(player_flags(&loaded), [false, false]);
+}
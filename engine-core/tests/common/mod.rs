@@ -0,0 +1,57 @@
+//! Scènes de test partagées : sol, cubes et sphères sur un `WorldCore` natif.
+#![allow(dead_code)]
+
+use engine_core::ecs::{Entity, Transform};
+use engine_core::{Stage, WorldCore};
+use glam::Vec3;
+
+/// Durée d'une frame à 60 Hz (ms) : environ un pas fixe par `update` (l'accumulateur arrondit).
+pub const FRAME_MS: f32 = 1000.0 / 60.0;
+
+pub fn run(world: &mut WorldCore, frames: u32) {
+    for _ in 0..frames {
+        world.update(FRAME_MS);
+    }
+}
+
+/// Exactement un pas fixe de physique, sans passer par l'accumulateur de `update`.
+pub fn fixed_step(world: &mut WorldCore) {
+    let step = world.fixed_time().step();
+    world.run_stage(Stage::FixedUpdate, step);
+    world.run_stage(Stage::PostUpdate, step);
+}
+
+pub fn position(world: &WorldCore, id: Entity) -> Vec3 {
+    world.components().get::<Transform>(id).expect("transform").position
+}
+
+pub fn velocity(world: &WorldCore, id: Entity) -> Vec3 {
+    Vec3::from(world.get_velocity(id))
+}
+
+/// Sol statique de 40 × 40 m dont le dessus est à y = 0.
+pub fn floor(world: &mut WorldCore) -> Entity {
+    let id = world.create_entity();
+    world.add_transform(id, 0.0, -0.5, 0.0);
+    world.add_rigid_body(id, true);
+    world.add_collider_aabb(id, 20.0, 0.5, 20.0);
+    id
+}
+
+/// Cube dynamique d'1 m de côté centré en `at`.
+pub fn cube(world: &mut WorldCore, at: Vec3) -> Entity {
+    let id = world.create_entity();
+    world.add_transform(id, at.x, at.y, at.z);
+    world.add_rigid_body(id, false);
+    world.add_collider_aabb(id, 0.5, 0.5, 0.5);
+    id
+}
+
+/// Sphère dynamique de rayon `radius` centrée en `at`.
+pub fn ball(world: &mut WorldCore, at: Vec3, radius: f32) -> Entity {
+    let id = world.create_entity();
+    world.add_transform(id, at.x, at.y, at.z);
+    world.add_rigid_body(id, false);
+    world.add_collider_sphere(id, radius);
+    id
+}
@@ -0,0 +1,177 @@
+//! Impulsions de contact : restitution, frottement de Coulomb, partage selon les masses ;
+//! colliders statiques déplacés entre deux `update` ; événements d'un trigger traversé.
+
+mod common;
+
+use common::{FRAME_MS, ball, cube, floor, position, run, velocity};
+use engine_core::ecs::{Entity, Transform};
+use engine_core::{CollisionPhase, WorldCore};
+use glam::{Quat, Vec3};
+
+#[test]
+fn elastic_ball_bounces_back_to_drop_height() {
+    let mut world = WorldCore::new();
+    let ground = floor(&mut world);
+    world.set_rigid_body_material(ground, 1.0, 1.0, 0.5);
+    let bouncy = ball(&mut world, Vec3::new(0.0, 3.0, 0.0), 0.5);
+    world.set_rigid_body_material(bouncy, 1.0, 1.0, 0.5);
+
+    // Chute de 2.5 m (~0.7 s), rebond, remontée : on garde le plus haut point après le rebond.
+    let (mut bounced, mut peak) = (false, 0.0_f32);
+    for _ in 0..120 {
+        run(&mut world, 1);
+        bounced |= velocity(&world, bouncy).y > 0.0;
+        if bounced {
+            peak = peak.max(position(&world, bouncy).y);
+        }
+    }
+    assert!(bounced);
+    assert!((peak - 3.0).abs() < 0.25, "remonte à {peak} m au lieu de 3 m");
+}
+
+#[test]
+fn slow_elastic_contact_does_not_bounce() {
+    let mut world = WorldCore::new();
+    let ground = floor(&mut world);
+    world.set_rigid_body_material(ground, 1.0, 1.0, 0.5);
+    // Sous RESTITUTION_MIN_SPEED, même un matériau parfaitement élastique reste posé.
+    let resting = cube(&mut world, Vec3::new(0.0, 0.52, 0.0));
+    world.set_rigid_body_material(resting, 1.0, 1.0, 0.5);
+    run(&mut world, 60);
+    assert!(position(&world, resting).y < 0.52);
+    assert!(velocity(&world, resting).length() < 0.05);
+}
+
+/// Rampe statique inclinée de 20° (descend vers +x) et une boîte posée dessus.
+fn box_on_slope(friction: f32) -> (WorldCore, Entity) {
+    let mut world = WorldCore::new();
+    let tilt = Quat::from_rotation_z(-20f32.to_radians());
+    let ramp = world.create_entity();
+    world.add_transform(ramp, 0.0, 0.0, 0.0);
+    world.components_mut().get_mut::<Transform>(ramp).unwrap().rotation = tilt;
+    world.add_rigid_body(ramp, true);
+    world.add_collider_aabb(ramp, 10.0, 0.5, 3.0);
+    world.set_rigid_body_material(ramp, 1.0, 0.0, friction);
+
+    let resting = cube(&mut world, tilt * Vec3::new(-3.0, 1.0, 0.0));
+    world.components_mut().get_mut::<Transform>(resting).unwrap().rotation = tilt;
+    world.set_rigid_body_material(resting, 1.0, 0.0, friction);
+    (world, resting)
+}
+
+#[test]
+fn frictionless_box_slides_down_slope() {
+    let (mut world, slider) = box_on_slope(0.0);
+    let start = position(&world, slider);
+    run(&mut world, 60);
+    // Sans frottement : a = g·sin(20°) ≈ 3.35 m/s², ~3.3 m/s après 1 s.
+    let speed = velocity(&world, slider).length();
+    assert!((speed - 9.8 * 20f32.to_radians().sin()).abs() < 0.3, "vitesse {speed}");
+    assert!(position(&world, slider).x - start.x > 1.0);
+}
+
+#[test]
+fn high_friction_box_stays_on_slope() {
+    let (mut world, sticker) = box_on_slope(1.0);
+    let start = position(&world, sticker);
+    run(&mut world, 60);
+    assert!(position(&world, sticker).distance(start) < 0.1, "tan(20°) < 1 : la boîte tient");
+    assert!(velocity(&world, sticker).length() < 0.1);
+}
+
+#[test]
+fn momentum_is_shared_between_unequal_masses() {
+    let mut world = WorldCore::new();
+    world.set_gravity(Vec3::ZERO);
+    let light = cube(&mut world, Vec3::new(-2.0, 0.0, 0.0));
+    let heavy = cube(&mut world, Vec3::new(0.0, 0.0, 0.0));
+    world.set_rigid_body_material(light, 1.0, 0.0, 0.0);
+    world.set_rigid_body_material(heavy, 3.0, 0.0, 0.0);
+    world.set_velocity(light, 4.0, 0.0, 0.0);
+    run(&mut world, 60);
+
+    // Choc mou : vitesse commune (1·4 + 3·0) / 4 = 1 m/s, quantité de mouvement conservée.
+    let (v_light, v_heavy) = (velocity(&world, light).x, velocity(&world, heavy).x);
+    assert!((v_light - 1.0).abs() < 0.05 && (v_heavy - 1.0).abs() < 0.05, "{v_light} / {v_heavy}");
+    assert!((v_light + 3.0 * v_heavy - 4.0).abs() < 0.05);
+}
+
+#[test]
+fn elastic_collision_exchanges_momentum_by_mass() {
+    let mut world = WorldCore::new();
+    world.set_gravity(Vec3::ZERO);
+    let light = ball(&mut world, Vec3::new(-2.0, 0.0, 0.0), 0.5);
+    let heavy = ball(&mut world, Vec3::new(0.0, 0.0, 0.0), 0.5);
+    world.set_rigid_body_material(light, 1.0, 1.0, 0.0);
+    world.set_rigid_body_material(heavy, 3.0, 1.0, 0.0);
+    world.set_velocity(light, 4.0, 0.0, 0.0);
+    run(&mut world, 60);
+
+    // Choc élastique 1 kg → 3 kg : v1 = (1-3)/4·4 = -2 m/s, v2 = 2·1/4·4 = 2 m/s.
+    let (v_light, v_heavy) = (velocity(&world, light).x, velocity(&world, heavy).x);
+    assert!((v_light + 2.0).abs() < 0.1 && (v_heavy - 2.0).abs() < 0.1, "{v_light} / {v_heavy}");
+}
+
+#[test]
+fn teleported_static_collides_in_the_same_update() {
+    let mut world = WorldCore::new();
+    let ground = floor(&mut world);
+    let falling = cube(&mut world, Vec3::new(0.0, 10.0, 0.0));
+    run(&mut world, 5);
+
+    // Sol remonté juste sous le cube, qui descend de ~0.1 m par pas : il le touche au prochain pas.
+    world.set_velocity(falling, 0.0, -6.0, 0.0);
+    let top = position(&world, falling).y - 0.55;
+    world.set_position(ground, 0.0, top - 0.5, 0.0);
+    world.update(20.0);
+
+    let y = position(&world, falling).y;
+    assert!((y - (top + 0.5)).abs() < 0.02, "posé sur le sol déplacé, y = {y} (dessus à {top})");
+    assert!(velocity(&world, falling).y > -1.0, "chute arrêtée");
+}
+
+#[test]
+fn teleported_trigger_fires_in_the_same_update() {
+    let mut world = WorldCore::new();
+    floor(&mut world);
+    let resting = cube(&mut world, Vec3::new(0.0, 0.5, 0.0));
+    let zone = world.create_entity();
+    world.add_transform(zone, 50.0, 0.5, 0.0);
+    world.add_collider_aabb(zone, 1.0, 1.0, 1.0);
+    world.set_collider_trigger(zone, true);
+    run(&mut world, 10);
+    world.drain_collision_events();
+
+    world.set_position(zone, 0.0, 0.5, 0.0);
+    world.update(20.0);
+    let entered = world.drain_collision_events().into_iter()
+        .any(|e| e.trigger && e.phase == CollisionPhase::Enter && [e.a, e.b].contains(&resting));
+    assert!(entered, "le trigger déplacé détecte le cube dès ce pas");
+}
+
+#[test]
+fn falling_through_a_trigger_reports_enter_stay_exit() {
+    let mut world = WorldCore::new();
+    let zone = world.create_entity();
+    world.add_transform(zone, 0.0, 0.0, 0.0);
+    world.add_collider_aabb(zone, 2.0, 1.0, 2.0);
+    world.set_collider_trigger(zone, true);
+    let falling = ball(&mut world, Vec3::new(0.0, 3.0, 0.0), 0.3);
+
+    // Phases successives de la paire, relevées update après update.
+    let mut phases = Vec::new();
+    for _ in 0..90 {
+        world.update(FRAME_MS);
+        for e in world.drain_collision_events() {
+            assert!(e.trigger && [e.a, e.b].contains(&zone) && [e.a, e.b].contains(&falling), "{e:?}");
+            phases.push(e.phase);
+        }
+    }
+    assert!(position(&world, falling).y < -2.0, "la balle a traversé la zone");
+    let stays = phases.len().saturating_sub(2);
+    assert!(stays > 0, "au moins un Stay : {phases:?}");
+    let mut expected = vec![CollisionPhase::Enter];
+    expected.extend(std::iter::repeat_n(CollisionPhase::Stay, stays));
+    expected.push(CollisionPhase::Exit);
+    assert_eq!(phases, expected);
+}
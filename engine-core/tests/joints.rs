@@ -0,0 +1,78 @@
+mod common;
+
+use common::*;
+use engine_core::WorldCore;
+use engine_core::ecs::{Entity, Joint, Transform};
+use glam::Vec3;
+
+#[test]
+fn distance_joints_keep_their_rest_length_under_gravity() {
+    let mut world = WorldCore::new();
+    let pivot = Vec3::new(0.0, 10.0, 0.0);
+    // Double pendule lâché à l'horizontale : la tige du monde à A, puis de A à B.
+    let a = ball(&mut world, pivot + Vec3::X * 2.0, 0.2);
+    let b = ball(&mut world, pivot + Vec3::X * 3.5, 0.2);
+    assert!(world.add_distance_joint(a, None, Vec3::ZERO, pivot, None));
+    assert!(world.add_distance_joint(b, Some(a), Vec3::ZERO, Vec3::ZERO, None));
+
+    let mut lowest = f32::INFINITY;
+    for _ in 0..180 {
+        fixed_step(&mut world);
+        let (pa, pb) = (position(&world, a), position(&world, b));
+        assert!((pa.distance(pivot) - 2.0).abs() < 0.02, "tige monde-A : {}", pa.distance(pivot));
+        assert!((pb.distance(pa) - 1.5).abs() < 0.02, "tige A-B : {}", pb.distance(pa));
+        lowest = lowest.min(pb.y);
+    }
+    assert!(lowest < pivot.y - 2.0, "le pendule est bien tombé");
+}
+
+/// Angle (degrés) de la planche autour de Y.
+fn door_angle(world: &WorldCore, door: Entity) -> f32 {
+    let q = world.components().get::<Transform>(door).expect("transform").rotation;
+    (2.0 * q.y.atan2(q.w)).to_degrees()
+}
+
+#[test]
+fn hinge_stays_within_its_limits() {
+    let mut world = WorldCore::new();
+    // Porte de 2 m de large, gonds verticaux sur son bord gauche ; -30° à 60°.
+    let door = world.create_entity();
+    world.add_transform(door, 1.0, 1.0, 0.0);
+    world.add_rigid_body(door, false);
+    world.add_collider_aabb(door, 1.0, 1.0, 0.05);
+    assert!(world.add_hinge_joint(door, None, Vec3::new(0.0, 1.0, 0.0), Vec3::Y, Some((-30.0, 60.0))));
+
+    for (push, limit) in [(-4.0, 60.0), (4.0, -30.0)] {
+        let mut widest = 0.0_f32;
+        for _ in 0..120 {
+            world.set_velocity(door, 0.0, 0.0, push);
+            fixed_step(&mut world);
+            let angle = door_angle(&world, door);
+            assert!((-30.5..=60.5).contains(&angle), "angle {angle} hors des butées");
+            widest = if limit > 0.0 { widest.max(angle) } else { widest.min(angle) };
+            let hinge = position(&world, door) - Vec3::new(0.0, 1.0, 0.0);
+            assert!((hinge.length() - 1.0).abs() < 0.02, "la porte quitte ses gonds");
+        }
+        assert!((widest - limit).abs() < 1.0, "butée {limit} atteinte : {widest}");
+    }
+}
+
+#[test]
+fn removing_the_target_drops_the_joint() {
+    let mut world = WorldCore::new();
+    let anchor = ball(&mut world, Vec3::new(0.0, 10.0, 0.0), 0.2);
+    world.add_rigid_body(anchor, true);
+    let hanging = ball(&mut world, Vec3::new(0.0, 8.0, 0.0), 0.2);
+    assert!(world.add_distance_joint(hanging, Some(anchor), Vec3::ZERO, Vec3::ZERO, None));
+    run(&mut world, 30);
+    assert!((position(&world, hanging).y - 8.0).abs() < 0.02, "pendu sous l'ancre");
+
+    world.remove_entity(anchor);
+    assert!(world.components().get::<Joint>(hanging).is_none(), "joint vers une entité supprimée");
+    // Le slot de l'ancre est réutilisé : l'ancien handle ne doit ni paniquer ni retenir le corps.
+    let reused = ball(&mut world, Vec3::new(5.0, 10.0, 0.0), 0.2);
+    world.add_rigid_body(reused, true);
+    assert_eq!(reused.index(), anchor.index());
+    run(&mut world, 30);
+    assert!(position(&world, hanging).y < 7.0, "le corps libéré tombe");
+}
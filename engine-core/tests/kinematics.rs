@@ -0,0 +1,74 @@
+//! Corps kinematics : transport des passagers, poussée des corps dynamiques, passage en statique.
+
+mod common;
+
+use common::*;
+use engine_core::WorldCore;
+use engine_core::ecs::{BodyType, Entity};
+use glam::Vec3;
+
+/// Plateforme kinematic de 4 × 0.5 × 4 m dont le dessus est à y = 0, lancée à `velocity`.
+fn platform(world: &mut WorldCore, velocity: Vec3) -> Entity {
+    let id = world.create_entity();
+    world.add_transform(id, 0.0, -0.25, 0.0);
+    world.add_rigid_body(id, false);
+    world.set_body_type(id, BodyType::Kinematic);
+    world.add_collider_aabb(id, 2.0, 0.25, 2.0);
+    world.set_velocity(id, velocity.x, velocity.y, velocity.z);
+    id
+}
+
+#[test]
+fn rider_is_carried_by_a_moving_kinematic() {
+    let mut world = WorldCore::new();
+    let lift = platform(&mut world, Vec3::ZERO);
+    let rider = cube(&mut world, Vec3::new(0.0, 0.5, 0.0));
+    run(&mut world, 30);
+
+    world.set_velocity(lift, 1.5, 0.5, 0.0);
+    run(&mut world, 60);
+    let moved = position(&world, lift) - Vec3::new(0.0, -0.25, 0.0);
+    assert!(moved.x > 1.0 && moved.y > 0.3, "la plateforme avance : {moved:?}");
+    let p = position(&world, rider);
+    assert!((p.x - moved.x).abs() < 0.05, "le passager suit en x : {} / {}", p.x, moved.x);
+    assert!((p.y - (moved.y + 0.5)).abs() < 0.05, "toujours posé dessus, y = {}", p.y);
+    assert!(velocity(&world, lift).abs_diff_eq(Vec3::new(1.5, 0.5, 0.0), 1e-6), "les contacts ne freinent pas le kinematic");
+}
+
+#[test]
+fn kinematic_pushes_a_dynamic_body() {
+    let mut world = WorldCore::new();
+    floor(&mut world);
+    let pusher = world.create_entity();
+    world.add_transform(pusher, -2.0, 0.5, 0.0);
+    world.add_rigid_body(pusher, false);
+    world.set_body_type(pusher, BodyType::Kinematic);
+    world.add_collider_aabb(pusher, 0.5, 0.5, 0.5);
+    let crate_id = cube(&mut world, Vec3::new(0.0, 0.5, 0.0));
+    run(&mut world, 10);
+
+    world.set_velocity(pusher, 2.0, 0.0, 0.0);
+    run(&mut world, 90);
+    let (wall, pushed) = (position(&world, pusher), position(&world, crate_id));
+    assert!(wall.x > 0.5, "le kinematic avance sans être arrêté : x = {}", wall.x);
+    assert!(pushed.x - wall.x > 0.95, "le cube est poussé devant : {} / {}", pushed.x, wall.x);
+    assert!((velocity(&world, pusher).x - 2.0).abs() < 1e-6);
+}
+
+#[test]
+fn body_switched_to_static_stops_interpolating() {
+    let mut world = WorldCore::new();
+    let falling = cube(&mut world, Vec3::new(0.0, 5.0, 0.0));
+    // Une frame et demie : le rendu est à mi-chemin entre les deux derniers pas.
+    world.update(FRAME_MS * 1.5);
+    assert!(!world.compute_render_matrix(falling).abs_diff_eq(world.compute_world_matrix(falling), 1e-6));
+
+    world.set_body_type(falling, BodyType::Static);
+    for _ in 0..3 {
+        world.update(FRAME_MS * 0.5);
+        assert!(
+            world.compute_render_matrix(falling).abs_diff_eq(world.compute_world_matrix(falling), 1e-6),
+            "le statique est rendu à sa position, sans interpolation"
+        );
+    }
+}
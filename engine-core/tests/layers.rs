@@ -0,0 +1,65 @@
+//! Couches de collision : deux colliders dont les masques s'excluent ne se résolvent pas
+//! et ne déclenchent pas de trigger.
+
+mod common;
+
+use common::*;
+use engine_core::ecs::Entity;
+use engine_core::{ALL_LAYERS, WorldCore};
+use glam::Vec3;
+
+const PLAYER: u32 = 1 << 1;
+const GHOST:  u32 = 1 << 2;
+
+/// Deux cubes en apesanteur lancés l'un vers l'autre selon X à 3 m/s.
+fn head_on() -> (WorldCore, Entity, Entity) {
+    let mut world = WorldCore::new();
+    world.set_gravity(Vec3::ZERO);
+    let left  = cube(&mut world, Vec3::new(-2.0, 0.0, 0.0));
+    let right = cube(&mut world, Vec3::new( 2.0, 0.0, 0.0));
+    world.set_velocity(left,   3.0, 0.0, 0.0);
+    world.set_velocity(right, -3.0, 0.0, 0.0);
+    (world, left, right)
+}
+
+#[test]
+fn masked_out_bodies_pass_through_each_other() {
+    let (mut world, left, right) = head_on();
+    // Un seul masque qui exclut l'autre suffit.
+    world.set_collider_layers(left, PLAYER, ALL_LAYERS & !GHOST);
+    world.set_collider_layers(right, GHOST, ALL_LAYERS);
+    run(&mut world, 90);
+
+    assert!(position(&world, left).x > 2.0 && position(&world, right).x < -2.0, "les cubes se sont croisés");
+    assert!(velocity(&world, left).abs_diff_eq(Vec3::new(3.0, 0.0, 0.0), 1e-4));
+    assert!(velocity(&world, right).abs_diff_eq(Vec3::new(-3.0, 0.0, 0.0), 1e-4));
+    assert!(world.drain_collision_events().is_empty(), "aucun contact");
+
+    // Témoin : couches par défaut, le choc les renvoie.
+    let (mut world, left, right) = head_on();
+    run(&mut world, 90);
+    assert!(position(&world, left).x < position(&world, right).x, "les cubes se heurtent");
+}
+
+#[test]
+fn masked_out_body_does_not_fire_a_trigger() {
+    let mut world = WorldCore::new();
+    let zone = world.create_entity();
+    world.add_transform(zone, 0.0, 0.0, 0.0);
+    world.add_collider_aabb(zone, 2.0, 1.0, 2.0);
+    world.set_collider_trigger(zone, true);
+    world.set_collider_layers(zone, 1, ALL_LAYERS & !GHOST);
+    let ghost = ball(&mut world, Vec3::new(-1.0, 3.0, 0.0), 0.3);
+    world.set_collider_layers(ghost, GHOST, ALL_LAYERS);
+    let player = ball(&mut world, Vec3::new(1.0, 3.0, 0.0), 0.3);
+    world.set_collider_layers(player, PLAYER, ALL_LAYERS);
+
+    let mut touched = Vec::new();
+    for _ in 0..90 {
+        world.update(FRAME_MS);
+        touched.extend(world.drain_collision_events().into_iter().map(|e| if e.a == zone { e.b } else { e.a }));
+    }
+    assert!(position(&world, ghost).y < -2.0, "la balle masquée a traversé la zone");
+    assert!(touched.contains(&player), "la balle non masquée déclenche le trigger");
+    assert!(!touched.contains(&ghost), "la balle masquée ne déclenche rien");
+}
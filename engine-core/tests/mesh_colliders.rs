@@ -0,0 +1,132 @@
+//! Colliders maillage (`add_mesh_collider`) : rampe praticable, pose transformée,
+//! raycast et triangles dégénérés.
+
+mod common;
+
+use common::*;
+use engine_core::ecs::Entity;
+use engine_core::{ALL_LAYERS, WorldCore};
+use glam::Vec3;
+
+/// Entité statique dont le collider est le mesh custom (`positions`, `indices`), posée en `at`.
+fn mesh_entity(world: &mut WorldCore, positions: &[Vec3], indices: &[u32], at: Vec3) -> Option<Entity> {
+    // 15 floats par sommet : seule la position compte pour la collision.
+    let vertices: Vec<f32> = positions.iter()
+        .flat_map(|p| [p.x, p.y, p.z].into_iter().chain(std::iter::repeat_n(0.0, 12)))
+        .collect();
+    let mesh = world.add_custom_mesh(&vertices, indices);
+    let id = world.create_entity();
+    world.add_transform(id, at.x, at.y, at.z);
+    world.add_mesh_renderer(id);
+    world.set_mesh_type(id, &format!("custom:{mesh}"));
+    world.add_rigid_body(id, true);
+    world.add_mesh_collider(id).then_some(id)
+}
+
+/// Palier en y = 0 pour x ∈ [-10, 0], puis rampe jusqu'à y = 2 en x = 10 (~11°), z ∈ [-3, 3].
+fn ramp(world: &mut WorldCore) -> Entity {
+    let positions = [
+        Vec3::new(-10.0, 0.0, -3.0), Vec3::new(-10.0, 0.0, 3.0),
+        Vec3::new(  0.0, 0.0, -3.0), Vec3::new(  0.0, 0.0, 3.0),
+        Vec3::new( 10.0, 2.0, -3.0), Vec3::new( 10.0, 2.0, 3.0),
+    ];
+    let indices = [0, 1, 2, 2, 1, 3, 2, 3, 4, 4, 3, 5];
+    mesh_entity(world, &positions, &indices, Vec3::ZERO).expect("rampe")
+}
+
+/// Carré unité horizontal centré sur l'origine.
+const QUAD: [Vec3; 4] = [
+    Vec3::new(-0.5, 0.0, -0.5), Vec3::new(-0.5, 0.0, 0.5),
+    Vec3::new( 0.5, 0.0, -0.5), Vec3::new( 0.5, 0.0, 0.5),
+];
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 2, 1, 3];
+
+#[test]
+fn character_walks_up_a_mesh_ramp() {
+    let mut world = WorldCore::new();
+    ramp(&mut world);
+    let walker = world.create_entity();
+    world.add_transform(walker, -5.0, 1.0, 0.0);
+    world.add_rigid_body(walker, false);
+    world.add_collider_capsule(walker, 0.3, 0.4);
+    world.set_character_controller(walker, 4.0, 40.0, 0.3, 1.0, 0.3, 45.0, 0.1, 0.1);
+    run(&mut world, 60);
+    let start = position(&world, walker);
+    assert!((start.y - 0.7).abs() < 0.05, "debout sur le palier, y = {}", start.y);
+
+    world.set_character_intent(walker, 1.0, 0.0, false);
+    for _ in 0..300 {
+        world.update(FRAME_MS);
+        let p = position(&world, walker);
+        // Hauteur du sol maillage sous le personnage.
+        let ground = p.x.clamp(0.0, 10.0) * 0.2;
+        assert!(p.y > ground + 0.6, "traverse la rampe en x = {} (y = {})", p.x, p.y);
+        if p.x > 9.0 {
+            break;
+        }
+    }
+    let top = position(&world, walker);
+    assert!(top.x > 9.0, "n'a pas monté la rampe : x = {}", top.x);
+    assert!(top.y > 2.0, "en haut de la rampe, y = {}", top.y);
+}
+
+#[test]
+fn body_rests_on_rotated_and_scaled_mesh() {
+    let mut world = WorldCore::new();
+    // Carré de 20 m retourné (les triangles ont deux faces) et tourné de 30° autour de Y.
+    let plate = mesh_entity(&mut world, &QUAD, &QUAD_INDICES, Vec3::new(0.0, 1.0, 0.0)).expect("plaque");
+    world.set_scale(plate, 20.0, 1.0, 20.0);
+    world.set_rotation(plate, 180.0, 30.0, 0.0);
+    // Hors de la plaque non mise à l'échelle (demi-côté 0.5), dessus une fois mise à l'échelle.
+    let resting = ball(&mut world, Vec3::new(6.0, 4.0, 5.0), 0.5);
+    run(&mut world, 120);
+
+    let p = position(&world, resting);
+    assert!((p.y - 1.5).abs() < 0.03, "posée sur la plaque, y = {}", p.y);
+    assert!(velocity(&world, resting).length() < 0.05);
+
+    // Au-delà du bord mis à l'échelle : tombe.
+    let outside = ball(&mut world, Vec3::new(16.0, 4.0, 0.0), 0.5);
+    run(&mut world, 120);
+    assert!(position(&world, outside).y < 0.0, "hors de la plaque");
+}
+
+#[test]
+fn raycast_hits_the_mesh_surface() {
+    let mut world = WorldCore::new();
+    let slope = ramp(&mut world);
+
+    let hit = world.raycast(Vec3::new(5.0, 10.0, 1.0), Vec3::NEG_Y, 100.0, ALL_LAYERS).expect("rampe");
+    assert_eq!(hit.entity, slope);
+    assert!((hit.distance - 9.0).abs() < 1e-3, "sol à y = 1 : distance {}", hit.distance);
+    assert!(hit.point.abs_diff_eq(Vec3::new(5.0, 1.0, 1.0), 1e-3));
+    assert!(hit.normal.abs_diff_eq(Vec3::new(-0.2, 1.0, 0.0).normalize(), 1e-3), "normale {:?}", hit.normal);
+
+    // Par-dessous : même triangle, normale tournée vers le rayon.
+    let below = world.raycast(Vec3::new(-5.0, -3.0, 0.0), Vec3::Y, 100.0, ALL_LAYERS).expect("palier");
+    assert!((below.distance - 3.0).abs() < 1e-3);
+    assert!(below.normal.abs_diff_eq(Vec3::NEG_Y, 1e-3));
+
+    assert!(world.raycast(Vec3::new(5.0, 10.0, 4.0), Vec3::NEG_Y, 100.0, ALL_LAYERS).is_none(), "à côté");
+}
+
+#[test]
+fn degenerate_triangles_are_skipped() {
+    let mut world = WorldCore::new();
+    let positions = [
+        QUAD[0], QUAD[1], QUAD[2], QUAD[3],
+        Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0),
+    ];
+    // Sommet répété, points alignés, indice hors limites, le carré, puis un triplet incomplet.
+    let indices = [0, 0, 1, 4, 5, 6, 1, 2, 99, 0, 1, 2, 2, 1, 3, 7];
+    let plate = mesh_entity(&mut world, &positions, &indices, Vec3::ZERO).expect("carré valide");
+    world.set_scale(plate, 10.0, 1.0, 10.0);
+    let resting = cube(&mut world, Vec3::new(0.0, 2.0, 0.0));
+    run(&mut world, 120);
+    assert!((position(&world, resting).y - 0.5).abs() < 0.03, "posé sur les triangles valides");
+
+    // Rien que des triangles dégénérés : pas de collider.
+    let flat = [Vec3::ZERO, Vec3::X, Vec3::X * 2.0];
+    assert!(mesh_entity(&mut world, &flat, &[0, 1, 2, 0, 0, 0], Vec3::ZERO).is_none());
+    assert!(mesh_entity(&mut world, &flat, &[], Vec3::ZERO).is_none());
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use engine_core::{ALL_LAYERS, WorldCore};
+use engine_core::ecs::Entity;
+use glam::{Quat, Vec3};
+
+/// Boîte statique d'1 m de côté centrée en `at`.
+fn wall(world: &mut WorldCore, at: Vec3) -> Entity {
+    let id = world.create_entity();
+    world.add_transform(id, at.x, at.y, at.z);
+    world.add_collider_aabb(id, 0.5, 0.5, 0.5);
+    id
+}
+
+/// Trois boîtes alignées sur +X, faces avant à x = 2.5, 5.5 et 8.5.
+fn row() -> (WorldCore, [Entity; 3]) {
+    let mut world = WorldCore::new();
+    let ids = [3.0, 6.0, 9.0].map(|x| wall(&mut world, Vec3::new(x, 0.0, 0.0)));
+    (world, ids)
+}
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!(a.abs_diff_eq(b, 1e-3), "{a:?} != {b:?}");
+}
+
+#[test]
+fn ray_hits_the_nearest_collider() {
+    let (world, [near, _, far]) = row();
+    let hit = world.raycast(Vec3::ZERO, Vec3::X * 3.0, 100.0, ALL_LAYERS).expect("impact");
+    assert_eq!(hit.entity, near);
+    assert!((hit.distance - 2.5).abs() < 1e-4);
+    assert_close(hit.point, Vec3::new(2.5, 0.0, 0.0));
+    assert_close(hit.normal, Vec3::NEG_X);
+
+    let back = world.raycast(Vec3::new(12.0, 0.0, 0.0), Vec3::NEG_X, 100.0, ALL_LAYERS).expect("impact");
+    assert_eq!(back.entity, far);
+    assert_close(back.normal, Vec3::X);
+
+    assert!(world.raycast(Vec3::ZERO, Vec3::X, 2.0, ALL_LAYERS).is_none(), "hors de portée");
+    assert!(world.raycast(Vec3::ZERO, Vec3::Y, 100.0, ALL_LAYERS).is_none());
+}
+
+#[test]
+fn ray_skips_triggers() {
+    let (mut world, [near, middle, _]) = row();
+    world.set_collider_trigger(near, true);
+    let hit = world.raycast(Vec3::ZERO, Vec3::X, 100.0, ALL_LAYERS).expect("impact");
+    assert_eq!(hit.entity, middle);
+    assert!((hit.distance - 5.5).abs() < 1e-4);
+}
+
+#[test]
+fn ray_respects_layer_mask() {
+    let (mut world, [near, middle, far]) = row();
+    world.set_collider_layers(near, 1 << 1, ALL_LAYERS);
+    world.set_collider_layers(middle, 1 << 2, ALL_LAYERS);
+    let hit = |mask| world.raycast(Vec3::ZERO, Vec3::X, 100.0, mask).map(|h| h.entity);
+    assert_eq!(hit(1 << 2), Some(middle));
+    assert_eq!(hit(!(1 << 1) & !(1 << 2)), Some(far));
+    assert_eq!(hit(1 << 1 | 1 << 2), Some(near));
+    assert_eq!(hit(1 << 3), None);
+}
+
+#[test]
+fn ray_normal_and_distance_on_round_and_rotated_shapes() {
+    let mut world = WorldCore::new();
+    let ball = world.create_entity();
+    world.add_transform(ball, 0.0, 0.0, 0.0);
+    world.add_collider_sphere(ball, 1.0);
+    let hit = world.raycast(Vec3::new(0.0, 5.0, 0.0), Vec3::NEG_Y, 100.0, ALL_LAYERS).expect("sphère");
+    assert!((hit.distance - 4.0).abs() < 1e-4);
+    assert_close(hit.normal, Vec3::Y);
+
+    // Boîte tournée de 45° autour de Y : arête verticale vers -X à x = 10 - √2/2.
+    let diamond = wall(&mut world, Vec3::new(10.0, 0.0, 0.0));
+    world.set_rotation(diamond, 0.0, 45.0, 0.0);
+    let hit = world.raycast(Vec3::new(5.0, 0.0, 0.2), Vec3::X, 100.0, ALL_LAYERS).expect("boîte tournée");
+    assert_eq!(hit.entity, diamond);
+    assert_close(hit.normal, Quat::from_rotation_y(45_f32.to_radians()) * Vec3::NEG_X);
+    let edge = 10.0 - std::f32::consts::FRAC_1_SQRT_2;
+    assert!((hit.distance - (edge - 5.0 + 0.2)).abs() < 1e-3, "distance {}", hit.distance);
+}
+
+#[test]
+fn shape_casts_stop_at_the_nearest_face() {
+    let (world, [near, ..]) = row();
+    let hit = world.sphere_cast(Vec3::ZERO, 0.5, Vec3::X, 100.0, ALL_LAYERS).expect("sphère");
+    assert_eq!(hit.entity, near);
+    assert!((hit.distance - 2.0).abs() < 1e-2, "distance {}", hit.distance);
+    assert_close(hit.normal, Vec3::NEG_X);
+
+    // Passe au-dessus des boîtes : seul le box-cast, plus haut que le rayon, les accroche.
+    let origin = Vec3::new(0.0, 0.9, 0.0);
+    assert!(world.raycast(origin, Vec3::X, 100.0, ALL_LAYERS).is_none());
+    let hit = world.box_cast(origin, Vec3::splat(0.5), Quat::IDENTITY, Vec3::X, 100.0, ALL_LAYERS).expect("boîte");
+    assert_eq!(hit.entity, near);
+    assert!((hit.distance - 2.0).abs() < 1e-2, "distance {}", hit.distance);
+}
+
+#[test]
+fn queries_follow_moved_and_removed_colliders() {
+    let (mut world, [near, middle, _]) = row();
+    assert_eq!(world.raycast(Vec3::ZERO, Vec3::X, 100.0, ALL_LAYERS).map(|h| h.entity), Some(near));
+
+    world.set_position(near, 0.0, 5.0, 0.0);
+    assert_eq!(world.raycast(Vec3::ZERO, Vec3::X, 100.0, ALL_LAYERS).map(|h| h.entity), Some(middle));
+    assert_eq!(world.raycast(Vec3::ZERO, Vec3::Y, 100.0, ALL_LAYERS).map(|h| h.entity), Some(near));
+
+    world.remove_entity(middle);
+    let hit = world.raycast(Vec3::ZERO, Vec3::X, 100.0, ALL_LAYERS).expect("impact");
+    assert!((hit.distance - 8.5).abs() < 1e-4, "boîte supprimée encore touchée");
+}
+
+#[test]
+fn ray_finds_nearest_among_many_colliders() {
+    let mut world = WorldCore::new();
+    let mut grid = Vec::new();
+    for x in 0..20 {
+        for z in 0..20 {
+            grid.push(wall(&mut world, Vec3::new(x as f32 * 2.0, 0.0, z as f32 * 2.0)));
+        }
+    }
+    // Diagonale descendante : touche le dessus de la boîte (7, 7) avant toute autre.
+    let target = Vec3::new(14.0, 0.5, 14.0);
+    let origin = target + Vec3::new(-3.0, 3.0, 0.0);
+    let hit = world.raycast(origin, target - origin, 100.0, ALL_LAYERS).expect("impact");
+    assert_eq!(hit.entity, grid[7 * 20 + 7]);
+    assert_close(hit.point, target);
+    assert_close(hit.normal, Vec3::Y);
+}
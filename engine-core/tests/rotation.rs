@@ -0,0 +1,38 @@
+mod common;
+
+use common::*;
+use engine_core::WorldCore;
+use engine_core::ecs::Entity;
+use glam::Vec3;
+
+/// Cube en apesanteur à l'origine, frappé selon +X par une balle à la hauteur `offset`.
+fn strike(offset: f32) -> (WorldCore, Entity) {
+    let mut world = WorldCore::new();
+    world.set_gravity(Vec3::ZERO);
+    let target = cube(&mut world, Vec3::ZERO);
+    let bullet = ball(&mut world, Vec3::new(-1.0, offset, 0.0), 0.2);
+    world.set_velocity(bullet, 5.0, 0.0, 0.0);
+    run(&mut world, 30);
+    (world, target)
+}
+
+fn angular_velocity(world: &WorldCore, id: Entity) -> Vec3 {
+    Vec3::from(world.get_angular_velocity(id))
+}
+
+#[test]
+fn off_centre_impulse_spins_the_body() {
+    let (world, target) = strike(0.3);
+    assert!(velocity(&world, target).x > 1.0, "le cube est poussé");
+    // Poussée +X au-dessus du centre : couple r × F selon -Z.
+    let w = angular_velocity(&world, target);
+    assert!(w.z < -0.5, "rotation attendue autour de -Z : {w:?}");
+    assert!(w.x.abs() < 1e-3 && w.y.abs() < 1e-3, "{w:?}");
+}
+
+#[test]
+fn centred_impulse_does_not_spin() {
+    let (world, target) = strike(0.0);
+    assert!(velocity(&world, target).x > 1.0);
+    assert!(angular_velocity(&world, target).length() < 1e-3);
+}
@@ -0,0 +1,121 @@
+//! Sommeil des corps au repos, par îlots de contact.
+
+mod common;
+
+use common::{ball, cube, floor, position, run, velocity};
+use engine_core::ecs::Entity;
+use engine_core::{CollisionPhase, WorldCore};
+use glam::Vec3;
+
+/// Pile de `n` cubes posée sur le sol en x = 0.
+fn stack(world: &mut WorldCore, n: usize) -> Vec<Entity> {
+    (0..n).map(|i| cube(world, Vec3::new(0.0, 0.5 + i as f32, 0.0))).collect()
+}
+
+fn asleep(world: &WorldCore, ids: &[Entity]) -> Vec<bool> {
+    ids.iter().map(|&id| world.is_sleeping(id)).collect()
+}
+
+/// Pile endormie : elle a passé `SLEEP_DELAY` (0.5 s) au repos.
+fn sleeping_stack(n: usize) -> (WorldCore, Vec<Entity>) {
+    let mut world = WorldCore::new();
+    floor(&mut world);
+    let ids = stack(&mut world, n);
+    run(&mut world, 120);
+    assert_eq!(asleep(&world, &ids), vec![true; n]);
+    (world, ids)
+}
+
+#[test]
+fn stack_falls_asleep_together_after_delay() {
+    let mut world = WorldCore::new();
+    floor(&mut world);
+    let ids = stack(&mut world, 4);
+
+    // Moins de 0.5 s de repos : tout le monde est encore éveillé.
+    run(&mut world, 20);
+    assert_eq!(asleep(&world, &ids), vec![false; 4]);
+
+    // Les corps s'endorment au même pas.
+    let mut frames = 20;
+    while !world.is_sleeping(ids[0]) {
+        run(&mut world, 1);
+        frames += 1;
+        assert!(frames < 120, "la pile ne s'endort pas");
+    }
+    assert_eq!(asleep(&world, &ids), vec![true; 4]);
+    assert!(frames >= 30, "endormie après {frames} frames, avant SLEEP_DELAY");
+
+    // Endormie, elle ne bouge plus du tout.
+    let before: Vec<Vec3> = ids.iter().map(|&id| position(&world, id)).collect();
+    run(&mut world, 120);
+    let after: Vec<Vec3> = ids.iter().map(|&id| position(&world, id)).collect();
+    assert_eq!(before, after);
+    assert!(ids.iter().all(|&id| velocity(&world, id) == Vec3::ZERO));
+}
+
+#[test]
+fn impulse_wakes_the_whole_island() {
+    let (mut world, ids) = sleeping_stack(3);
+    world.apply_impulse(ids[2], Vec3::new(3.0, 0.0, 0.0));
+    assert_eq!(asleep(&world, &ids), vec![false; 3]);
+    run(&mut world, 10);
+    assert!(position(&world, ids[2]).x > 0.3, "le cube poussé glisse");
+}
+
+#[test]
+fn set_velocity_and_set_position_wake_the_island() {
+    let (mut world, ids) = sleeping_stack(3);
+    world.set_velocity(ids[0], 0.0, 0.0, 0.0);
+    assert_eq!(asleep(&world, &ids), vec![false; 3]);
+
+    let (mut world, ids) = sleeping_stack(3);
+    world.set_position(ids[1], 5.0, 0.5, 0.0);
+    assert_eq!(asleep(&world, &ids), vec![false; 3]);
+    run(&mut world, 60);
+    assert!(position(&world, ids[2]).y < 2.0, "le cube du haut retombe sur celui du bas");
+}
+
+#[test]
+fn contact_with_awake_body_wakes_the_island() {
+    let (mut world, ids) = sleeping_stack(3);
+    let projectile = ball(&mut world, Vec3::new(-5.0, 2.5, 0.0), 0.3);
+    world.set_rigid_body_material(projectile, 5.0, 0.0, 0.5);
+    world.set_velocity(projectile, 15.0, 0.0, 0.0);
+    run(&mut world, 30);
+    assert_eq!(asleep(&world, &ids), vec![false; 3]);
+    assert!(position(&world, ids[2]).x > 0.3);
+}
+
+#[test]
+fn sleeping_contacts_keep_emitting_stay() {
+    let (mut world, ids) = sleeping_stack(2);
+    world.drain_collision_events();
+    run(&mut world, 60);
+    let events = world.drain_collision_events();
+    assert!(events.iter().all(|e| e.phase == CollisionPhase::Stay), "pas d'Exit pendant le sommeil");
+    let touching = |a: Entity, b: Entity| {
+        events.iter().filter(|e| (e.a, e.b) == (a, b) || (e.a, e.b) == (b, a)).count()
+    };
+    assert_eq!(touching(ids[0], ids[1]), 60, "un Stay par pas");
+}
+
+#[test]
+fn removing_a_sleeper_drops_its_resting_contacts() {
+    let (mut world, ids) = sleeping_stack(3);
+    run(&mut world, 1);
+    world.drain_collision_events();
+
+    world.remove_entity(ids[1]);
+    assert_eq!(asleep(&world, &[ids[0], ids[2]]), vec![false; 2], "ses voisins se réveillent");
+    run(&mut world, 1);
+    let events = world.drain_collision_events();
+    let involving = |phase: CollisionPhase| {
+        events.iter().filter(|e| e.phase == phase && (e.a == ids[1] || e.b == ids[1])).count()
+    };
+    assert_eq!(involving(CollisionPhase::Exit), 2, "Exit avec les deux voisins");
+    assert_eq!(involving(CollisionPhase::Stay), 0);
+
+    run(&mut world, 30);
+    assert!(world.drain_collision_events().iter().all(|e| e.a != ids[1] && e.b != ids[1]));
+}
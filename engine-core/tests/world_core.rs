@@ -0,0 +1,181 @@
+//! `WorldCore` natif : scènes (dont l'ancien format de rotation), chute sur un collider statique,
+//! noms et tags, composants de jeu enregistrés.
+
+mod common;
+
+use common::{cube, floor, position, run, velocity};
+use engine_core::ecs::{Collider, ColliderShape, Entity, Parent, RigidBody, Transform};
+use engine_core::WorldCore;
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn body_falls_and_lands_on_static_collider() {
+    let mut world = WorldCore::new();
+    floor(&mut world);
+    let crate_id = cube(&mut world, Vec3::new(0.0, 3.0, 0.0));
+
+    run(&mut world, 10);
+    assert!(position(&world, crate_id).y < 3.0, "la gravité fait tomber le corps");
+
+    run(&mut world, 110);
+    let p = position(&world, crate_id);
+    assert!((p.y - 0.5).abs() < 0.03, "posé sur le sol, y = {}", p.y);
+    assert!(velocity(&world, crate_id).length() < 0.05);
+    assert!(world.components().get::<RigidBody>(crate_id).unwrap().on_ground);
+}
+
+#[test]
+fn scene_round_trip() {
+    let mut world = WorldCore::new();
+    let ground = floor(&mut world);
+    world.set_entity_name(ground, "Ground".into());
+    let parent = cube(&mut world, Vec3::new(1.0, 2.0, 3.0));
+    world.set_entity_name(parent, "Crate".into());
+    world.set_tag(parent, "loot");
+    world.set_rigid_body_material(parent, 4.0, 0.3, 0.8);
+    let child = world.create_entity();
+    world.add_transform(child, 0.0, 1.0, 0.0);
+    world.add_collider_sphere(child, 0.25);
+    world.set_parent(child, parent);
+    world.set_gravity(Vec3::new(0.0, -3.0, 0.0));
+
+    let json = world.save_scene();
+    let mut loaded = WorldCore::new();
+    let ids = loaded.load_scene(&json).expect("scène valide");
+    assert_eq!(ids.len(), 3);
+    assert_eq!(loaded.gravity(), Vec3::new(0.0, -3.0, 0.0));
+
+    let crate_id = loaded.get_entity_by_tag("loot").expect("tag conservé");
+    assert_eq!(loaded.get_entity_name(crate_id), "Crate");
+    assert_eq!(position(&loaded, crate_id), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(loaded.get_rigid_body_material(crate_id), [4.0, 0.3, 0.8]);
+
+    let child_id = loaded.get_children(crate_id)[0];
+    assert_eq!(loaded.components().get::<Parent>(child_id).unwrap().parent_id, crate_id);
+    assert!(matches!(loaded.components().get::<Collider>(child_id).unwrap().shape, ColliderShape::Sphere { radius } if radius == 0.25));
+
+    // Sauver la scène rechargée redonne le même document.
+    assert_eq!(loaded.save_scene(), json);
+}
+
+fn rotation(world: &WorldCore, id: Entity) -> Quat {
+    world.components().get::<Transform>(id).expect("transform").rotation
+}
+
+#[test]
+fn legacy_euler_rotation_loads_as_quaternion() {
+    let mut world = WorldCore::new();
+    let id = world.create_entity();
+    world.add_transform(id, 0.0, 0.0, 0.0);
+    world.set_tag(id, "rotated");
+    let scene: serde_json::Value = serde_json::from_str(&world.save_scene()).expect("json");
+    let load = |value: serde_json::Value| {
+        let mut scene = scene.clone();
+        scene["entities"][0]["transform"]["rotation"] = value;
+        let mut loaded = WorldCore::new();
+        loaded.load_scene(&scene.to_string()).expect("scène valide");
+        rotation(&loaded, loaded.get_entity_by_tag("rotated").expect("tag conservé"))
+    };
+
+    // Ancien format : angles d'Euler XYZ en degrés.
+    let q = load(serde_json::json!([0.0, 90.0, 0.0]));
+    assert!(q.abs_diff_eq(Quat::from_rotation_y(90_f32.to_radians()), 1e-5), "{q:?}");
+    let q = load(serde_json::json!([30.0, 45.0, 60.0]));
+    let expected = Quat::from_rotation_x(30_f32.to_radians())
+        * Quat::from_rotation_y(45_f32.to_radians())
+        * Quat::from_rotation_z(60_f32.to_radians());
+    assert!(q.abs_diff_eq(expected, 1e-5), "{q:?} != {expected:?}");
+
+    // Quaternion non normé : normalisé ; nul : identité.
+    let q = load(serde_json::json!([0.0, 0.0, 2.0, 0.0]));
+    assert!(q.abs_diff_eq(Quat::from_rotation_z(180_f32.to_radians()), 1e-5), "{q:?}");
+    assert_eq!(load(serde_json::json!([0.0, 0.0, 0.0, 0.0])), Quat::IDENTITY);
+}
+
+#[test]
+fn name_and_tag_lookups() {
+    let mut world = WorldCore::new();
+    let player = world.create_entity();
+    let enemy  = world.create_entity();
+    world.set_entity_name(player, "Hero".into());
+    world.set_tag(player, "player");
+    world.set_tag(enemy, "enemy");
+
+    assert_eq!(world.get_entity_name(player), "Hero");
+    assert_eq!(world.get_entity_name(enemy), format!("Entity {}", enemy.index()));
+    assert_eq!(world.get_entity_by_tag("player"), Some(player));
+    assert_eq!(world.get_entity_by_tag("enemy"), Some(enemy));
+    assert_eq!(world.get_tag(enemy), "enemy");
+    assert_eq!(world.get_entity_by_tag("boss"), None);
+
+    world.remove_entity(enemy);
+    assert_eq!(world.get_entity_by_tag("enemy"), None);
+    assert_eq!(world.get_tag(player), "player");
+}
+
+/// Composant de jeu sérialisé dans la scène.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Health {
+    hp:  u32,
+    max: u32,
+}
+
+/// Composant de jeu non sérialisé.
+struct Cooldown(f32);
+
+fn register_game_components(world: &mut WorldCore) {
+    world.components_mut().register_serialized::<Health>("health");
+    world.components_mut().register::<Cooldown>();
+}
+
+#[test]
+fn registered_component_survives_scene_round_trip() {
+    let mut world = WorldCore::new();
+    register_game_components(&mut world);
+    let hero = cube(&mut world, Vec3::ZERO);
+    world.set_tag(hero, "hero");
+    world.components_mut().insert(hero, Health { hp: 7, max: 10 });
+    world.components_mut().insert(hero, Cooldown(0.5));
+    let json = world.save_scene();
+
+    let mut loaded = WorldCore::new();
+    register_game_components(&mut loaded);
+    loaded.load_scene(&json).expect("scène valide");
+    let hero = loaded.get_entity_by_tag("hero").expect("tag conservé");
+    assert_eq!(loaded.components().get::<Health>(hero), Some(&Health { hp: 7, max: 10 }));
+    assert!(loaded.components().get::<Cooldown>(hero).is_none(), "non sérialisé");
+    assert_eq!(loaded.save_scene(), json);
+
+    // Sans enregistrement, la clé est ignorée et le reste de l'entité se charge.
+    let mut bare = WorldCore::new();
+    bare.load_scene(&json).expect("scène valide");
+    let hero = bare.get_entity_by_tag("hero").expect("tag conservé");
+    assert!(bare.components().get::<Transform>(hero).is_some());
+}
+
+#[test]
+fn removal_clears_every_registered_storage() {
+    let mut world = WorldCore::new();
+    register_game_components(&mut world);
+    let spawn = |world: &mut WorldCore| {
+        let id = cube(world, Vec3::ZERO);
+        world.components_mut().insert(id, Health { hp: 1, max: 1 });
+        world.components_mut().insert(id, Cooldown(1.0));
+        id
+    };
+    let removed = spawn(&mut world);
+    let cleared = spawn(&mut world);
+
+    world.remove_entity(removed);
+    assert!(world.components().get::<Health>(removed).is_none());
+    assert!(world.components().get::<Cooldown>(removed).is_none());
+    assert!(world.components().ids().all(|id| id != removed), "plus aucun composant");
+    assert_eq!(world.components().get::<Cooldown>(cleared).map(|c| c.0), Some(1.0), "l'autre entité intacte");
+
+    // `load_scene` vide la scène courante, composants de jeu compris.
+    world.load_scene(&WorldCore::new().save_scene()).expect("scène vide");
+    assert!(world.components().storage::<Health>().is_empty());
+    assert!(world.components().storage::<Cooldown>().is_empty());
+    assert!(world.components().ids().all(|id| id != cleared));
+}
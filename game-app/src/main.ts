@@ -342,7 +342,8 @@ world.set_emissive(skyboxId, 2.0, 2.0, 2.5);
 const player = world.create_entity();
 world.add_transform(player, 0, 2, 0);
 world.add_rigid_body(player, false);
-world.add_collider_aabb(player, 0.4, 0.9, 0.4);
+world.add_collider_capsule(player, 0.4, 0.5);
+world.set_ccd(player, true); // pas de traversée du sol après une longue chute
 world.set_player(player);
 
 // ── 6. Logique de Jeu (Projectiles & Ennemis) ─────────────────────────────